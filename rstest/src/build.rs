@@ -0,0 +1,250 @@
+//! Build script helpers to recompile your tests when the files that match a
+//! `#[files(...)]` glob are added or removed.
+//!
+//! `rstest` resolves the globs when it expands the tests, so cargo doesn't know that it
+//! should expand them again if a new file appears. Add `rstest` with the `build` feature to
+//! your `[build-dependencies]` and call [`rerun_if_files_changed`] in your `build.rs` with the
+//! same globs that you use in the tests:
+//!
+//! ```no_run
+//! // In your build.rs's main()
+//! rstest::build::rerun_if_files_changed(&["tests/corpus/**/*.txt"]);
+//! ```
+//!
+//! Take care that if your build script prints any `cargo:rerun-if-changed` directive,
+//! cargo doesn't rerun it when other files change: if you need it, add the other paths
+//! too.
+//!
+//! The globs are resolved like in `#[files(...)]`: from the crate's default base folder
+//! (the `base` key in the `[package.metadata.rstest]` table of your `Cargo.toml`, or the
+//! crate root). Use [`rerun_if_files_changed_in`] for the globs that use the `base` option.
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+};
+
+/// The folder where the globs are resolved: the same values of the `base` option of
+/// `#[files(...)]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Base {
+    /// The crate root (`CARGO_MANIFEST_DIR`): `base = "crate"`
+    Crate,
+    /// The workspace root: `base = "workspace"`
+    Workspace,
+    /// The build script output folder (`OUT_DIR`): `base = "out_dir"`
+    OutDir,
+    /// The folder in the given environment variable, relative to the crate root:
+    /// `base = env("VAR")`
+    Env(String),
+}
+
+impl Base {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "crate" => Some(Self::Crate),
+            "workspace" => Some(Self::Workspace),
+            "out_dir" => Some(Self::OutDir),
+            _ => None,
+        }
+    }
+
+    /// The crate's default base: the `base` key in the `[package.metadata.rstest]` table.
+    fn crate_default(root: &Path) -> Result<Self, String> {
+        let value = match manifest(root).as_ref().and_then(|m| {
+            m.get("package")?
+                .get("metadata")?
+                .get("rstest")?
+                .get("base")
+                .cloned()
+        }) {
+            Some(value) => value,
+            None => return Ok(Self::Crate),
+        };
+        let base = match &value {
+            toml::Value::String(name) => Self::from_name(name),
+            toml::Value::Table(t) if t.len() == 1 => t
+                .get("env")
+                .and_then(|v| v.as_str())
+                .map(|v| Self::Env(v.to_owned())),
+            _ => None,
+        };
+        base.ok_or_else(|| {
+            format!(
+                r#"Invalid base `{value}` in [package.metadata.rstest]: use "crate", "workspace", "out_dir" or {{ env = "VAR" }}"#
+            )
+        })
+    }
+
+    fn resolve(&self, root: &Path) -> Result<PathBuf, String> {
+        let var = |name: &str| {
+            env::var(name).map_err(|_| format!("The environment variable {name} is not defined"))
+        };
+        match self {
+            Self::Crate => Ok(root.to_owned()),
+            Self::Workspace => Ok(root
+                .ancestors()
+                .find(|dir| {
+                    manifest(dir)
+                        .map(|m| m.contains_key("workspace"))
+                        .unwrap_or_default()
+                })
+                .unwrap_or(root)
+                .to_owned()),
+            Self::OutDir => var("OUT_DIR").map(PathBuf::from),
+            Self::Env(name) => var(name).map(|dir| root.join(dir)),
+        }
+    }
+}
+
+/// Read and parse the `Cargo.toml` in the given folder, if any.
+fn manifest(dir: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()?
+        .parse()
+        .ok()
+}
+
+fn manifest_dir() -> PathBuf {
+    env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .expect("rstest::build helpers should be called from a build script")
+}
+
+/// Tell cargo to rerun the build script, and so recompile the crate's tests, when
+/// something changes in the folders that contain the files matched by the given globs.
+/// The globs are relative to the crate's default base folder like in `#[files(...)]`.
+///
+/// # Panics
+///
+/// If the `[package.metadata.rstest]` base is invalid or its folder cannot be resolved.
+pub fn rerun_if_files_changed(globs: &[&str]) {
+    let root = manifest_dir();
+    let base = Base::crate_default(&root).unwrap_or_else(|e| panic!("{e}"));
+    rerun_if_files_changed_in(base, globs)
+}
+
+/// Like [`rerun_if_files_changed`] but the globs are relative to the given `base` folder:
+/// use it for the `#[files(...)]` globs that use the `base` option.
+///
+/// # Panics
+///
+/// If the `base` folder cannot be resolved.
+pub fn rerun_if_files_changed_in(base: Base, globs: &[&str]) {
+    let root = manifest_dir();
+    if let Base::Env(name) = &base {
+        println!("cargo:rerun-if-env-changed={name}");
+    }
+    let base_dir = base.resolve(&root).unwrap_or_else(|e| panic!("{e}"));
+    for directive in rerun_if_changed_directives(&base_dir, globs) {
+        println!("{directive}");
+    }
+}
+
+fn rerun_if_changed_directives(root: &Path, globs: &[&str]) -> Vec<String> {
+    let mut folders = globs
+        .iter()
+        .map(|glob| root.join(glob_base(glob)))
+        .collect::<Vec<_>>();
+    folders.sort();
+    folders.dedup();
+    folders
+        .into_iter()
+        .map(|folder| format!("cargo:rerun-if-changed={}", folder.display()))
+        .collect()
+}
+
+/// The longest folder without wildcards that contains all the glob's matches.
+fn glob_base(glob: &str) -> PathBuf {
+    let path = Path::new(glob);
+    let components = path.components().collect::<Vec<_>>();
+    let mut base = components
+        .iter()
+        .take_while(|c| match c {
+            Component::Normal(segment) => !segment
+                .to_string_lossy()
+                .contains(['*', '?', '[', ']', '{', '}']),
+            _ => true,
+        })
+        .collect::<PathBuf>();
+    // Without wildcards the last component is the file itself
+    if base.components().count() == components.len() {
+        base.pop();
+    }
+    base
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn use_the_folder_before_the_first_wildcard() {
+        assert_eq!(
+            PathBuf::from("tests/corpus"),
+            glob_base("tests/corpus/**/*.txt")
+        );
+        assert_eq!(PathBuf::from("tests"), glob_base("tests/c*/a.txt"));
+        assert_eq!(PathBuf::from("../shared"), glob_base("../shared/[ab].txt"));
+        assert_eq!(PathBuf::from(""), glob_base("*.txt"));
+    }
+
+    #[test]
+    fn use_the_parent_folder_of_a_glob_without_wildcards() {
+        assert_eq!(
+            PathBuf::from("tests/data"),
+            glob_base("tests/data/input.txt")
+        );
+    }
+
+    fn write_manifest(dir: &Path, content: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("Cargo.toml"), content).unwrap();
+    }
+
+    #[test]
+    fn read_the_crate_default_base() {
+        let root = env::temp_dir().join("rstest_build_default_base");
+        let base = |content: &str| {
+            write_manifest(&root, content);
+            Base::crate_default(&root)
+        };
+
+        assert_eq!(Ok(Base::Crate), base("[package]\nname = \"a\"\n"));
+        assert_eq!(
+            Ok(Base::Workspace),
+            base("[package.metadata.rstest]\nbase = \"workspace\"\n")
+        );
+        assert_eq!(
+            Ok(Base::Env("VAR".to_owned())),
+            base("[package.metadata.rstest]\nbase = { env = \"VAR\" }\n")
+        );
+        assert!(base("[package.metadata.rstest]\nbase = \"other\"\n").is_err());
+    }
+
+    #[test]
+    fn resolve_the_workspace_root() {
+        let workspace = env::temp_dir().join("rstest_build_workspace");
+        let root = workspace.join("member");
+        write_manifest(&workspace, "[workspace]\nmembers = [\"member\"]\n");
+        write_manifest(&root, "[package]\nname = \"member\"\n");
+
+        assert_eq!(Ok(workspace), Base::Workspace.resolve(&root));
+        assert_eq!(Ok(root.clone()), Base::Crate.resolve(&root));
+    }
+
+    #[test]
+    fn print_a_directive_for_each_folder() {
+        let directives = rerun_if_changed_directives(
+            Path::new("/root"),
+            &["tests/b/*.txt", "tests/a/**/*.txt", "tests/a/*.json"],
+        );
+
+        assert_eq!(
+            vec![
+                "cargo:rerun-if-changed=/root/tests/a".to_owned(),
+                "cargo:rerun-if-changed=/root/tests/b".to_owned(),
+            ],
+            directives
+        );
+    }
+}
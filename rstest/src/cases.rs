@@ -0,0 +1,78 @@
+use std::fmt::Display;
+
+use crate::outcome::{isolated, report, Outcome};
+
+/// Call `test` for each named case, catching its panics and releasing its resources, and
+/// fail at the end if some cases failed.
+///
+/// # Panics
+///
+/// If there are no cases or `test` fails for some of them: the message lists all the
+/// failed cases.
+pub fn execute_cases<N: Display, A, O: Outcome>(
+    cases: impl IntoIterator<Item = (N, A)>,
+    mut test: impl FnMut(A) -> O,
+) -> O {
+    let mut total = 0;
+    let failures = cases
+        .into_iter()
+        .inspect(|_| total += 1)
+        .filter_map(|(name, args)| isolated(|| test(args)).err().map(|message| (name, message)))
+        .collect::<Vec<_>>();
+    if total == 0 {
+        panic!("No case found");
+    }
+    if !failures.is_empty() {
+        panic!("{}", report(total, "cases", &failures));
+    }
+    O::success()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outcome::panic_message;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn run_all_the_cases() {
+        let mut sum = 0;
+
+        execute_cases(vec![("one", 1), ("two", 2), ("three", 3)], |n| sum += n);
+
+        assert_eq!(6, sum);
+    }
+
+    #[test]
+    fn report_all_the_failed_cases() {
+        let message = catch_unwind(|| {
+            execute_cases(
+                (0..4).map(|n| (format!("case_{n}"), n)),
+                |n| -> Result<(), String> {
+                    assert_ne!(1, n, "one is wrong");
+                    if n == 3 {
+                        Err("three is wrong".to_owned())
+                    } else {
+                        Ok(())
+                    }
+                },
+            )
+        })
+        .map_err(panic_message)
+        .unwrap_err();
+
+        assert!(message.starts_with("2 of 4 cases failed:"), "{message}");
+        assert!(message.contains("case_1: assertion"), "{message}");
+        assert!(
+            message.contains(r#"case_3: Error: "three is wrong""#),
+            "{message}"
+        );
+        assert!(!message.contains("case_0"), "{message}");
+    }
+
+    #[test]
+    #[should_panic(expected = "No case found")]
+    fn panic_if_no_cases() {
+        execute_cases(Vec::<(String, u32)>::new(), |_| ());
+    }
+}
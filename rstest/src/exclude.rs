@@ -0,0 +1,71 @@
+//! Runtime support for `#[exclude_when(condition)]`.
+//!
+//! The combinations that the condition excludes at compile time are rendered as ignored
+//! tests. The ones where the condition cannot be evaluated at compile time run and check it
+//! when their arguments are resolved: the excluded ones write it on the standard error and
+//! are skipped, so the test harness counts them as passed (`ok`).
+use std::{cell::Cell, future::Future};
+
+use crate::outcome::{self, Outcome};
+
+thread_local! {
+    static SKIPPED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Skip the combination excluded at runtime by `condition`.
+pub fn skip<O: Outcome>(condition: &'static str) -> O {
+    SKIPPED.with(|skipped| skipped.set(true));
+    outcome::skip(format_args!("excluded by `{condition}`"))
+}
+
+/// Call `test` and tell whether it skipped an excluded combination: the wrappers that
+/// check the test's outcome (like `#[xfail]`) should not check a skipped one.
+pub(crate) fn skipped<R>(test: impl FnOnce() -> R) -> (R, bool) {
+    let outer = SKIPPED.with(|skipped| skipped.replace(false));
+    let result = test();
+    (result, restore(outer))
+}
+
+/// The async version of [`skipped`].
+pub(crate) async fn skipped_async<R>(test: impl Future<Output = R>) -> (R, bool) {
+    let outer = SKIPPED.with(|skipped| skipped.replace(false));
+    let result = test.await;
+    (result, restore(outer))
+}
+
+/// Mark the outer call as skipped if the inner one is and return the inner mark.
+fn restore(outer: bool) -> bool {
+    SKIPPED.with(|skipped| {
+        let inner = skipped.get();
+        skipped.set(outer || inner);
+        inner
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_with_the_success() {
+        let (outcome, skipped) = skipped(|| skip::<Result<(), String>>("a == 1"));
+
+        assert_eq!(Ok(()), outcome);
+        assert!(skipped);
+    }
+
+    #[test]
+    fn not_mark_the_tests_that_run() {
+        let ((), skipped) = skipped(|| ());
+
+        assert!(!skipped);
+    }
+
+    #[test]
+    fn mark_the_outer_call_too() {
+        let (inner, outer) = skipped(|| skipped(|| skip::<()>("a == 1")).1);
+
+        assert!(inner);
+        assert!(outer);
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(feature = "expect-err")]
+use std::{fmt::Display, sync::Mutex};
+
+use std::future::Future;
+
+use crate::{
+    exclude,
+    outcome::{isolated, isolated_async, Outcome},
+};
+
+fn because(reason: Option<&str>) -> String {
+    reason.map(|r| format!(" ({r})")).unwrap_or_default()
+}
+
+/// Run a test that is expected to fail: it succeeds if `test` panics or returns an `Err`
+/// and panics if `test` passes. A combination skipped by `#[exclude_when(...)]` is neither
+/// an expected failure nor a pass: it's skipped.
+pub fn execute_xfail<O: Outcome>(reason: Option<&str>, test: impl FnOnce() -> O) -> O {
+    xfail_outcome(reason, exclude::skipped(|| isolated(test)))
+}
+
+/// The async version of [`execute_xfail`]: the panics of `test` are caught while it's
+/// awaited.
+pub async fn execute_xfail_async<O: Outcome>(
+    reason: Option<&str>,
+    test: impl Future<Output = O>,
+) -> O {
+    xfail_outcome(reason, exclude::skipped_async(isolated_async(test)).await)
+}
+
+fn xfail_outcome<O: Outcome>(reason: Option<&str>, outcome: (Result<O, String>, bool)) -> O {
+    match outcome {
+        (_, true) => O::success(),
+        (Err(failure), _) => {
+            eprintln!("Expected failure{}: {failure}", because(reason));
+            O::success()
+        }
+        (Ok(_), _) => panic!("Test expected to fail{} but it passed", because(reason)),
+    }
+}
+
+/// The regular expression of an `#[expect_err("regex")]` attribute: the macro has already
+/// validated it, so it's compiled the first time a test uses it and then reused.
+#[cfg(feature = "expect-err")]
+pub struct Pattern {
+    source: &'static str,
+    regex: Mutex<Option<regex::Regex>>,
+}
+
+#[cfg(feature = "expect-err")]
+impl Pattern {
+    pub const fn new(source: &'static str) -> Self {
+        Self {
+            source,
+            regex: Mutex::new(None),
+        }
+    }
+
+    fn regex(&self) -> regex::Regex {
+        self.regex
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(|| {
+                regex::Regex::new(self.source)
+                    .unwrap_or_else(|e| panic!(r#""{}" Should be a valid regex: {e}"#, self.source))
+            })
+            .clone()
+    }
+}
+
+/// Check that the test returned an error whose `Display` matches the `pattern` regular
+/// expression.
+///
+/// # Panics
+///
+/// If the test returned `Ok` or an error that doesn't match.
+#[cfg(feature = "expect-err")]
+pub fn check_err<T: Default, E: Display>(pattern: &Pattern, result: Result<T, E>) -> Result<T, E> {
+    match result {
+        Ok(_) => panic!(
+            "Expected an error that matches `{}` but the test passed",
+            pattern.source
+        ),
+        Err(e) if pattern.regex().is_match(&e.to_string()) => Ok(T::default()),
+        Err(e) => panic!("Error `{e}` doesn't match `{}`", pattern.source),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xfail_succeeds_on_panic() {
+        execute_xfail(Some("known bug"), || -> () { panic!("boom") });
+    }
+
+    #[test]
+    fn xfail_succeeds_on_error() {
+        assert_eq!(Ok(()), execute_xfail(None, || Err::<(), _>("wrong")));
+    }
+
+    #[test]
+    fn xfail_skips_the_excluded_combination() {
+        execute_xfail(None, || exclude::skip::<()>("a == 1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Test expected to fail (known bug) but it passed")]
+    fn xfail_fails_if_the_test_passes() {
+        execute_xfail(Some("known bug"), || ());
+    }
+
+    #[async_std::test]
+    async fn xfail_succeeds_on_async_panic() {
+        execute_xfail_async(Some("known bug"), async { panic!("boom") }).await
+    }
+
+    #[async_std::test]
+    async fn xfail_skips_the_async_excluded_combination() {
+        execute_xfail_async(None, async { exclude::skip::<()>("a == 1") }).await
+    }
+
+    #[async_std::test]
+    #[should_panic(expected = "Test expected to fail but it passed")]
+    async fn xfail_fails_if_the_async_test_passes() {
+        execute_xfail_async(None, async { Ok::<(), String>(()) })
+            .await
+            .unwrap()
+    }
+
+    #[cfg(feature = "expect-err")]
+    #[test]
+    fn accept_the_matching_error() {
+        static PATTERN: Pattern = Pattern::new("invalid (digit|port)");
+
+        assert_eq!(
+            Ok(()),
+            check_err(&PATTERN, Err::<(), _>("invalid digit found"))
+        );
+        assert_eq!(Ok(0), check_err(&PATTERN, Err::<u32, _>("invalid port 0")));
+    }
+
+    #[cfg(feature = "expect-err")]
+    #[test]
+    #[should_panic(expected = "Error `not found` doesn't match `invalid`")]
+    fn reject_other_errors() {
+        let _ = check_err(&Pattern::new("invalid"), Err::<(), _>("not found"));
+    }
+
+    #[cfg(feature = "expect-err")]
+    #[test]
+    #[should_panic(expected = "Expected an error that matches `invalid` but the test passed")]
+    fn reject_the_success() {
+        let _ = check_err::<_, String>(&Pattern::new("invalid"), Ok(42));
+    }
+}
@@ -0,0 +1,275 @@
+//! Runtime expansion of `#[files(...)]` arguments.
+//!
+//! `#[files("glob", expand = "runtime")]` doesn't generate a test for each file but a
+//! single test that resolves the globs when it runs and calls the test body for each
+//! matched file. Every failure is caught and the test fails at the end with a report that
+//! lists all the failed files:
+//!
+//! ```rust,ignore
+//! # use rstest::*;
+//! # use std::path::PathBuf;
+//! # fn parse(_input: &str) {}
+//! #[rstest]
+//! fn corpus(#[files("tests/corpus/**/*.bin", expand = "runtime")] path: PathBuf) {
+//!     parse(&std::fs::read_to_string(path).unwrap());
+//! }
+//! ```
+//!
+//! Usually you don't need to use this module directly: the macro renders the code that
+//! uses it.
+use std::path::{Path, PathBuf};
+
+use relative_path::RelativePath;
+
+use crate::outcome::{isolated, report, Outcome};
+
+/// The kind of the files to take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Just regular files
+    File,
+    /// Just folders
+    Dir,
+    /// Just symbolic links
+    Symlink,
+}
+
+impl Kind {
+    fn matches(&self, path: &Path) -> bool {
+        match self {
+            Kind::File => path.is_file(),
+            Kind::Dir => path.is_dir(),
+            Kind::Symlink => path.is_symlink(),
+        }
+    }
+}
+
+/// The files matched by some globs and the rules to filter them: the same ones of the
+/// compile time `#[files(...)]` expansion.
+#[derive(Debug, Clone)]
+pub struct Files {
+    base_dir: PathBuf,
+    globs: Vec<String>,
+    exclude: Vec<regex::Regex>,
+    ignore_dot_files: bool,
+    kind: Option<Kind>,
+}
+
+impl Files {
+    /// Take the files that match the given globs: relative globs are resolved from
+    /// `base_dir`.
+    pub fn new(base_dir: impl Into<PathBuf>, globs: &[&str]) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+            globs: globs.iter().map(ToString::to_string).collect(),
+            exclude: vec![],
+            ignore_dot_files: true,
+            kind: None,
+        }
+    }
+
+    /// Skip the files whose path relative to the base folder matches the given regular
+    /// expression.
+    ///
+    /// # Panics
+    ///
+    /// If `regex` is not a valid regular expression.
+    pub fn exclude(mut self, regex: &str) -> Self {
+        self.exclude.push(
+            regex::Regex::new(regex)
+                .unwrap_or_else(|e| panic!(r#""{regex}" Should be a valid regex: {e}"#)),
+        );
+        self
+    }
+
+    /// Take also the files or folders that start with `"."`.
+    pub fn include_dot_files(mut self) -> Self {
+        self.ignore_dot_files = false;
+        self
+    }
+
+    /// Take just the files of the given kind.
+    pub fn kind(mut self, kind: Kind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// The sorted paths of the matched files.
+    ///
+    /// # Panics
+    ///
+    /// If a glob is invalid or a matched file cannot be read.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let base_dir = self.base_dir.to_string_lossy();
+        let mut paths = self
+            .globs
+            .iter()
+            .flat_map(|pattern| {
+                let pattern = RelativePath::new(pattern).to_logical_path(&self.base_dir);
+                glob::glob(&pattern.to_string_lossy()).unwrap_or_else(|e| {
+                    panic!("glob failed for whole path `{}` due {e}", pattern.display())
+                })
+            })
+            .map(|p| p.unwrap_or_else(|e| panic!("glob failed for file due {e}")))
+            .filter(|p| self.kind.map(|k| k.matches(p)).unwrap_or(true))
+            .map(|p| {
+                p.canonicalize()
+                    .unwrap_or_else(|e| panic!("failed to canonicalize {} due {e}", p.display()))
+            })
+            .filter(|p| {
+                self.is_valid(&RelativePath::new(&base_dir).relative(&*p.to_string_lossy()))
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    fn is_valid(&self, p: &RelativePath) -> bool {
+        if self.ignore_dot_files
+            && p.components()
+                .any(|c| matches!(c, relative_path::Component::Normal(c) if c.starts_with('.')))
+        {
+            return false;
+        }
+        !self.exclude.iter().any(|e| e.is_match(p.as_ref()))
+    }
+
+    /// Call `test` for each matched file, catching its panics and releasing its resources,
+    /// and fail at the end if some calls failed.
+    ///
+    /// # Panics
+    ///
+    /// If no file matches or `test` fails for some files: the message lists all the failed
+    /// files.
+    pub fn run<O: Outcome>(&self, mut test: impl FnMut(&Path) -> O) -> O {
+        let paths = self.paths();
+        if paths.is_empty() {
+            panic!("No file found for {:?}", self.globs);
+        }
+        let failures = paths
+            .iter()
+            .filter_map(|path| {
+                isolated(|| test(path))
+                    .err()
+                    .map(|message| (path.display(), message))
+            })
+            .collect::<Vec<_>>();
+        if !failures.is_empty() {
+            panic!("{}", report(paths.len(), "files", &failures));
+        }
+        O::success()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outcome::panic_message;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str, files: &[&str]) -> Self {
+            let root =
+                std::env::temp_dir().join(format!("rstest_files_{name}_{}", std::process::id()));
+            for file in files {
+                let path = root.join(file);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, file).unwrap();
+            }
+            Self(root.canonicalize().unwrap())
+        }
+
+        fn names(&self, paths: Vec<PathBuf>) -> Vec<String> {
+            paths
+                .iter()
+                .map(|p| {
+                    p.strip_prefix(&self.0)
+                        .unwrap()
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                })
+                .collect()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn resolve_the_globs_from_base_dir() {
+        let dir = TempDir::new(
+            "filter",
+            &[
+                "b.txt",
+                "a.txt",
+                "skip.txt",
+                ".hidden.txt",
+                "sub/c.txt",
+                "d.bin",
+            ],
+        );
+
+        let files = Files::new(&dir.0, &["*.txt", "sub/*.txt", "a.*"]).exclude("skip");
+
+        assert_eq!(
+            vec!["a.txt", "b.txt", "sub/c.txt"],
+            dir.names(files.paths())
+        );
+        assert_eq!(
+            vec![".hidden.txt", "a.txt", "b.txt"],
+            dir.names(
+                Files::new(&dir.0, &["*.txt"])
+                    .exclude("skip")
+                    .include_dot_files()
+                    .paths()
+            )
+        );
+        assert_eq!(
+            vec!["sub"],
+            dir.names(Files::new(&dir.0, &["*"]).kind(Kind::Dir).paths())
+        );
+    }
+
+    #[test]
+    fn report_all_the_failed_files() {
+        let dir = TempDir::new("report", &["a.txt", "b.txt", "c.txt"]);
+        let mut calls = 0;
+
+        let message = catch_unwind(AssertUnwindSafe(|| {
+            Files::new(&dir.0, &["*.txt"]).run(|path| {
+                calls += 1;
+                assert!(!path.ends_with("b.txt"), "b is wrong");
+                if path.ends_with("c.txt") {
+                    Err("c is wrong")
+                } else {
+                    Ok(())
+                }
+            })
+        }))
+        .map_err(panic_message)
+        .unwrap_err();
+
+        assert_eq!(3, calls);
+        assert!(message.starts_with("2 of 3 files failed:"), "{message}");
+        assert!(message.contains("b.txt: b is wrong"), "{message}");
+        assert!(
+            message.contains(r#"c.txt: Error: "c is wrong""#),
+            "{message}"
+        );
+        assert!(!message.contains("a.txt"), "{message}");
+    }
+
+    #[test]
+    #[should_panic(expected = "No file found")]
+    fn panic_if_no_files_found() {
+        let dir = TempDir::new("empty", &["a.txt"]);
+
+        Files::new(&dir.0, &["*.bin"]).run(|_| ());
+    }
+}
@@ -0,0 +1,109 @@
+//! Golden files support: compare the actual output of your test with the content of an
+//! expected file, or update the expected file when the `RSTEST_BLESS` environment variable
+//! is set.
+//!
+//! You can use it together with `#[files_pair(...)]` to get the input and expected file
+//! paths:
+//!
+//! ```rust,ignore
+//! # use rstest::*;
+//! # use std::path::PathBuf;
+//! # fn parse(_input: &str) -> String { String::new() }
+//! #[rstest]
+//! fn parser(#[files_pair("tests/inputs/*.txt", "tests/expected/{stem}.out")] files: (PathBuf, PathBuf)) {
+//!     let (input, expected) = files;
+//!     let actual = parse(&std::fs::read_to_string(input).unwrap());
+//!
+//!     rstest::golden::check(expected, actual);
+//! }
+//! ```
+//!
+//! Run your tests with `RSTEST_BLESS=1 cargo test` to write the actual outputs in the
+//! expected files instead of comparing them.
+use std::{env, path::Path};
+
+/// The environment variable that enables the bless mode.
+pub const BLESS_ENV: &str = "RSTEST_BLESS";
+
+/// Return `true` if the `RSTEST_BLESS` environment variable is set to anything but an
+/// empty string or `0`.
+pub fn is_bless() -> bool {
+    is_bless_value(env::var(BLESS_ENV).ok().as_deref())
+}
+
+fn is_bless_value(value: Option<&str>) -> bool {
+    !matches!(value, None | Some("") | Some("0"))
+}
+
+/// Check that `actual` is equal to the content of the `expected` file or, in bless mode,
+/// write `actual` in the `expected` file (creating it and its folders if they are missed).
+///
+/// # Panics
+///
+/// If the content is different, or the `expected` file cannot be read (or written in bless
+/// mode).
+pub fn check(expected: impl AsRef<Path>, actual: impl AsRef<[u8]>) {
+    let expected = expected.as_ref();
+    let actual = actual.as_ref();
+    if is_bless() {
+        if let Some(parent) = expected.parent() {
+            std::fs::create_dir_all(parent)
+                .unwrap_or_else(|e| panic!("Cannot create the folder {}: {e}", parent.display()));
+        }
+        std::fs::write(expected, actual)
+            .unwrap_or_else(|e| panic!("Cannot write golden file {}: {e}", expected.display()));
+        return;
+    }
+    let content = std::fs::read(expected).unwrap_or_else(|e| {
+        panic!(
+            "Cannot read golden file {}: {e}: run the test with {BLESS_ENV}=1 to create it",
+            expected.display()
+        )
+    });
+    compare(expected, &content, actual);
+}
+
+fn compare(path: &Path, expected: &[u8], actual: &[u8]) {
+    if expected == actual {
+        return;
+    }
+    let message = format!(
+        "Golden file {} doesn't match: run the test with {BLESS_ENV}=1 to update it",
+        path.display()
+    );
+    match (std::str::from_utf8(expected), std::str::from_utf8(actual)) {
+        (Ok(expected), Ok(actual)) => assert_eq!(expected, actual, "{message}"),
+        _ => assert_eq!(expected, actual, "{message}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bless_mode_is_enabled_by_a_not_empty_value() {
+        assert!(!is_bless_value(None));
+        assert!(!is_bless_value(Some("")));
+        assert!(!is_bless_value(Some("0")));
+        assert!(is_bless_value(Some("1")));
+        assert!(is_bless_value(Some("yes")));
+    }
+
+    #[test]
+    fn accept_the_same_content() {
+        compare(Path::new("expected.out"), b"some text", b"some text");
+    }
+
+    #[test]
+    #[should_panic(expected = "Golden file expected.out doesn't match")]
+    fn panic_on_different_text() {
+        compare(Path::new("expected.out"), b"some text", b"other text");
+    }
+
+    #[test]
+    #[should_panic(expected = "RSTEST_BLESS=1")]
+    fn panic_on_different_bytes() {
+        compare(Path::new("expected.bin"), &[0xff, 0x00], &[0xff, 0x01]);
+    }
+}
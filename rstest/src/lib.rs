@@ -97,6 +97,10 @@
 //!
 //! - `async-timeout`: `timeout` for `async` tests (Default enabled)
 //! - `crate-name`: Import `rstest` package with different name (Default enabled)
+//! - `expect-err`: `#[expect_err("regex")]` attribute
+//! - `files-runtime`: `#[files(..., expand = "runtime")]` arguments and `rstest::files`
+//!   module
+//! - `manifest`: Register the generated tests in `rstest::manifest` registry
 //!
 //! ## Injecting fixtures as function arguments
 //!
@@ -239,14 +243,60 @@
 //!
 //! - **`async-timeout`** *(enabled by default)* — Implement timeout for async
 //! tests.
+//! - **`expect-err`** — Check the error returned by a test with
+//!   `#[expect_err("regex")]`.
+//! - **`files-runtime`** — Resolve the `#[files(..., expand = "runtime")]` globs
+//!   when the test runs (see `rstest::files` module).
+//! - **`manifest`** — Register every generated test with its case, values and
+//!   tags in the `rstest::manifest` registry.
 //!
 //! # Rust version compatibility
 //!
 //! The minimum supported Rust version is 1.67.1.
 //!
 
+#[cfg(any(feature = "build", test))]
+pub mod build;
+#[doc(hidden)]
+pub mod cases;
+#[doc(hidden)]
+pub mod exclude;
+#[doc(hidden)]
+pub mod expect;
+#[cfg(feature = "files-runtime")]
+pub mod files;
+pub mod golden;
 #[doc(hidden)]
 pub mod magic_conversion;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+
+/// Register a generated test in the [`manifest`] registry: `#[rstest]` always calls it, it
+/// does nothing without the `manifest` feature.
+#[cfg(feature = "manifest")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register {
+    ($($entry:tt)*) => {
+        $crate::manifest::inventory::submit! { $($entry)* }
+    };
+}
+
+#[cfg(not(feature = "manifest"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register {
+    ($($entry:tt)*) => {};
+}
+#[doc(hidden)]
+pub mod once;
+pub mod outcome;
+pub mod random;
+#[doc(hidden)]
+pub mod retries;
+pub mod scope;
+pub mod tags;
+pub mod teardown;
 #[doc(hidden)]
 pub mod timeout;
 
@@ -416,14 +466,195 @@ pub mod timeout;
 /// }
 /// ```
 ///
-/// There are some limitations when you use `#[once]` fixture. `rstest` forbid to use once fixture
-/// for:
+/// `#[once]` works also with `async` fixtures: the fixture becomes a future that resolves to
+/// the static reference and the first test that awaits it computes the value. It doesn't
+/// depend on the async runtime, so tests that use different runtimes share the same value.
 ///
-/// - `async` function
-/// - Generic function (both with generic types or use `impl` trait)
+/// ```
+/// # use rstest::*;
+/// #[fixture]
+/// #[once]
+/// async fn once_fixture() -> i32 { 42 }
+///
+/// #[rstest]
+/// #[async_std::test]
+/// async fn single(#[future(awt)] once_fixture: &i32) {
+///     assert_eq!(&42, once_fixture)
+/// }
+/// ```
+///
+/// A generic `#[once]` fixture computes a value for every concrete type that the tests use:
+///
+/// ```
+/// # use rstest::*;
+/// # trait Codec: Default + Sync { fn name(&self) -> &'static str; }
+/// # #[derive(Default)] struct Json;
+/// # impl Codec for Json { fn name(&self) -> &'static str { "json" } }
+/// # #[derive(Default)] struct Yaml;
+/// # impl Codec for Yaml { fn name(&self) -> &'static str { "yaml" } }
+/// #[fixture]
+/// #[once]
+/// fn codec<C: Codec>() -> C { C::default() }
+///
+/// #[rstest]
+/// fn json(codec: &Json) {
+///     assert_eq!("json", codec.name())
+/// }
+///
+/// #[rstest]
+/// fn yaml(codec: &Yaml) {
+///     assert_eq!("yaml", codec.name())
+/// }
+/// ```
+///
+/// The fixture's generic types should be `'static`: `rstest` adds this bound for you.
+///
+/// There are some limitations when you use `#[once]` fixture. `rstest` forbid to use once fixture
+/// for functions that have lifetimes or const generic parameters or take `impl` arguments.
+/// Moreover the fixture's type should be `Sync` because its value is shared between the test
+/// threads.
 ///
 /// Take care that the `#[once]` fixture value will **never be dropped**.
 ///
+/// # Teardown
+///
+/// If your fixture acquires a resource that should be released when the test is done (a
+/// database, a temporary server, ...) you can use the `#[teardown]` attribute. Every test
+/// generated by `rstest` releases its fixtures' resources after the test body **also when the
+/// test fails**, in the reverse order of how the fixtures were resolved: a fixture is always
+/// torn down before the fixtures that it depends on.
+///
+/// There are two forms. With `#[teardown]` your fixture should return a `(value, guard)`
+/// tuple where `guard` is a [`teardown::TearDown`] (every `FnOnce()` closure is a
+/// `TearDown`): the test will receive just the value and the guard will be called at the
+/// end of the test.
+///
+/// ```
+/// use rstest::*;
+/// # struct Db;
+/// # impl Db { fn create() -> Self { Db } fn drop_all(&self) {} }
+///
+/// #[fixture]
+/// #[teardown]
+/// fn db() -> (std::sync::Arc<Db>, impl FnOnce()) {
+///     let db = std::sync::Arc::new(Db::create());
+///     let guard = { let db = db.clone(); move || db.drop_all() };
+///     (db, guard)
+/// }
+///
+/// #[rstest]
+/// fn use_db(db: std::sync::Arc<Db>) {
+///     // Use db: `drop_all()` will be called when the test ends
+/// }
+/// ```
+///
+/// With `#[teardown(expression)]` the expression should evaluate to a closure that takes a
+/// reference to the fixture value, just after the fixture is built, and returns the guard
+/// (a [`teardown::TearDown`]) that will release it at the end of the test. The test
+/// receives the value itself, so the guard should take from the value just what it needs
+/// to release the resource.
+///
+/// ```
+/// use rstest::*;
+/// # struct Handle(u16);
+/// # impl Handle { fn stop(self) {} }
+/// # struct Server(u16);
+/// # impl Server { fn start() -> Self { Server(8080) } fn handle(&self) -> Handle { Handle(self.0) } }
+///
+/// #[fixture]
+/// #[teardown(|s: &Server| { let handle = s.handle(); move || handle.stop() })]
+/// fn server() -> Server {
+///     Server::start()
+/// }
+///
+/// #[rstest]
+/// fn use_server(server: Server) {
+///     assert_eq!(8080, server.0);
+/// }
+/// ```
+///
+/// You cannot use `#[teardown]` with `#[once]` fixtures. If you resolve a fixture outside of
+/// a test generated by `rstest` (for instance calling `Fixture::default()` directly) the
+/// teardown will be executed when the current thread ends.
+///
+/// # Scoped Fixtures
+///
+/// Sometimes a fixture is too expensive to be built for every test but `#[once]` shares it
+/// for the whole test binary and never drops it. With `#[scope(group)]` the fixture value
+/// is shared by all the tests generated by the same `#[rstest]` function (every case and
+/// every matrix cell) and it's dropped (and torn down, see [Teardown](#teardown)) when the
+/// group's last test ends. If the test harness runs just some of the group's tests (for
+/// instance filtering them by name or skipping the ignored ones) the value is dropped when
+/// the test process exits. With `#[scope(module)]` the value is shared by all the tests
+/// in the same module and, like `#[once]` fixtures, it's never dropped: neither its `Drop`
+/// implementation nor its teardown is ever called.
+///
+/// Scoped fixtures return an [`Arc`](std::sync::Arc) to the shared value, so the fixture
+/// type must be `Send + Sync`.
+///
+/// ```
+/// use rstest::*;
+/// use std::sync::Arc;
+/// # struct Parser;
+/// # impl Parser { fn heavyweight() -> Self { Parser } fn parse(&self, s: &str) -> usize { s.len() } }
+///
+/// #[fixture]
+/// #[scope(group)]
+/// fn parser() -> Parser {
+///     Parser::heavyweight()
+/// }
+///
+/// #[rstest]
+/// #[case("a", 1)]
+/// #[case("ab", 2)]
+/// #[case("abc", 3)]
+/// fn parse(parser: Arc<Parser>, #[case] input: &str, #[case] expected: usize) {
+///     // All cases share the same parser
+///     assert_eq!(expected, parser.parse(input));
+/// }
+/// ```
+///
+/// The shared value is computed by the first test that needs it, so all the tests that use
+/// the same scoped fixture should resolve it with the same arguments. If a group's teardown
+/// panics at exit, `rstest` reports it on the standard error.
+///
+/// You cannot use `#[scope]` with `#[once]`, `async` or generic fixtures and `#[teardown]`
+/// is not allowed for `#[scope(module)]` fixtures.
+///
+/// # Parametrized Fixtures
+///
+/// A fixture can define `#[values(...)]` lists and `#[case(...)]` attributes like a test:
+/// every combination of them is a _variant_ of the fixture. A test argument marked by
+/// `#[params]` takes all the fixture's variants and `rstest` generates a test for each of
+/// them (combined with the test's own cases and values).
+///
+/// ```
+/// use rstest::*;
+/// # #[derive(Debug, Clone, Copy)] enum Kind { Memory, Sqlite }
+/// # struct Storage(Kind);
+/// # impl Storage { fn new(kind: Kind) -> Self { Storage(kind) } fn get(&self, _k: &str) -> Option<u32> { None } }
+///
+/// #[fixture]
+/// fn backend(#[values(Kind::Memory, Kind::Sqlite)] kind: Kind) -> Storage {
+///     Storage::new(kind)
+/// }
+///
+/// #[rstest]
+/// fn should_be_empty(#[params] backend: Storage) {
+///     // Runs once with the `Kind::Memory` backend and once with the `Kind::Sqlite` one
+///     assert!(backend.get("key").is_none());
+/// }
+/// ```
+///
+/// The test names contain the variant's name: the case description (or `case_N`) and the
+/// values. A test that doesn't mark the argument by `#[params]` (and the fixture's
+/// `default()` and partial functions) uses just the first variant. You can also use
+/// `#[params]` together with `#[from(path::to::fixture)]`.
+///
+/// To expand the tests `rstest` uses a macro defined by the fixture with `pub(crate)`
+/// visibility, so you can take the variants of fixtures defined in the same crate only.
+/// You cannot use `#[once]` or `#[scope]` on parametrized fixtures.
+///
 /// # Partial Injection
 ///
 /// You can also partially inject fixture dependency using `#[with(v1, v2, ..)]` attribute:
@@ -834,6 +1065,57 @@ pub use rstest_macros::fixture;
 /// fn fail(#[case] v: u32) { assert_eq!(0, v) }
 /// ```
 ///
+/// ### Cases from data files
+///
+/// You can also load the cases from a `CSV`, `JSON` or `TOML` file with the
+/// `#[cases_from("path")]` attribute: the path is relative to your crate root and the file
+/// is read at compile time. Every row becomes a case and the columns fill the `#[case]`
+/// arguments with the same name. All values are rendered as string literals, so they are
+/// converted to your argument types by [Magic Conversion](#magic-conversion). The optional
+/// `name` column is used as case description.
+///
+/// ```rust,ignore
+/// # use rstest::rstest;
+/// // tests/data/sum.csv
+/// //
+/// // name,a,b,expected
+/// // zero,0,0,0
+/// // small,2,3,5
+/// #[rstest]
+/// #[cases_from("tests/data/sum.csv")]
+/// fn sum(#[case] a: u32, #[case] b: u32, #[case] expected: u32) {
+///     assert_eq!(expected, a + b)
+/// }
+/// ```
+///
+/// A `JSON` file should contain an array of objects. In a `TOML` file each table is a case
+/// (the table's name is the description) and you can also use arrays of tables like
+/// `[[cases]]`. You can use `#[cases_from]` more than once and together with `#[case]`
+/// attributes.
+///
+/// ### Cases generated at runtime
+///
+/// If your cases come from something that you can compute just when the test runs, use
+/// `#[cases_from_fn(path::to::fn)]`: the function returns an `IntoIterator` of
+/// `(name, (case args...))` and the values fill the `#[case]` arguments in order. `rstest`
+/// generates a single test that runs all the cases, resolving the fixtures for each of
+/// them, and fails at the end with a report of all the failed cases (it fails also if
+/// there are no cases). You cannot use it together with `#[case]`, `#[cases_from]` or
+/// `#[values]` and in `async` tests.
+///
+/// ```
+/// # use rstest::*;
+/// fn squares() -> impl IntoIterator<Item = (String, (u32, u32))> {
+///     (1..=10).map(|n| (format!("square_of_{n}"), (n, n * n)))
+/// }
+///
+/// #[rstest]
+/// #[cases_from_fn(squares)]
+/// fn square(#[case] n: u32, #[case] expected: u32) {
+///     assert_eq!(expected, n.pow(2))
+/// }
+/// ```
+///
 /// ## Values Lists
 ///
 /// Another useful way to write a test and execute it for some values
@@ -897,6 +1179,153 @@ pub use rstest_macros::fixture;
 /// }
 /// ```
 ///
+/// ### Named values
+///
+/// When the values are long expressions the test names are not so readable: give them a
+/// name by `name => value` and `rstest` uses it in place of the expression.
+///
+/// ```
+/// # use rstest::rstest;
+/// # fn big_vec() -> Vec<u32> { (0..1000).collect() }
+/// #[rstest]
+/// fn sort(#[values(empty => vec![], small => vec![3, 1, 2], huge => big_vec())] mut v: Vec<u32>) {
+///     // Generates `v_1_empty`, `v_2_small` and `v_3_huge`
+///     v.sort();
+///     assert!(v.windows(2).all(|w| w[0] <= w[1]))
+/// }
+/// ```
+///
+/// ### Ranges and constants
+///
+/// `#[values(range = ...)]` takes a range of integer literals and expands it at compile
+/// time to a test for each integer: `#[values(range = 0..8)]` is
+/// `#[values(0, 1, 2, 3, 4, 5, 6, 7)]`. Add `; step = n` to take one integer every `n`. The
+/// literal's type suffix, if any, is applied to every value. A range can expand to at most
+/// 1024 values. A plain `#[values(0..8)]` is still a single `Range` value.
+///
+/// ```
+/// # use rstest::rstest;
+/// #[rstest]
+/// fn shift(
+///     #[values(range = 0..8)] bits: u32,
+///     #[values(range = 0..=255; step = 17)] byte: u8,
+/// ) {
+///     assert_eq!(byte as u32, ((byte as u32) << bits) >> bits)
+/// }
+/// ```
+///
+/// With `#[values(from = MY_CONST; len = n)]` the values are the `n` elements of a `const`
+/// array or slice: the macro cannot read the constant, so you give its length, which is
+/// checked at compile time. Every element gets its own test, named by the constant and the
+/// element's index (like `p_1_PRIMES_0`), and the elements should implement `Clone`.
+///
+/// ```
+/// # use rstest::rstest;
+/// const PRIMES: [u32; 5] = [2, 3, 5, 7, 11];
+///
+/// #[rstest]
+/// fn is_prime(#[values(from = PRIMES; len = 5)] p: u32) {
+///     assert!((2..p).all(|d| p % d != 0))
+/// }
+/// ```
+///
+/// ### Pairwise combinations
+///
+/// The tests for all the combinations grow very fast: 5 arguments with 8 values each are
+/// 32768 tests. Mark the test by `#[pairwise]` to generate just a deterministic subset of
+/// the combinations where every pair of values of two different arguments is tested at
+/// least once: a lot of bugs depend on the interaction of just two arguments. The tests
+/// keep the same names of the full combinations.
+///
+/// ```
+/// # use rstest::rstest;
+/// # fn render(_format: &str, _color: bool, _width: u32, _locale: &str) {}
+/// #[rstest]
+/// #[pairwise]
+/// fn render_report(
+///     #[values("text", "html", "json")] format: &str,
+///     #[values(true, false)] color: bool,
+///     #[values(40, 80, 120)] width: u32,
+///     #[values("en", "it", "ja")] locale: &str,
+/// ) {
+///     render(format, color, width, locale)
+/// }
+/// ```
+///
+/// ### Zip values lists
+///
+/// If the values lists are columns that should be read in lock step use `#[zip]`:
+/// `rstest` generates a test for the first values of every list, one for the second ones
+/// and so on, instead of all the combinations. All the lists should have the same length.
+///
+/// ```
+/// # use rstest::rstest;
+/// #[rstest]
+/// #[zip]
+/// fn double(#[values(1, 2, 3)] input: u32, #[values(2, 4, 6)] expected: u32) {
+///     assert_eq!(expected, 2 * input)
+/// }
+/// ```
+///
+/// ### Exclude some combinations
+///
+/// Some combinations of the values don't make sense: use
+/// `#[exclude_when(condition)]` to skip the ones where the condition is true. You can use
+/// it more than once: the combination is excluded if at least one of the conditions is
+/// true.
+///
+/// ```
+/// # use rstest::rstest;
+/// # #[derive(Debug, PartialEq)] enum State { Running, Terminated }
+/// # #[derive(Debug, PartialEq)] enum Event { Tick, Fatal }
+/// # fn step(_state: State, _event: Event) {}
+/// #[rstest]
+/// #[exclude_when(state == State::Terminated && event == Event::Tick)]
+/// fn transitions(
+///     #[values(State::Running, State::Terminated)] state: State,
+///     #[values(Event::Tick, Event::Fatal)] event: Event,
+/// ) {
+///     step(state, event)
+/// }
+/// ```
+///
+/// When the condition just combines by `&&`, `||` and `!` the comparisons (`==` and `!=`)
+/// of the arguments with literals or paths the excluded tests are marked by `#[ignore]`.
+/// Two literals are compared just when they have the same kind and are not floats (a
+/// string converted to another argument's type is known just at runtime). Two paths are
+/// equal if they are the same path and different if they are two variants of the same
+/// enum; any other comparison (e.g. two constants) cannot be evaluated at compile time. The tests whose condition is not known at compile time run and check it when
+/// their arguments are resolved: the excluded ones write that they are skipped on the
+/// standard error and return early. Rust's test harness cannot ignore a running test, so
+/// it counts them as passed (`ok`).
+///
+/// ## Custom test names
+///
+/// Use `#[rstest(name = "template")]` to name the tests by the arguments' values instead
+/// of `case_07` or nested `arg_1_value` modules: every `{arg}` placeholder is replaced
+/// by the `#[case]` or `#[values]` argument's value (or by the file name for
+/// `#[files(...)]`) and `{case}` by the case's description or number. The tests are
+/// rendered flat in the test module and the expansion fails if two of them get the same
+/// name or an empty one. A name that is a keyword is prefixed by the placeholders' names:
+/// `name = "{flag}"` with `#[values(true, false)] flag: bool` generates `flag_true` and
+/// `flag_false`.
+///
+/// ```
+/// # use rstest::rstest;
+/// # #[derive(Clone, Copy)] enum Unit { Celsius, Fahrenheit }
+/// # use Unit::*;
+/// # fn convert(value: f64, _from: Unit, _to: Unit) -> f64 { value }
+/// #[rstest(name = "{from}_to_{to}")]
+/// fn conversion_roundtrip(
+///     #[values(Celsius, Fahrenheit)] from: Unit,
+///     #[values(Celsius, Fahrenheit)] to: Unit,
+/// ) {
+///     // Generates `Celsius_to_Celsius`, `Celsius_to_Fahrenheit`,
+///     // `Fahrenheit_to_Celsius` and `Fahrenheit_to_Fahrenheit`
+///     assert_eq!(20.0, convert(convert(20.0, from, to), to, from))
+/// }
+/// ```
+///
 /// ## Files path as input arguments
 ///
 /// If you need to create a test for each file in a given location you can use
@@ -918,6 +1347,95 @@ pub use rstest_macros::fixture;
 /// exclusion rules with the `#[exclude("regex")]` attributes that filter out all
 /// paths that verify the regular expression.
 ///
+/// The glob matches both files and folders: use the `kind = "file"`, `kind = "dir"` or
+/// `kind = "symlink"` option (e.g. `#[files("tests/*", kind = "file")]`) to take just one kind
+/// of them. `#[dirs("glob")]` is a shortcut for `kind = "dir"`, for instance to generate a
+/// test for each project folder:
+///
+/// ```
+/// # use rstest::rstest;
+/// # use std::path::PathBuf;
+/// #[rstest]
+/// fn for_each_folder(#[dirs("tests/*")] path: PathBuf) {
+///     assert!(path.is_dir())
+/// }
+/// ```
+///
+/// If you need the file contents instead of its path you can use `#[mode = str]` to get
+/// them as `&'static str` or `#[mode = bytes]` to get them as `&'static [u8]`: the files
+/// are embedded in the test by `include_str!` and `include_bytes!`, so cargo recompiles
+/// your tests when you change them.
+///
+/// ```
+/// # use rstest::rstest;
+/// #[rstest]
+/// fn for_each_file_content(#[files("src/**/*.rs")] #[mode = str] contents: &str) {
+///     assert!(!contents.is_empty())
+/// }
+/// ```
+///
+/// For golden tests you can use `#[files_pair("glob", "expected path template")]`: the
+/// argument receives a tuple with the input file and its expected output file. The template
+/// is relative to the globs' base folder (the crate root by default) and can use the
+/// `{name}`, `{stem}` and `{ext}` placeholders of the input file. Use
+/// [`golden::check`] to compare the actual output with the expected file's content or to
+/// update it when the `RSTEST_BLESS` environment variable is set. The compilation fails if
+/// an expected file is missing, but in bless mode: `RSTEST_BLESS` is read at compile time
+/// too, so run `RSTEST_BLESS=1 cargo test` to create the missed expected files. With
+/// `#[mode = str]` or `#[mode = bytes]` a missed expected file is always an error.
+///
+/// ```rust,ignore
+/// # use rstest::rstest;
+/// # use std::path::PathBuf;
+/// # fn format(_input: &str) -> String { String::new() }
+/// #[rstest]
+/// fn golden(
+///     #[files_pair("tests/inputs/*.txt", "tests/expected/{stem}.out")] files: (PathBuf, PathBuf)
+/// ) {
+///     let (input, expected) = files;
+///     rstest::golden::check(expected, format(&std::fs::read_to_string(input).unwrap()))
+/// }
+/// ```
+///
+/// When a glob matches a lot of files, for instance a fuzzing corpus, you can take just a
+/// subset of them: `limit = N` takes the first `N` files, `sample = N` takes `N` pseudo-random
+/// files chosen by their paths (use `seed = S` to choose another sample) and `shards = N`
+/// splits the files in `N` shards and takes the one in the `RSTEST_FILES_SHARD` environment
+/// variable (from `0` to `N - 1`) at compile time, or all of them if it's not defined: changing
+/// it rebuilds the tests. The chosen files don't change between runs and the test names are
+/// the ones of the full list, so the same file has the same name in every subset.
+///
+/// ```
+/// # use rstest::rstest;
+/// # use std::path::PathBuf;
+/// #[rstest]
+/// fn some_files(#[files("src/**/*.rs", sample = 3, seed = 42)] path: PathBuf) {
+///     assert!(path.exists())
+/// }
+/// ```
+///
+/// For very large sets of files you can enable the `files-runtime` feature and use the
+/// `expand = "runtime"` option: `rstest`
+/// doesn't generate a test for each file but a single test that resolves the globs when it
+/// runs and calls the test body for each file. It catches every failure and, at the end,
+/// fails with a report that lists all the failed files (see `rstest::files` module). The
+/// `#[exclude(...)]`, `#[include_dot_files]`, `#[mode = ...]`, `kind` and `base` rules
+/// work as usual, but you cannot use it in `async` tests or together with `limit`, `sample`,
+/// `shards` or `#[files_pair(...)]`.
+///
+/// ```rust,ignore
+/// # use rstest::rstest;
+/// # use std::path::PathBuf;
+/// #[rstest]
+/// fn all_sources(#[files("src/**/*.rs", expand = "runtime")] #[mode = str] contents: &str) {
+///     assert!(!contents.is_empty())
+/// }
+/// ```
+///
+/// `rstest` resolves the globs at compile time, so cargo doesn't expand your tests again if
+/// you just add or remove a file: use `rstest::build::rerun_if_files_changed` (`build`
+/// feature) in your build script to recompile the tests when the folders' contents change.
+///
 /// Sometime is useful to have test files in a workspace folder to share them between the
 /// crates in your workspace. You can do that by use the usual parent folders `..` in
 /// the glob path. In this case the test names will be the relative path from the crate root
@@ -925,6 +1443,82 @@ pub use rstest_macros::fixture;
 /// `valid_call.yaml` in the folder `../test_cases` (from your crate root) a test name could be
 /// `path_1__UP_test_cases_valid_call_yaml`.
 ///
+/// Otherwise you can change the folder where the globs are resolved by the `base` option:
+/// `base = "crate"` (the default), `base = "workspace"` for the workspace root,
+/// `base = "out_dir"` for the build script's `OUT_DIR` or `base = env("VAR")` for the folder
+/// in the `VAR` environment variable (relative to the crate root). The test names are
+/// relative to this folder. You can also change the crate's default base in its
+/// `Cargo.toml`:
+///
+/// ```toml
+/// [package.metadata.rstest]
+/// base = "workspace" # or { env = "VAR" }
+/// ```
+///
+/// ```rust,ignore
+/// # use rstest::rstest;
+/// # use std::path::PathBuf;
+/// #[rstest]
+/// fn shared(#[files("test_cases/*.yaml", base = "workspace")] path: PathBuf) {
+///     assert!(path.exists())
+/// }
+/// ```
+///
+/// ## Parametrized fixtures
+///
+/// Mark a fixture argument by `#[params]` to generate a test for each variant of a
+/// parametrized fixture (see [Parametrized Fixtures](attr.fixture.html#parametrized-fixtures)):
+///
+/// ```
+/// # use rstest::*;
+/// #[fixture]
+/// #[case::small(1)]
+/// #[case::big(1000)]
+/// fn limit(#[case] value: u32) -> u32 {
+///     value
+/// }
+///
+/// #[rstest]
+/// fn should_accept_limit(#[params] limit: u32, #[values(1, 2)] step: u32) {
+///     // Generates `limit_1_small::step_1`, `limit_1_small::step_2`,
+///     // `limit_2_big::step_1` and `limit_2_big::step_2`
+///     assert!(limit * step > 0);
+/// }
+/// ```
+///
+/// ## Property based tests
+///
+/// Mark an argument by `#[random(strategy)]` to turn the test into a property test: the
+/// test is called with `cases = n` (256 by default) values drawn by the strategy, while
+/// the other arguments are still resolved as fixtures for every call. When a call fails
+/// the values are shrunk to a minimal counterexample and the failure message prints it
+/// with the seed: use the `seed = n` option or the `RSTEST_RANDOM_SEED` environment
+/// variable to replay it. See [`random`](crate::random) module for the provided
+/// strategies and how to implement yours.
+///
+/// ```
+/// # use rstest::*;
+/// use rstest::random::{any, vec};
+/// # struct Codec;
+/// # impl Codec {
+/// #     fn encode(&self, data: &[u8]) -> Vec<u8> { data.to_vec() }
+/// #     fn decode(&self, data: &[u8]) -> Vec<u8> { data.to_vec() }
+/// # }
+///
+/// #[fixture]
+/// fn codec() -> Codec {
+///     Codec
+/// }
+///
+/// #[rstest]
+/// fn roundtrip(codec: Codec, #[random(vec(any::<u8>(), 0..64), cases = 100)] data: Vec<u8>) {
+///     assert_eq!(data, codec.decode(&codec.encode(&data)))
+/// }
+/// ```
+///
+/// The random values must implement `Clone` and `Debug`, a test can have at most 6
+/// `#[random]` arguments and you cannot use them in `async` tests.
+///
 /// ## Use Parametrize definition in more tests
 ///
 /// If you need to use a test list for more than one test you can use
@@ -1084,6 +1678,97 @@ pub use rstest_macros::fixture;
 /// If you want to use `timeout` for `async` test you need to use `async-timeout`
 /// feature (enabled by default).
 ///
+/// ## Expected failures
+///
+/// Sometimes a case documents a known bug: mark it by `#[xfail]` or `#[xfail("reason")]`
+/// and the test passes if it panics (or returns an `Err`) and fails if it unexpectedly
+/// passes. If the test returns a `Result` you can use `#[expect_err("regex")]` (`expect-err`
+/// feature) to check that the error's `Display` matches the given regular expression. Both
+/// of them can be used on the whole test or on a single case, also in `async` tests: the
+/// panics are caught while the test is awaited.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # use std::num::ParseIntError;
+/// #[rstest]
+/// #[case::valid("42", 42)]
+/// #[xfail("u8 overflow is not handled yet")]
+/// #[case::overflow("300", 44)]
+/// #[expect_err("invalid digit")]
+/// #[case::invalid("4x", 0)]
+/// fn parse(#[case] input: &str, #[case] expected: u8) -> Result<(), ParseIntError> {
+///     assert_eq!(expected, input.parse::<u8>()?);
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Retry flaky tests
+///
+/// Use `#[retries(n)]` to run a flaky test again, at most `n` times (with `n` positive), when
+/// it panics or returns an `Err`: the test passes if one of the attempts succeeds. Every
+/// attempt resolves the fixtures again, the resources of a failed attempt are torn down
+/// before the next one starts and every failed attempt is printed. Like `timeout` you can use it
+/// on the whole test or on a single case (the case's one wins). In `async` tests every
+/// attempt awaits the test again, with its fixtures built again too.
+///
+/// ```
+/// # use rstest::*;
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// static CALLS: AtomicUsize = AtomicUsize::new(0);
+///
+/// #[fixture]
+/// fn attempt() -> usize {
+///     CALLS.fetch_add(1, Ordering::SeqCst) + 1
+/// }
+///
+/// #[rstest]
+/// #[retries(2)]
+/// fn flaky(attempt: usize) {
+///     assert!(attempt >= 2, "the service is not ready yet")
+/// }
+/// ```
+///
+/// ## Tags
+///
+/// Use `#[tag("name", ...)]` on the test, on a case or on a `#[values(...)]` value to
+/// select the tests at runtime by the `RSTEST_TAGS` environment variable, without
+/// recompiling them: for instance `RSTEST_TAGS=fast,!db cargo test` runs the tests
+/// tagged by `fast` and skips the ones tagged by `db`. A test takes the tags of its
+/// function, case and values. Tests without tags always run.
+///
+/// ```
+/// # use rstest::rstest;
+/// # fn sum(v: &[u64]) -> u64 { v.iter().sum() }
+/// #[rstest]
+/// #[case::small(vec![1, 2])]
+/// #[tag("slow")]
+/// #[case::huge((0..100_000).collect())]
+/// #[tag("math")]
+/// fn sum_all(#[case] values: Vec<u64>, #[values(1, #[tag("db")] 2)] factor: u64) {
+///     // Generates `case_1_small::factor_1_1_tag_math`,
+///     // `case_1_small::factor_2_2_tag_db_tag_math`,
+///     // `case_2_huge::factor_1_1_tag_math_tag_slow` and
+///     // `case_2_huge::factor_2_2_tag_db_tag_math_tag_slow`
+///     assert_eq!(sum(&values) * factor, values.iter().map(|v| v * factor).sum())
+/// }
+/// ```
+///
+/// The tags are also appended to the test names, so you can list them by
+/// `cargo test -- --list` and filter them by the test harness too (e.g.
+/// `cargo test -- --skip tag_slow`). The Rust test harness cannot ignore a test that is
+/// already running: the tests filtered out by `RSTEST_TAGS` write that they are skipped
+/// on the standard error and the harness counts them as passed (`ok`). See [`tags`]
+/// module for the filter syntax.
+///
+/// ## Tests manifest
+///
+/// With the `manifest` feature every generated test is registered in the
+/// `rstest::manifest` registry with its case (index, description and arguments), its
+/// `#[values(...)]` and `#[files(...)]` values, its tags and their source lines: external
+/// tools can map a name like `my_test::case_3_overflow::x_2_7` back to the source rows
+/// by the `rstest::manifest::tests()` entries or the JSON dumped by
+/// `rstest::manifest::to_json()`.
+///
 /// ## Inject Test Attribute
 ///
 /// If you would like to use another `test` attribute for your test you can simply
@@ -0,0 +1,236 @@
+//! The registry of the tests generated by `#[rstest]` (`manifest` feature).
+//!
+//! Every generated test registers a [`TestEntry`] with the case, the values and the tags
+//! that generated it and where they are in the source code: use [`tests()`] to read them
+//! or [`to_json()`] to dump them for external tools, for instance by a test like
+//!
+//! ```
+//! #[test]
+//! fn dump_the_tests_manifest() {
+//!     std::fs::write(
+//!         concat!(env!("CARGO_MANIFEST_DIR"), "/target/rstest-manifest.json"),
+//!         rstest::manifest::to_json(),
+//!     )
+//!     .unwrap();
+//! }
+//! ```
+//!
+//! The registry is filled before `main()` runs, so it contains the tests compiled in the
+//! current test binary: also the ones that the test harness filters out. The tests
+//! generated at runtime (`#[files(...)]` arguments resolved at runtime,
+//! `#[cases_from_fn(...)]` and `#[random(...)]`) are registered as a single test.
+
+#[doc(hidden)]
+pub use inventory;
+
+/// A test generated by `#[rstest]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestEntry {
+    /// The path of the module that contains the test (`module_path!()`).
+    pub module: &'static str,
+    /// The name of the test function in its module.
+    pub name: &'static str,
+    /// The name of the `#[rstest]` function.
+    pub function: &'static str,
+    /// The source file of the `#[rstest]` function.
+    pub file: &'static str,
+    /// The line of the `#[rstest]` function's name.
+    pub line: u32,
+    /// The test's case, if any.
+    pub case: Option<CaseEntry>,
+    /// The test's `#[values(...)]` or `#[files(...)]` values, in the arguments' order.
+    pub values: &'static [ValueEntry],
+    /// The test's tags, sorted.
+    pub tags: &'static [&'static str],
+}
+
+/// The `#[case(...)]` that generated a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaseEntry {
+    /// The case's index, starting from 1 like in the test name.
+    pub index: usize,
+    /// The case's description (`#[case::description(...)]`), if any.
+    pub description: Option<&'static str>,
+    /// The `#[case]` arguments' names and expressions.
+    pub args: &'static [(&'static str, &'static str)],
+    /// The line of the case.
+    pub line: u32,
+}
+
+/// The value of a `#[values(...)]` or `#[files(...)]` argument that generated a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueEntry {
+    /// The argument's name.
+    pub arg: &'static str,
+    /// The value's index, starting from 1 like in the test name.
+    pub index: usize,
+    /// The value's expression.
+    pub value: &'static str,
+    /// The value's name (`name => expression`) or the file path relative to the glob's
+    /// base folder, if any.
+    pub description: Option<&'static str>,
+    /// The `#[files(...)]` matched file path relative to the glob's base folder, if any.
+    pub file: Option<&'static str>,
+    /// The line of the value.
+    pub line: u32,
+}
+
+inventory::collect!(TestEntry);
+
+impl TestEntry {
+    /// The test name as reported by the test harness: the module path without the crate
+    /// name and the test function name.
+    pub fn test_name(&self) -> String {
+        match self.module.split_once("::") {
+            Some((_krate, path)) => format!("{path}::{}", self.name),
+            None => self.name.to_owned(),
+        }
+    }
+
+    fn to_json(self) -> String {
+        let case = match self.case {
+            Some(case) => format!(
+                r#"{{"index":{},"description":{},"args":{{{}}},"line":{}}}"#,
+                case.index,
+                json_option(case.description),
+                case.args
+                    .iter()
+                    .map(|(arg, value)| format!("{}:{}", json_str(arg), json_str(value)))
+                    .collect::<Vec<_>>()
+                    .join(","),
+                case.line
+            ),
+            None => "null".to_owned(),
+        };
+        let values = self
+            .values
+            .iter()
+            .map(|value| {
+                format!(
+                    r#"{{"arg":{},"index":{},"value":{},"description":{},"file":{},"line":{}}}"#,
+                    json_str(value.arg),
+                    value.index,
+                    json_str(value.value),
+                    json_option(value.description),
+                    json_option(value.file),
+                    value.line
+                )
+            })
+            .collect::<Vec<_>>();
+        format!(
+            r#"{{"name":{},"function":{},"file":{},"line":{},"case":{case},"values":[{}],"tags":[{}]}}"#,
+            json_str(&self.test_name()),
+            json_str(self.function),
+            json_str(self.file),
+            self.line,
+            values.join(","),
+            self.tags
+                .iter()
+                .map(|tag| json_str(tag))
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    }
+}
+
+/// All the tests generated by `#[rstest]` in the current test binary, sorted by name.
+pub fn tests() -> Vec<&'static TestEntry> {
+    let mut tests = inventory::iter::<TestEntry>.into_iter().collect::<Vec<_>>();
+    tests.sort_by_key(|test| (test.module, test.name));
+    tests
+}
+
+/// The [`tests()`] as a JSON array: one object for every test with the `name`,
+/// `function`, `file`, `line`, `case`, `values` and `tags` fields.
+pub fn to_json() -> String {
+    format!(
+        "[\n{}\n]",
+        tests()
+            .into_iter()
+            .map(|test| test.to_json())
+            .collect::<Vec<_>>()
+            .join(",\n")
+    )
+}
+
+fn json_str(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_option(s: Option<&str>) -> String {
+    s.map(json_str).unwrap_or_else(|| "null".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY: TestEntry = TestEntry {
+        module: "my_crate::my_test::case_3_overflow",
+        name: "x_2_7",
+        function: "my_test",
+        file: "tests/my.rs",
+        line: 12,
+        case: Some(CaseEntry {
+            index: 3,
+            description: Some("overflow"),
+            args: &[("a", "u8 :: MAX")],
+            line: 10,
+        }),
+        values: &[ValueEntry {
+            arg: "x",
+            index: 2,
+            value: "\"7\"",
+            description: None,
+            file: None,
+            line: 11,
+        }],
+        tags: &["slow"],
+    };
+
+    #[test]
+    fn return_the_test_name() {
+        assert_eq!("my_test::case_3_overflow::x_2_7", ENTRY.test_name());
+        assert_eq!(
+            "single",
+            TestEntry {
+                module: "my_crate",
+                name: "single",
+                ..ENTRY
+            }
+            .test_name()
+        );
+    }
+
+    #[test]
+    fn render_the_entry_as_json() {
+        assert_eq!(
+            concat!(
+                r#"{"name":"my_test::case_3_overflow::x_2_7","function":"my_test","file":"tests/my.rs","line":12,"#,
+                r#""case":{"index":3,"description":"overflow","args":{"a":"u8 :: MAX"},"line":10},"#,
+                r#""values":[{"arg":"x","index":2,"value":"\"7\"","description":null,"file":null,"line":11}],"#,
+                r#""tags":["slow"]}"#
+            ),
+            ENTRY.to_json()
+        );
+    }
+
+    #[test]
+    fn escape_the_json_strings() {
+        assert_eq!(r#""a\"b\\c\nd\u0001""#, json_str("a\"b\\c\nd\u{1}"));
+    }
+}
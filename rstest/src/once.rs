@@ -0,0 +1,233 @@
+//! Runtime support for `#[once]` fixtures.
+use std::{
+    any::{Any, TypeId},
+    future::Future,
+    pin::Pin,
+    sync::{Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+};
+
+/// The cells of the generic `#[once]` fixtures: a fixture can't define a `static` that
+/// depends on its generic types, so we leak a cell for every key and type.
+type Cells = Vec<(TypeId, &'static (dyn Any + Send + Sync))>;
+
+static CELLS: Mutex<Cells> = Mutex::new(Vec::new());
+
+fn cell<K: 'static, C: Any + Send + Sync>(new: impl FnOnce() -> C) -> &'static C {
+    let key = TypeId::of::<(K, C)>();
+    let mut cells = CELLS.lock().unwrap_or_else(|e| e.into_inner());
+    let cell = match cells.iter().find(|(k, _)| *k == key) {
+        Some((_, cell)) => *cell,
+        None => {
+            let cell: &'static C = Box::leak(Box::new(new()));
+            cells.push((key, cell));
+            cell
+        }
+    };
+    cell.downcast_ref()
+        .expect("The cell's type is part of the key")
+}
+
+/// Return the value of the `#[once]` fixture identified by the key `K` or compute it by
+/// `init`. Generic fixtures use their concrete types in the key to get a value for each of
+/// them. The value will never be dropped.
+pub fn generic<K: 'static, T: Sync + 'static>(init: impl FnOnce() -> T) -> &'static T {
+    let cell = cell::<K, Mutex<Option<&'static T>>>(|| Mutex::new(None));
+    let mut value = cell.lock().unwrap_or_else(|e| e.into_inner());
+    value.get_or_insert_with(|| Box::leak(Box::new(init())))
+}
+
+/// Like [`generic`] but for `async` fixtures.
+pub async fn generic_async<K, T, F, Fut>(init: F) -> &'static T
+where
+    K: 'static,
+    T: Sync + 'static,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    cell::<K, AsyncOnceCell<T>>(AsyncOnceCell::new)
+        .get_or_init(init)
+        .await
+}
+
+struct State<T: 'static> {
+    value: Option<&'static T>,
+    running: bool,
+    wakers: Vec<Waker>,
+}
+
+/// A cell that can be initialized just once by an async computation. It doesn't depend on
+/// any async runtime: the tasks that ask for the value while another task is computing it
+/// are woken up when the value is ready.
+///
+/// If the task that computes the value is cancelled (or panics) the next one that asks for
+/// the value will compute it.
+pub struct AsyncOnceCell<T: 'static> {
+    state: Mutex<State<T>>,
+}
+
+impl<T: 'static> AsyncOnceCell<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                value: None,
+                running: false,
+                wakers: Vec::new(),
+            }),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Return the cell's value or compute it by `init` if the cell is empty. The value
+    /// will never be dropped.
+    pub async fn get_or_init<F, Fut>(&'static self, init: F) -> &'static T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        if let Some(value) = (WaitTurn { cell: self }).await {
+            return value;
+        }
+        let running = Running { cell: self };
+        let value: &'static T = Box::leak(Box::new(init().await));
+        running.complete(value);
+        value
+    }
+}
+
+/// Resolve to the cell's value if it's ready or to `None` if the current task should
+/// compute it.
+struct WaitTurn<T: 'static> {
+    cell: &'static AsyncOnceCell<T>,
+}
+
+impl<T: 'static> Future for WaitTurn<T> {
+    type Output = Option<&'static T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.cell.state();
+        if let Some(value) = state.value {
+            return Poll::Ready(Some(value));
+        }
+        if !state.running {
+            state.running = true;
+            return Poll::Ready(None);
+        }
+        if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+/// Wake up the waiting tasks when the computation ends: if it was cancelled one of them
+/// will compute the value.
+struct Running<T: 'static> {
+    cell: &'static AsyncOnceCell<T>,
+}
+
+impl<T: 'static> Running<T> {
+    fn complete(self, value: &'static T) {
+        self.cell.state().value = Some(value);
+    }
+}
+
+impl<T: 'static> Drop for Running<T> {
+    fn drop(&mut self) {
+        let wakers = {
+            let mut state = self.cell.state();
+            state.running = false;
+            std::mem::take(&mut state.wakers)
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[async_std::test]
+    async fn compute_the_value_just_once() {
+        static CELL: AsyncOnceCell<u32> = AsyncOnceCell::new();
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let init = || async {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            async_std::task::sleep(std::time::Duration::from_millis(10)).await;
+            42
+        };
+
+        let tasks = (0..10)
+            .map(|_| async_std::task::spawn(CELL.get_or_init(init)))
+            .collect::<Vec<_>>();
+        for task in tasks {
+            assert_eq!(42, *task.await);
+        }
+
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+    }
+
+    #[async_std::test]
+    async fn compute_the_value_again_if_the_first_computation_is_cancelled() {
+        static CELL: AsyncOnceCell<u32> = AsyncOnceCell::new();
+
+        let cancelled = async_std::future::timeout(
+            std::time::Duration::from_millis(10),
+            CELL.get_or_init(|| async {
+                async_std::task::sleep(std::time::Duration::from_secs(60)).await;
+                0
+            }),
+        )
+        .await;
+
+        assert!(cancelled.is_err());
+        assert_eq!(42, *CELL.get_or_init(|| async { 42 }).await);
+    }
+
+    #[test]
+    fn compute_a_value_for_each_generic_key() {
+        struct A;
+        struct B;
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn init(v: u32) -> impl FnOnce() -> u32 {
+            move || {
+                CALLS.fetch_add(1, Ordering::SeqCst);
+                v
+            }
+        }
+
+        assert_eq!(1, *generic::<A, _>(init(1)));
+        assert_eq!(2, *generic::<B, _>(init(2)));
+        assert_eq!(1, *generic::<A, _>(init(3)));
+        assert_eq!("a", *generic::<A, _>(|| "a"));
+
+        assert_eq!(2, CALLS.load(Ordering::SeqCst));
+    }
+
+    #[async_std::test]
+    async fn compute_an_async_value_for_each_generic_key() {
+        struct A;
+        struct B;
+
+        assert_eq!(1, *generic_async::<A, _, _, _>(|| async { 1 }).await);
+        assert_eq!(2, *generic_async::<B, _, _, _>(|| async { 2 }).await);
+        assert_eq!(1, *generic_async::<A, _, _, _>(|| async { 3 }).await);
+    }
+
+    #[tokio::test]
+    async fn not_depend_on_the_runtime() {
+        static CELL: AsyncOnceCell<u32> = AsyncOnceCell::new();
+
+        let (a, b) = tokio::join!(
+            CELL.get_or_init(|| async { 42 }),
+            CELL.get_or_init(|| async { 0 })
+        );
+
+        assert_eq!((42, 42), (*a, *b));
+    }
+}
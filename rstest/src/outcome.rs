@@ -0,0 +1,152 @@
+//! The outcomes of the test bodies that `rstest` calls more than once in the same test:
+//! the runtime expanded files, the cases, the random values and the retries.
+use std::{
+    any::Any,
+    fmt::{Debug, Display},
+    future::Future,
+    io::Write,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::teardown::Scope;
+
+/// The outcomes of a test body that can be checked: `()` and `Result<T, E>`.
+pub trait Outcome {
+    /// The failure message, if any.
+    fn failure(&self) -> Option<String>;
+    /// The outcome of the whole test when all the calls succeed.
+    fn success() -> Self;
+}
+
+impl Outcome for () {
+    fn failure(&self) -> Option<String> {
+        None
+    }
+
+    fn success() -> Self {}
+}
+
+impl<T: Default, E: Debug> Outcome for Result<T, E> {
+    fn failure(&self) -> Option<String> {
+        self.as_ref().err().map(|e| format!("Error: {e:?}"))
+    }
+
+    fn success() -> Self {
+        Ok(T::default())
+    }
+}
+
+/// Call `test` in its own teardown [`Scope`], catching its panics: return its outcome if it
+/// succeeds or its failure message. The resources registered by `test` are released before
+/// returning, so they don't pile up across the iterations or the attempts.
+pub(crate) fn isolated<O: Outcome>(test: impl FnOnce() -> O) -> Result<O, String> {
+    catch_unwind(AssertUnwindSafe(|| {
+        let _scope = Scope::new();
+        test()
+    }))
+    .map_err(panic_message)
+    .and_then(|outcome| outcome.failure().map_or(Ok(outcome), Err))
+}
+
+/// Await `test` in its own teardown [`Scope`], catching its panics: the async version of
+/// [`isolated`].
+pub(crate) async fn isolated_async<O: Outcome>(test: impl Future<Output = O>) -> Result<O, String> {
+    CatchUnwind(Box::pin(async {
+        let _scope = Scope::new();
+        test.await
+    }))
+    .await
+    .map_err(panic_message)
+    .and_then(|outcome| outcome.failure().map_or(Ok(outcome), Err))
+}
+
+/// A future that catches the panics of every poll of the inner one.
+struct CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = &mut self.get_mut().0;
+        match catch_unwind(AssertUnwindSafe(|| inner.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_owned())
+}
+
+/// Skip the running test: write why on the standard error and return the success.
+///
+/// The test harness cannot skip a test that is already running, so it counts the skipped
+/// test as passed (`ok`), not as ignored.
+pub(crate) fn skip<O: Outcome>(reason: impl Display) -> O {
+    let thread = std::thread::current();
+    let name = thread.name().unwrap_or("test");
+    let _ = writeln!(std::io::stderr(), "test {name} ... skipped, {reason}");
+    O::success()
+}
+
+/// The report of the failed items: a line for each of them.
+pub(crate) fn report(total: usize, items: &str, failures: &[(impl Display, String)]) -> String {
+    let mut report = format!("{} of {total} {items} failed:", failures.len());
+    for (item, message) in failures {
+        report.push_str(&format!("\n  {item}: {message}"));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teardown::register;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[test]
+    fn release_the_resources_of_each_call() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let _outer = Scope::new();
+        for n in 0..3 {
+            let l = log.clone();
+            let outcome = isolated(|| {
+                let l = l.clone();
+                register(move || l.borrow_mut().push(n));
+                assert_ne!(1, n, "one is wrong");
+            });
+            assert_eq!(n == 1, outcome.is_err());
+            assert_eq!((0..=n).collect::<Vec<_>>(), *log.borrow());
+        }
+    }
+
+    #[test]
+    fn report_the_teardown_failure() {
+        let outcome = isolated(|| register(|| panic!("teardown failed")));
+
+        assert_eq!(Err("teardown failed".to_owned()), outcome);
+    }
+
+    #[test]
+    fn skip_with_the_success() {
+        skip::<()>("not selected");
+        assert_eq!(Ok(()), skip::<Result<(), String>>("not selected"));
+    }
+
+    #[test]
+    fn return_the_error_message_or_the_outcome() {
+        assert_eq!(Ok(Ok(42)), isolated(|| Ok::<_, &str>(42)));
+        assert_eq!(
+            Err(r#"Error: "wrong""#.to_owned()),
+            isolated(|| Err::<u32, _>("wrong"))
+        );
+    }
+}
@@ -0,0 +1,478 @@
+//! Property based tests: the values of the `#[random(strategy)]` arguments are drawn by
+//! the [`Strategy`]s and, when the test fails, shrunk to a minimal counterexample.
+//!
+//! A strategy is a [`Strategy`] implementation: the integer ranges, [`any()`] for the
+//! types that implement [`Arbitrary`], [`vec()`] and [`from_fn()`] are provided, but you can
+//! implement your own.
+//!
+//! ```
+//! use rstest::{rstest, random::vec};
+//!
+//! #[rstest]
+//! fn sort_is_idempotent(#[random(vec(0..100i32, 0..20))] mut values: Vec<i32>) {
+//!     values.sort();
+//!     let sorted = values.clone();
+//!     values.sort();
+//!     assert_eq!(sorted, values)
+//! }
+//! ```
+//!
+//! Every run uses a new seed: the failure message contains it and you can use
+//! `RSTEST_RANDOM_SEED` environment variable or the `seed = ...` option to replay the
+//! same values. The tests panic if `RSTEST_RANDOM_SEED` is not an unsigned integer.
+use std::{
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+};
+
+use crate::outcome::{isolated, Outcome};
+
+/// The environment variable that overrides the random seed.
+pub const SEED_ENV: &str = "RSTEST_RANDOM_SEED";
+
+/// The default number of the generated cases.
+pub const DEFAULT_CASES: u32 = 256;
+
+/// The maximum number of test calls to shrink a counterexample.
+const MAX_SHRINK_CALLS: usize = 1024;
+
+/// A deterministic pseudo random number generator (SplitMix64).
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// A generator that always draws the same numbers for the same `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// The next random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A random number in `0..n`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is 0.
+    pub fn below(&mut self, n: u128) -> u128 {
+        assert!(n > 0, "Cannot draw a value from an empty range");
+        (((self.next_u64() as u128) << 64) | self.next_u64() as u128) % n
+    }
+}
+
+/// How to generate the random values and how to shrink them.
+pub trait Strategy {
+    /// The generated values.
+    type Value: Clone + Debug;
+
+    /// Draw a new value.
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// The simpler values to try, the simplest first, when `value` fails the test.
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        vec![]
+    }
+}
+
+/// The values in `low..=high` that are simpler than `value`: the closest to zero first.
+fn shrink_int(low: i128, high: i128, value: i128) -> Vec<i128> {
+    let target = 0.clamp(low, high);
+    let mut candidates = vec![target, target + (value - target) / 2];
+    candidates.push(value - (value - target).signum());
+    candidates.dedup();
+    candidates.retain(|&c| c != value);
+    candidates
+}
+
+macro_rules! int_strategies {
+    ($($t:ty),*) => {
+        $(
+            impl Strategy for Range<$t> {
+                type Value = $t;
+
+                fn generate(&self, rng: &mut Rng) -> $t {
+                    let span = (self.end as i128 - self.start as i128).max(0) as u128;
+                    (self.start as i128 + rng.below(span) as i128) as $t
+                }
+
+                fn shrink(&self, value: &$t) -> Vec<$t> {
+                    shrink_int(self.start as i128, self.end as i128 - 1, *value as i128)
+                        .into_iter()
+                        .map(|v| v as $t)
+                        .collect()
+                }
+            }
+
+            impl Strategy for RangeInclusive<$t> {
+                type Value = $t;
+
+                fn generate(&self, rng: &mut Rng) -> $t {
+                    let span = (*self.end() as i128 - *self.start() as i128 + 1).max(0) as u128;
+                    (*self.start() as i128 + rng.below(span) as i128) as $t
+                }
+
+                fn shrink(&self, value: &$t) -> Vec<$t> {
+                    shrink_int(*self.start() as i128, *self.end() as i128, *value as i128)
+                        .into_iter()
+                        .map(|v| v as $t)
+                        .collect()
+                }
+            }
+
+            impl Arbitrary for $t {
+                fn arbitrary(rng: &mut Rng) -> Self {
+                    rng.next_u64() as $t
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    shrink_int(<$t>::MIN as i128, <$t>::MAX as i128, *self as i128)
+                        .into_iter()
+                        .map(|v| v as $t)
+                        .collect()
+                }
+            }
+        )*
+    };
+}
+
+int_strategies!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// The types that have a default strategy: see [`any()`].
+pub trait Arbitrary: Clone + Debug {
+    /// Draw a new value.
+    fn arbitrary(rng: &mut Rng) -> Self;
+
+    /// The simpler values to try, the simplest first.
+    fn shrink(&self) -> Vec<Self> {
+        vec![]
+    }
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        rng.next_u64() & 1 == 1
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        match self {
+            true => vec![false],
+            false => vec![],
+        }
+    }
+}
+
+/// The strategy returned by [`any()`].
+#[derive(Debug)]
+pub struct Any<T>(PhantomData<T>);
+
+/// Any value of the type `T`.
+pub fn any<T: Arbitrary>() -> Any<T> {
+    Any(PhantomData)
+}
+
+impl<T: Arbitrary> Strategy for Any<T> {
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng) -> T {
+        T::arbitrary(rng)
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        value.shrink()
+    }
+}
+
+/// The strategy returned by [`vec()`].
+#[derive(Debug)]
+pub struct VecStrategy<S> {
+    element: S,
+    len: Range<usize>,
+}
+
+/// The vectors with a length in `len` and the elements drawn by `element`.
+pub fn vec<S: Strategy>(element: S, len: Range<usize>) -> VecStrategy<S> {
+    VecStrategy { element, len }
+}
+
+impl<S: Strategy> Strategy for VecStrategy<S> {
+    type Value = Vec<S::Value>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let len = self.len.generate(rng);
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let min = self.len.start;
+        let mut candidates = vec![];
+        // Shorter vectors first: drop the second half and then every single element
+        if value.len() / 2 >= min && value.len() > 1 {
+            candidates.push(value[..value.len() / 2].to_vec());
+        }
+        if value.len() > min {
+            candidates.extend((0..value.len()).map(|i| {
+                let mut shorter = value.clone();
+                shorter.remove(i);
+                shorter
+            }));
+        }
+        for (i, element) in value.iter().enumerate() {
+            if let Some(simpler) = self.element.shrink(element).into_iter().next() {
+                let mut candidate = value.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+/// The strategy returned by [`from_fn()`].
+pub struct FromFn<F>(F);
+
+/// The values returned by `f`: they cannot be shrunk.
+pub fn from_fn<T: Clone + Debug, F: Fn(&mut Rng) -> T>(f: F) -> FromFn<F> {
+    FromFn(f)
+}
+
+impl<T: Clone + Debug, F: Fn(&mut Rng) -> T> Strategy for FromFn<F> {
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng) -> T {
+        (self.0)(rng)
+    }
+}
+
+/// The test's random arguments: a tuple of values that can be described by the
+/// arguments' names.
+#[doc(hidden)]
+pub trait Arguments {
+    fn describe(&self, names: &[&str]) -> String;
+}
+
+// The tuples up to the maximum number of `#[random(...)]` arguments that the macro accepts.
+macro_rules! tuple_strategies {
+    ($(($($s:ident $i:tt),+)),*) => {
+        $(
+            impl<$($s: Strategy),+> Strategy for ($($s,)+) {
+                type Value = ($($s::Value,)+);
+
+                fn generate(&self, rng: &mut Rng) -> Self::Value {
+                    ($(self.$i.generate(rng),)+)
+                }
+
+                fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                    let mut candidates = vec![];
+                    $(
+                        for simpler in self.$i.shrink(&value.$i) {
+                            let mut candidate = value.clone();
+                            candidate.$i = simpler;
+                            candidates.push(candidate);
+                        }
+                    )+
+                    candidates
+                }
+            }
+
+            impl<$($s: Debug),+> Arguments for ($($s,)+) {
+                fn describe(&self, names: &[&str]) -> String {
+                    [$(format!("{} = {:?}", names[$i], self.$i)),+].join(", ")
+                }
+            }
+        )*
+    };
+}
+
+tuple_strategies!(
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3),
+    (A 0, B 1, C 2, D 3, E 4),
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+);
+
+/// The failure message of `test` called with `value`, if any.
+fn failure<V, O: Outcome>(test: &mut impl FnMut(V) -> O, value: V) -> Option<String> {
+    isolated(|| test(value)).err()
+}
+
+/// The given `seed`, the [`SEED_ENV`] one or a new one.
+///
+/// # Panics
+///
+/// If [`SEED_ENV`] is not an unsigned integer: the values could not be replayed.
+fn resolve_seed(seed: Option<u64>) -> u64 {
+    seed.or_else(|| std::env::var_os(SEED_ENV).map(|value| parse_seed(&value.to_string_lossy())))
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or_default()
+        })
+}
+
+fn parse_seed(value: &str) -> u64 {
+    value
+        .trim()
+        .parse()
+        .unwrap_or_else(|_| panic!("Invalid {SEED_ENV}={value}: it should be an unsigned integer"))
+}
+
+/// Call `test` with `cases` (default [`DEFAULT_CASES`]) values drawn by `strategy`: on
+/// the first failure shrink the values to a minimal counterexample.
+///
+/// # Panics
+///
+/// If `test` fails: the message contains the seed to replay the same values, the minimal
+/// counterexample described by the arguments' `names` and its failure.
+pub fn check<S, O>(
+    strategy: S,
+    cases: Option<u32>,
+    seed: Option<u64>,
+    names: &[&str],
+    mut test: impl FnMut(S::Value) -> O,
+) -> O
+where
+    S: Strategy,
+    S::Value: Arguments,
+    O: Outcome,
+{
+    let seed = resolve_seed(seed);
+    let cases = cases.unwrap_or(DEFAULT_CASES);
+    let mut rng = Rng::new(seed);
+    for case in 1..=cases {
+        let value = strategy.generate(&mut rng);
+        if let Some(message) = failure(&mut test, value.clone()) {
+            let (value, message) = shrink(&strategy, &mut test, value, message);
+            panic!(
+                "Property failed after {case} of {cases} cases (seed: {seed}, replay it by {SEED_ENV}={seed})\nMinimal failing input: {}\n{message}",
+                value.describe(names)
+            );
+        }
+    }
+    O::success()
+}
+
+/// Replace the failing `value` with a simpler one that still fails until none of them
+/// fails.
+fn shrink<S: Strategy, O: Outcome>(
+    strategy: &S,
+    test: &mut impl FnMut(S::Value) -> O,
+    mut value: S::Value,
+    mut message: String,
+) -> (S::Value, String) {
+    let mut calls = 0;
+    'simpler: while calls < MAX_SHRINK_CALLS {
+        for candidate in strategy.shrink(&value) {
+            calls += 1;
+            if let Some(m) = failure(test, candidate.clone()) {
+                value = candidate;
+                message = m;
+                continue 'simpler;
+            }
+            if calls >= MAX_SHRINK_CALLS {
+                break;
+            }
+        }
+        break;
+    }
+    (value, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outcome::panic_message;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn draw_the_same_values_for_the_same_seed() {
+        let strategy = (0..1000u32, vec(any::<i8>(), 0..10));
+        let draw = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10)
+                .map(|_| strategy.generate(&mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(draw(42), draw(42));
+        assert_ne!(draw(42), draw(43));
+    }
+
+    #[test]
+    fn draw_the_values_in_the_range() {
+        let mut rng = Rng::new(7);
+
+        assert!((0..100)
+            .map(|_| (-5..=5i64).generate(&mut rng))
+            .all(|v| (-5..=5).contains(&v)));
+        assert!((0..100)
+            .map(|_| (250..255u8).generate(&mut rng))
+            .all(|v| (250..255).contains(&v)));
+    }
+
+    #[test]
+    fn pass_if_all_the_cases_pass() {
+        let mut calls = 0;
+
+        check((0..10u32,), Some(20), Some(1), &["n"], |(n,)| {
+            calls += 1;
+            assert!(n < 10)
+        });
+
+        assert_eq!(20, calls);
+    }
+
+    #[test]
+    fn shrink_to_a_minimal_counterexample() {
+        let message = catch_unwind(|| {
+            check(
+                (0..1000u32, vec(0..100u8, 0..10)),
+                None,
+                Some(3),
+                &["n", "values"],
+                |(n, values)| assert!(n < 10 || values.is_empty(), "bad"),
+            )
+        })
+        .map_err(panic_message)
+        .unwrap_err();
+
+        assert!(message.contains("(seed: 3, "), "{message}");
+        assert!(
+            message.contains("Minimal failing input: n = 10, values = [0]\nbad"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn read_the_seed() {
+        assert_eq!(42, parse_seed(" 42"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid RSTEST_RANDOM_SEED=0x2a: it should be an unsigned integer")]
+    fn reject_an_invalid_seed() {
+        parse_seed("0x2a");
+    }
+
+    #[test]
+    fn replay_the_seed() {
+        let draw = || {
+            let mut drawn = vec![];
+            check((any::<u64>(),), Some(5), Some(11), &["n"], |(n,)| {
+                drawn.push(n)
+            });
+            drawn
+        };
+
+        assert_eq!(draw(), draw());
+    }
+}
@@ -0,0 +1,136 @@
+use std::future::Future;
+
+use crate::{
+    outcome::{isolated, isolated_async, Outcome},
+    teardown::Scope,
+};
+
+/// Call `test` until it succeeds, for at most `retries + 1` times. Every attempt releases
+/// its resources before the next one starts. The last attempt is not caught: its panic or
+/// error is the test result.
+pub fn execute_with_retries<O: Outcome>(retries: usize, mut test: impl FnMut() -> O) -> O {
+    let attempts = retries + 1;
+    for attempt in 1..attempts {
+        match isolated(&mut test) {
+            Ok(outcome) => return outcome,
+            Err(failure) => eprintln!("Attempt {attempt} of {attempts} failed: {failure}"),
+        }
+    }
+    let _scope = Scope::new();
+    test()
+}
+
+/// The async version of [`execute_with_retries`]: `test` returns a new future, which
+/// resolves the fixtures again, for every attempt.
+pub async fn execute_with_retries_async<O: Outcome, F: Future<Output = O>>(
+    retries: usize,
+    mut test: impl FnMut() -> F,
+) -> O {
+    let attempts = retries + 1;
+    for attempt in 1..attempts {
+        match isolated_async(test()).await {
+            Ok(outcome) => return outcome,
+            Err(failure) => eprintln!("Attempt {attempt} of {attempts} failed: {failure}"),
+        }
+    }
+    let _scope = Scope::new();
+    test().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::teardown::register;
+    use std::{cell::Cell, rc::Rc};
+
+    #[test]
+    fn stop_at_the_first_success() {
+        let mut calls = 0;
+
+        execute_with_retries(3, || {
+            calls += 1;
+            assert!(calls > 1, "first attempt");
+        });
+
+        assert_eq!(2, calls);
+    }
+
+    #[test]
+    fn retry_on_errors() {
+        let mut calls = 0;
+
+        let result = execute_with_retries(2, || {
+            calls += 1;
+            if calls < 3 {
+                Err(calls)
+            } else {
+                Ok("done")
+            }
+        });
+
+        assert_eq!(Ok("done"), result);
+        assert_eq!(3, calls);
+    }
+
+    #[test]
+    fn return_the_last_error() {
+        let mut calls = 0;
+
+        let result = execute_with_retries(2, || -> Result<(), usize> {
+            calls += 1;
+            Err(calls)
+        });
+
+        assert_eq!(Err(3), result);
+    }
+
+    #[test]
+    fn release_the_resources_of_the_failed_attempts_before_retrying() {
+        let released = Rc::new(Cell::new(0));
+        let mut calls = 0;
+
+        execute_with_retries(2, || {
+            calls += 1;
+            assert_eq!(calls - 1, released.get(), "resources leaked");
+            let released = released.clone();
+            register(move || released.set(released.get() + 1));
+            assert!(calls > 2, "flaky");
+        });
+
+        assert_eq!(3, released.get());
+    }
+
+    #[test]
+    #[should_panic(expected = "always")]
+    fn panic_if_all_attempts_fail() {
+        execute_with_retries(1, || -> () { panic!("always") });
+    }
+
+    #[async_std::test]
+    async fn await_a_new_attempt_until_it_succeeds() {
+        let mut calls = 0;
+
+        let result = execute_with_retries_async(3, || {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                assert!(attempt > 1, "first attempt");
+                if attempt < 3 {
+                    Err(attempt)
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(Ok(()), result);
+        assert_eq!(3, calls);
+    }
+
+    #[async_std::test]
+    #[should_panic(expected = "always")]
+    async fn panic_if_all_async_attempts_fail() {
+        execute_with_retries_async(1, || async { panic!("always") }).await
+    }
+}
@@ -0,0 +1,377 @@
+//! Runtime support for `#[scope(group)]` and `#[scope(module)]` fixtures.
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::teardown::{self, TearDown};
+
+type Value = Arc<dyn Any + Send + Sync>;
+type Release = Box<dyn FnOnce() + Send>;
+type Cell = Arc<Mutex<Option<(Value, Option<Release>)>>>;
+
+/// The values shared in a scope: the key is the fixture type.
+struct Cells<K>(Vec<(K, Cell)>);
+
+impl<K> Default for Cells<K> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<K: PartialEq> Cells<K> {
+    const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    fn cell(&mut self, key: K) -> Cell {
+        match self.0.iter().find(|(k, _)| k == &key) {
+            Some((_, cell)) => cell.clone(),
+            None => {
+                let cell = Cell::default();
+                self.0.push((key, cell.clone()));
+                cell
+            }
+        }
+    }
+}
+
+fn get_or_init<T: Send + Sync + 'static>(
+    cell: Cell,
+    init: impl FnOnce() -> (T, Option<Release>),
+) -> Arc<T> {
+    let mut slot = cell.lock().unwrap_or_else(|e| e.into_inner());
+    let (value, _) = slot.get_or_insert_with(|| {
+        let (value, release) = init();
+        (Arc::new(value), release)
+    });
+    value
+        .clone()
+        .downcast()
+        .expect("The fixture type should not change")
+}
+
+/// Release the values in the reverse order of creation and return the first panic payload
+/// if some value's drop or release panics.
+fn release<K>(cells: Cells<K>) -> Option<Box<dyn Any + Send>> {
+    cells
+        .0
+        .into_iter()
+        .rev()
+        .filter_map(|(_, cell)| {
+            Arc::try_unwrap(cell)
+                .ok()?
+                .into_inner()
+                .unwrap_or_else(|e| e.into_inner())
+        })
+        .flat_map(|(value, release)| {
+            let dropped = catch_unwind(AssertUnwindSafe(move || drop(value))).err();
+            let released =
+                release.and_then(|release| catch_unwind(AssertUnwindSafe(release)).err());
+            dropped.into_iter().chain(released)
+        })
+        .fold(None, |first, payload| first.or(Some(payload)))
+}
+
+/// All the tests generated by the same `#[rstest]` function. `rstest` defines a `Group` for
+/// every test function with the number of its tests and every generated test enters its
+/// group before resolving its fixtures.
+///
+/// The group's fixtures are released when its last test ends. The test harness can run
+/// just a subset of the group's tests (name filters, `--skip`, ignored tests, ...): in this
+/// case the fixtures are released when the test process exits.
+pub struct Group {
+    path: &'static str,
+    tests: usize,
+    finished: AtomicUsize,
+    cells: Mutex<Cells<TypeId>>,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Vec<&'static Group>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The groups that hold some values: the ones that are not released by their last test are
+/// released at process exit.
+static GROUPS: Mutex<Vec<&'static Group>> = Mutex::new(Vec::new());
+
+extern "C" {
+    fn atexit(callback: extern "C" fn()) -> std::os::raw::c_int;
+}
+
+extern "C" fn release_groups() {
+    let groups = std::mem::take(&mut *GROUPS.lock().unwrap_or_else(|e| e.into_inner()));
+    for group in groups.into_iter().rev() {
+        group.release_and_report();
+    }
+}
+
+/// Register `group` to be released at process exit.
+fn release_at_exit(group: &'static Group) {
+    static REGISTER: std::sync::Once = std::sync::Once::new();
+    REGISTER.call_once(|| {
+        // SAFETY: `release_groups` is a plain function that never unwinds: every
+        // drop and teardown panic is caught by `release()`.
+        unsafe { atexit(release_groups) };
+    });
+    let mut groups = GROUPS.lock().unwrap_or_else(|e| e.into_inner());
+    if !groups.iter().any(|g| std::ptr::eq(*g, group)) {
+        groups.push(group);
+    }
+}
+
+impl Group {
+    /// Create a group named `path` (the module path of the group) that contains `tests`
+    /// tests.
+    pub const fn new(path: &'static str, tests: usize) -> Self {
+        Self {
+            path,
+            tests,
+            finished: AtomicUsize::new(0),
+            cells: Mutex::new(Cells::new()),
+        }
+    }
+
+    /// The module that contains the test function.
+    fn module(&self) -> &'static str {
+        self.path
+            .rsplit_once("::")
+            .map(|(module, _)| module)
+            .unwrap_or(self.path)
+    }
+
+    fn cells(&self) -> std::sync::MutexGuard<'_, Cells<TypeId>> {
+        self.cells.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Mark the start of a test in this group: the test ends when the returned value is
+    /// dropped.
+    pub fn enter(&'static self) -> Entered {
+        CURRENT.with(|c| c.borrow_mut().push(self));
+        Entered(self)
+    }
+
+    /// Mark the end of a test: the last one releases the group's values.
+    fn finish(&self) {
+        if self.finished.fetch_add(1, Ordering::SeqCst) + 1 == self.tests {
+            self.release_and_report();
+        }
+    }
+
+    /// Drop the group's values and call their guards.
+    fn release(&self) -> Option<Box<dyn Any + Send>> {
+        release(std::mem::take(&mut *self.cells()))
+    }
+
+    fn release_and_report(&self) {
+        if self.release().is_some() {
+            eprintln!(
+                "rstest: the teardown of a group fixture in {} panicked",
+                self.path
+            );
+        }
+    }
+
+    fn current() -> Option<&'static Group> {
+        CURRENT.with(|c| c.borrow().last().copied())
+    }
+}
+
+/// A running test in a [`Group`].
+#[must_use]
+pub struct Entered(&'static Group);
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        CURRENT.with(|c| c.borrow_mut().pop());
+        self.0.finish();
+    }
+}
+
+/// Resolve a `#[scope(group)]` fixture: `K` is the fixture type and `init` computes the
+/// value and its guard. The guard is called when the group's last test ends or, if the
+/// harness doesn't run all the group's tests, when the test process exits.
+///
+/// When there isn't any running group (i.e. the fixture is not used in a test generated
+/// by `rstest`) the value is not shared and its guard is registered in the current
+/// teardown scope.
+pub fn group<K: 'static, T: Send + Sync + 'static, G: TearDown + Send + 'static>(
+    init: impl FnOnce() -> (T, G),
+) -> Arc<T> {
+    match Group::current() {
+        Some(group) => {
+            let cell = group.cells().cell(TypeId::of::<K>());
+            get_or_init(cell, || {
+                let (value, guard) = init();
+                let release: Release = Box::new(move || guard.tear_down());
+                release_at_exit(group);
+                (value, Some(release))
+            })
+        }
+        None => {
+            let (value, guard) = init();
+            teardown::register(guard);
+            Arc::new(value)
+        }
+    }
+}
+
+static MODULES: Mutex<Cells<(&'static str, TypeId)>> = Mutex::new(Cells::new());
+
+/// Resolve a `#[scope(module)]` fixture: `K` is the fixture type and `init` computes the
+/// value. Module values are shared by all tests in the same module and are never dropped:
+/// neither their `Drop` implementation nor their teardown is ever called.
+///
+/// When there isn't any running group (i.e. the fixture is not used in a test generated
+/// by `rstest`) the value is not shared.
+pub fn module<K: 'static, T: Send + Sync + 'static>(init: impl FnOnce() -> T) -> Arc<T> {
+    match Group::current() {
+        Some(group) => {
+            let cell = MODULES
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .cell((group.module(), TypeId::of::<K>()));
+            get_or_init(cell, || (init(), None))
+        }
+        None => Arc::new(init()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Fixture;
+    struct OtherFixture;
+
+    #[test]
+    fn share_value_in_the_group_and_release_it_after_the_last_test() {
+        static GROUP: Group = Group::new("crate::tests::test_fn", 3);
+        static BUILT: AtomicUsize = AtomicUsize::new(0);
+        static RELEASED: AtomicUsize = AtomicUsize::new(0);
+        let resolve = || {
+            group::<Fixture, _, _>(|| {
+                BUILT.fetch_add(1, Ordering::SeqCst);
+                (42, || {
+                    RELEASED.fetch_add(1, Ordering::SeqCst);
+                })
+            })
+        };
+
+        let first = GROUP.enter();
+        let a = resolve();
+        let second = GROUP.enter();
+        let b = resolve();
+        drop(second);
+        drop(first);
+        assert_eq!(0, RELEASED.load(Ordering::SeqCst));
+        let c = {
+            let _test = GROUP.enter();
+            resolve()
+        };
+        assert_eq!(1, RELEASED.load(Ordering::SeqCst));
+
+        assert!(GROUP.release().is_none());
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(Arc::ptr_eq(&a, &c));
+        assert_eq!(1, BUILT.load(Ordering::SeqCst));
+        assert_eq!(1, RELEASED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn register_the_group_to_release_at_exit_just_once() {
+        static GROUP: Group = Group::new("crate::tests::registered", 3);
+
+        for _ in 0..2 {
+            let _test = GROUP.enter();
+            group::<Fixture, _, _>(|| (42, || {}));
+            group::<OtherFixture, _, _>(|| (42, || {}));
+        }
+
+        let registered = GROUPS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|g| std::ptr::eq(**g, &GROUP))
+            .count();
+        assert_eq!(1, registered);
+    }
+
+    #[test]
+    fn catch_the_panics_of_drop_and_teardown() {
+        struct PanicOnDrop;
+        impl Drop for PanicOnDrop {
+            fn drop(&mut self) {
+                panic!("drop")
+            }
+        }
+        static GROUP: Group = Group::new("crate::tests::panics", 2);
+        static RELEASED: AtomicUsize = AtomicUsize::new(0);
+
+        {
+            let _test = GROUP.enter();
+            group::<Fixture, _, _>(|| {
+                (PanicOnDrop, || {
+                    RELEASED.fetch_add(1, Ordering::SeqCst);
+                })
+            });
+            group::<OtherFixture, _, _>(|| (42, || panic!("teardown")));
+        }
+        let payload = GROUP.release().unwrap();
+
+        assert_eq!(Some(&"teardown"), payload.downcast_ref::<&str>());
+        assert_eq!(1, RELEASED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn use_a_value_for_each_fixture() {
+        static GROUP: Group = Group::new("crate::tests::test_fn", 1);
+
+        let _test = GROUP.enter();
+        let a = group::<Fixture, _, _>(|| (1, || {}));
+        let b = group::<OtherFixture, _, _>(|| (2, || {}));
+
+        assert_eq!((1, 2), (*a, *b));
+    }
+
+    #[test]
+    fn not_share_value_outside_a_group() {
+        let a = group::<Fixture, _, _>(|| (String::from("a"), || {}));
+        let b = group::<Fixture, _, _>(|| (String::from("b"), || {}));
+
+        assert_eq!(("a", "b"), (a.as_str(), b.as_str()));
+    }
+
+    #[test]
+    fn share_module_value_between_groups_in_the_same_module() {
+        static FIRST: Group = Group::new("crate::module_a::first", 1);
+        static SECOND: Group = Group::new("crate::module_a::second", 1);
+        static OTHER_MODULE: Group = Group::new("crate::module_b::first", 1);
+        static BUILT: AtomicUsize = AtomicUsize::new(0);
+        let resolve = || module::<Fixture, _>(|| BUILT.fetch_add(1, Ordering::SeqCst));
+
+        let a = {
+            let _test = FIRST.enter();
+            resolve()
+        };
+        let b = {
+            let _test = SECOND.enter();
+            resolve()
+        };
+        let c = {
+            let _test = OTHER_MODULE.enter();
+            resolve()
+        };
+
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(2, BUILT.load(Ordering::SeqCst));
+    }
+}
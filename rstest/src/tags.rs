@@ -0,0 +1,61 @@
+//! Select the tagged tests at runtime.
+//!
+//! The tests marked by `#[tag("name", ...)]` (on the test function, on a `#[case]` or on a
+//! `#[values(...)]` value) check the [`TAGS_ENV`] environment variable before running:
+//! it's a comma separated list of tags where `!tag` excludes the tests tagged by `tag`.
+//!
+//! A test runs if it has at least one of the required tags (when there are some) and none
+//! of the excluded ones: for instance `RSTEST_TAGS=fast,!db` runs the tests tagged by
+//! `fast` but not the ones tagged by `db`. Tests without tags are never filtered out, use
+//! the tags in their names (`_tag_<name>`) to select them by the test harness filters.
+//!
+//! The filtered out tests are skipped when they are already running: the test harness
+//! counts them as passed (`ok`), not as ignored, and they write that they are skipped on
+//! the standard error.
+use crate::outcome::{self, Outcome};
+
+/// The environment variable that selects the tagged tests.
+pub const TAGS_ENV: &str = "RSTEST_TAGS";
+
+/// Return whether a test with the given `tags` should run according to the
+/// [`TAGS_ENV`] environment variable.
+pub fn is_selected(tags: &[&str]) -> bool {
+    selected_by(&std::env::var(TAGS_ENV).unwrap_or_default(), tags)
+}
+
+fn selected_by(filter: &str, tags: &[&str]) -> bool {
+    let (excluded, required): (Vec<_>, Vec<_>) = filter
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .partition(|tag| tag.starts_with('!'));
+    let has = |tag: &str| tags.contains(&tag);
+    (required.is_empty() || required.iter().any(|&tag| has(tag)))
+        && !excluded.iter().any(|tag| has(tag[1..].trim()))
+}
+
+/// Skip a test that [`TAGS_ENV`] filters out: the test harness counts it as passed.
+#[doc(hidden)]
+pub fn skip<O: Outcome>(tags: &[&str]) -> O {
+    outcome::skip(format_args!(
+        "filtered out by {TAGS_ENV} (tags: {})",
+        tags.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_by_the_tags() {
+        assert!(selected_by("", &["slow"]));
+        assert!(selected_by("fast", &["fast", "db"]));
+        assert!(!selected_by("fast", &["slow"]));
+        assert!(selected_by("fast, slow", &["slow"]));
+        assert!(!selected_by("fast,!db", &["fast", "db"]));
+        assert!(selected_by("!db", &["fast"]));
+        assert!(!selected_by("! db", &["db"]));
+        assert!(selected_by(" , ", &["slow"]));
+    }
+}
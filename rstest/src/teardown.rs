@@ -0,0 +1,235 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+};
+
+/// Something that should be released when the test that requested it is done.
+///
+/// Every closure that takes no arguments is a `TearDown`: the closure is called to
+/// release the resource.
+pub trait TearDown {
+    fn tear_down(self);
+}
+
+impl<F: FnOnce()> TearDown for F {
+    fn tear_down(self) {
+        self()
+    }
+}
+
+/// Wrap a closure in a named type: useful when a `TearDown` should be stored in a struct
+/// field.
+pub struct TearDownClosure<F: FnOnce()>(pub F);
+
+impl<F: FnOnce()> TearDown for TearDownClosure<F> {
+    fn tear_down(self) {
+        self.0()
+    }
+}
+
+impl<F: FnOnce()> From<F> for TearDownClosure<F> {
+    fn from(closure: F) -> Self {
+        TearDownClosure(closure)
+    }
+}
+
+impl<A: TearDown, B: TearDown> TearDown for (A, B) {
+    fn tear_down(self) {
+        self.0.tear_down();
+        self.1.tear_down();
+    }
+}
+
+type Guard = Box<dyn FnOnce()>;
+
+thread_local! {
+    static SCOPES: RefCell<Vec<Vec<Guard>>> = const { RefCell::new(Vec::new()) };
+    static ORPHANS: Orphans = const { Orphans(RefCell::new(Vec::new())) };
+}
+
+/// Guards registered outside of any `Scope`: they are released when the thread ends.
+struct Orphans(RefCell<Vec<Guard>>);
+
+impl Drop for Orphans {
+    fn drop(&mut self) {
+        run_guards(std::mem::take(self.0.get_mut()));
+    }
+}
+
+/// The scope that collects all the guards registered while a test is running. When the
+/// scope is dropped (also if the test panics) all the collected guards are released in
+/// the reverse order of registration.
+///
+/// `rstest` opens a new scope in every test that it generates.
+#[must_use]
+pub struct Scope {
+    depth: usize,
+}
+
+impl Scope {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let depth = SCOPES.with(|s| {
+            let mut scopes = s.borrow_mut();
+            scopes.push(Vec::new());
+            scopes.len() - 1
+        });
+        Self { depth }
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let guards = SCOPES.with(|s| {
+            s.borrow_mut()
+                .drain(self.depth..)
+                .flatten()
+                .collect::<Vec<_>>()
+        });
+        if let Some(payload) = run_guards(guards) {
+            if !std::thread::panicking() {
+                resume_unwind(payload)
+            }
+        }
+    }
+}
+
+/// Release the guards in reverse order: a panicking guard doesn't prevent the others to be
+/// released and the first panic payload is returned.
+fn run_guards(guards: Vec<Guard>) -> Option<Box<dyn Any + Send>> {
+    guards
+        .into_iter()
+        .rev()
+        .filter_map(|g| catch_unwind(AssertUnwindSafe(g)).err())
+        .fold(None, |first, payload| first.or(Some(payload)))
+}
+
+/// Register the guard in the innermost open [`Scope`]. If there isn't any open scope the
+/// guard will be released when the current thread ends.
+pub fn register<G: TearDown + 'static>(guard: G) {
+    let guard: Guard = Box::new(move || guard.tear_down());
+    let guard = SCOPES.with(|s| match s.borrow_mut().last_mut() {
+        Some(scope) => {
+            scope.push(guard);
+            None
+        }
+        None => Some(guard),
+    });
+    if let Some(guard) = guard {
+        ORPHANS.with(|o| o.0.borrow_mut().push(guard));
+    }
+}
+
+/// Build the guard of the fixture's `value` by calling `f` with a reference to it. Used to
+/// implement `#[teardown(expression)]`.
+pub fn with_ref<T, G: TearDown, F: FnOnce(&T) -> G>(value: &T, f: F) -> G {
+    f(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    type Log = Rc<RefCell<Vec<&'static str>>>;
+
+    fn log() -> (Log, impl Fn(&'static str) -> Box<dyn FnOnce()>) {
+        let log = Rc::new(RefCell::new(vec![]));
+        let l = log.clone();
+        (log, move |msg| {
+            let l = l.clone();
+            Box::new(move || l.borrow_mut().push(msg))
+        })
+    }
+
+    #[test]
+    fn release_guards_in_reverse_order_when_scope_is_dropped() {
+        let (log, push) = log();
+        {
+            let _scope = Scope::new();
+            register(push("first"));
+            register(push("second"));
+            assert!(log.borrow().is_empty());
+        }
+
+        assert_eq!(vec!["second", "first"], *log.borrow());
+    }
+
+    #[test]
+    fn release_just_the_inner_scope_guards() {
+        let (log, push) = log();
+        let _outer = Scope::new();
+        register(push("outer"));
+        {
+            let _inner = Scope::new();
+            register(push("inner"));
+        }
+
+        assert_eq!(vec!["inner"], *log.borrow());
+    }
+
+    #[test]
+    fn release_guards_also_when_panic() {
+        let (log, push) = log();
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _scope = Scope::new();
+            register(push("guard"));
+            panic!("test failed");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(vec!["guard"], *log.borrow());
+    }
+
+    #[test]
+    fn release_all_guards_and_propagate_the_guard_panic() {
+        let (log, push) = log();
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _scope = Scope::new();
+            register(push("first"));
+            register(|| panic!("guard failed"));
+            register(push("last"));
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(vec!["last", "first"], *log.borrow());
+    }
+
+    #[test]
+    fn compose_guards_by_tuple() {
+        let (log, push) = log();
+        {
+            let _scope = Scope::new();
+            register((push("a"), push("b")));
+        }
+
+        assert_eq!(vec!["a", "b"], *log.borrow());
+    }
+
+    #[test]
+    fn with_ref_should_build_the_guard_from_the_value() {
+        let (log, push) = log();
+        {
+            let _scope = Scope::new();
+            let value = "value";
+            register(with_ref(&value, |v| push(v)));
+            assert!(log.borrow().is_empty());
+        }
+
+        assert_eq!(vec!["value"], *log.borrow());
+    }
+
+    #[test]
+    fn compose_closure_wrappers() {
+        let (log, push) = log();
+        {
+            let _scope = Scope::new();
+            register((TearDownClosure(push("a")), TearDownClosure::from(push("b"))));
+        }
+
+        assert_eq!(vec!["a", "b"], *log.borrow());
+    }
+}
@@ -221,6 +221,160 @@ mod should {
         assert_eq!(1, occurences);
     }
 
+    #[test]
+    fn accept_once_attribute_on_async_fixture_and_call_it_just_once() {
+        let project = prj("once_async.rs").with_nocapture();
+        project.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+        project.add_dependency("tokio", r#"{version="*", features=["rt", "macros"]}"#);
+
+        let output = project.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("base")
+            .ok("cases::case_1")
+            .ok("cases::case_2")
+            .ok("cases::case_3")
+            .ok("other_runtime")
+            .assert(output.clone());
+
+        assert_eq!(1, output.stderr.str().count("Exec fixture() just once"));
+    }
+
+    #[test]
+    fn accept_once_attribute_on_generic_fixture_and_call_it_once_for_each_type() {
+        let project = prj("once_generic.rs").with_nocapture();
+
+        let output = project.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("dec::case_1")
+            .ok("dec::case_2")
+            .ok("hex::case_1")
+            .ok("hex::case_2")
+            .ok("count::case_1")
+            .ok("count::case_2")
+            .assert(output.clone());
+
+        let stderr = output.stderr.str();
+        for name in ["Dec", "Hex", "digits"] {
+            assert_eq!(
+                1,
+                stderr.count(format!("Exec fixture() for {name}")),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn expand_the_tests_for_every_variant_of_parametrized_fixtures() {
+        let (output, _) = run_test("params.rs");
+
+        TestResults::new()
+            .ok("all_backends::backend_1_Kind__Memory")
+            .ok("all_backends::backend_2_Kind__Sqlite")
+            .ok("first_backend_by_default")
+            .fail("backends_and_limits::backend_1_Kind__Memory::limit_1_small")
+            .ok("backends_and_limits::backend_1_Kind__Memory::limit_2_big")
+            .fail("backends_and_limits::backend_2_Kind__Sqlite::limit_1_small")
+            .ok("backends_and_limits::backend_2_Kind__Sqlite::limit_2_big")
+            .ok("with_cases::case_1::backend_1_Kind__Memory")
+            .fail("with_cases::case_1::backend_2_Kind__Sqlite")
+            .fail("with_cases::case_2::backend_1_Kind__Memory")
+            .ok("with_cases::case_2::backend_2_Kind__Sqlite")
+            .ok("from_other_module::name_1___root__")
+            .ok("from_other_module::name_2___guest__")
+            .assert(output);
+    }
+
+    #[test]
+    fn tear_down_fixtures_in_reverse_order_also_when_test_fails() {
+        let project = prj("teardown.rs").with_nocapture();
+
+        let output = project.run_tests().unwrap();
+        let stderr = output.stderr.str().to_string();
+
+        TestResults::new()
+            .ok("passed")
+            .fail("failed")
+            .ok("cases::case_1")
+            .ok("cases::case_2")
+            .assert(output);
+
+        for label in ["passed", "failed", "first", "second"] {
+            let server = format!("TEARDOWN server {label}");
+            let db = format!("TEARDOWN db {label}");
+            assert_eq!(1, stderr.count(&server), "{server}");
+            assert_eq!(1, stderr.count(&db), "{db}");
+            assert!(stderr.find(&server) < stderr.find(&db));
+        }
+    }
+
+    #[test]
+    fn share_scoped_fixtures_and_release_group_ones_after_the_last_test() {
+        let mut project = prj("scope.rs").with_nocapture();
+        project.add_test_arg("--test-threads=1");
+
+        let output = project.run_tests().unwrap();
+        let stderr = output.stderr.str().to_string();
+
+        TestResults::new()
+            .ok("cases::case_1")
+            .ok("cases::case_2")
+            .ok("cases::case_3")
+            .fail("cases::case_4_fail")
+            .ok("matrix::_a_1_1::_b_1_3")
+            .ok("matrix::_a_1_1::_b_2_4")
+            .ok("matrix::_a_2_2::_b_1_3")
+            .ok("matrix::_a_2_2::_b_2_4")
+            .ok("single")
+            .ok("other::other_module")
+            .assert(output);
+
+        for group in ["cases", "matrix", "single"] {
+            assert_eq!(1, stderr.count(format!("BUILD context {group}")), "{group}");
+            assert_eq!(
+                1,
+                stderr.count(format!("RELEASE context {group}")),
+                "{group}"
+            );
+        }
+        assert_eq!(2, stderr.count("BUILD shared"));
+        assert!(stderr.find("RELEASE context cases") < stderr.find("BUILD context matrix"));
+        assert!(stderr.find("RELEASE context matrix") < stderr.find("BUILD context single"));
+    }
+
+    #[test]
+    fn release_group_fixtures_at_exit_when_some_tests_are_filtered_out() {
+        let mut project = prj("scope.rs").with_nocapture();
+        for arg in ["--skip", "case_4", "--skip", "_b_2_4", "--test-threads=1"] {
+            project.add_test_arg(arg);
+        }
+
+        let output = project.run_tests().unwrap();
+        let stderr = output.stderr.str().to_string();
+
+        TestResults::new()
+            .ok("cases::case_1")
+            .ok("cases::case_2")
+            .ok("cases::case_3")
+            .ok("matrix::_a_1_1::_b_1_3")
+            .ok("matrix::_a_2_2::_b_1_3")
+            .ok("single")
+            .ok("other::other_module")
+            .assert(output);
+
+        for group in ["cases", "matrix"] {
+            assert_eq!(1, stderr.count(format!("BUILD context {group}")), "{group}");
+            assert_eq!(
+                1,
+                stderr.count(format!("RELEASE context {group}")),
+                "{group}"
+            );
+        }
+        assert!(stderr.find("RELEASE context cases") > stderr.find("BUILD context single"));
+        assert!(stderr.find("RELEASE context single") < stderr.find("RELEASE context cases"));
+    }
+
     mod show_correct_errors {
         use super::*;
         use std::process::Output;
@@ -342,13 +496,14 @@ mod should {
         }
 
         #[rstest]
-        fn once_async(errors_once_rs: &(Output, String)) {
+        fn once_const_generics(errors_once_rs: &(Output, String)) {
             let (output, name) = errors_once_rs.clone();
+
             assert_in!(
                 output.stderr.str(),
                 format!(
                     r#"
-                    error: Cannot apply #[once] to async fixture.
+                    error: Cannot apply #[once] on fixture with lifetimes, const generics or impl arguments.
                      --> {}/src/lib.rs:4:1
                       |
                     4 | #[once]
@@ -360,18 +515,17 @@ mod should {
         }
 
         #[rstest]
-        fn once_generic_type(errors_once_rs: &(Output, String)) {
+        fn once_impl_argument(errors_once_rs: &(Output, String)) {
             let (output, name) = errors_once_rs.clone();
-
             assert_in!(
                 output.stderr.str(),
                 format!(
                     r#"
-                    error: Cannot apply #[once] on generic fixture.
-                     --> {}/src/lib.rs:9:1
-                      |
-                    9 | #[once]
-                    "#,
+                error: Cannot apply #[once] on fixture with lifetimes, const generics or impl arguments.
+                  --> {}/src/lib.rs:10:1
+                   |
+                10 | #[once]
+                "#,
                     name
                 )
                 .unindent()
@@ -379,34 +533,35 @@ mod should {
         }
 
         #[rstest]
-        fn once_generic_impl(errors_once_rs: &(Output, String)) {
+        fn once_on_not_sync_type(errors_once_rs: &(Output, String)) {
             let (output, name) = errors_once_rs.clone();
             assert_in!(
                 output.stderr.str(),
                 format!(
                     r#"
-                error: Cannot apply #[once] on generic fixture.
-                  --> {}/src/lib.rs:15:1
-                   |
-                15 | #[once]
-                "#,
-                    name
+                    error[E0277]: `Cell<u32>` cannot be shared between threads safely
+                      --> {}/src/lib.rs:15:1
+                       |
+                    15 | #[fixture]
+                       | ^^^^^^^^^^ `Cell<u32>` cannot be shared between threads safely
+                    "#,
+                    name,
                 )
-                .unindent()
+                .unindent(),
             );
         }
 
         #[rstest]
-        fn once_on_not_sync_type(errors_once_rs: &(Output, String)) {
+        fn once_async_on_not_sync_type(errors_once_rs: &(Output, String)) {
             let (output, name) = errors_once_rs.clone();
             assert_in!(
                 output.stderr.str(),
                 format!(
                     r#"
                     error[E0277]: `Cell<u32>` cannot be shared between threads safely
-                      --> {}/src/lib.rs:20:1
+                      --> {}/src/lib.rs:21:1
                        |
-                    20 | #[fixture]
+                    21 | #[fixture]
                        | ^^^^^^^^^^ `Cell<u32>` cannot be shared between threads safely
                     "#,
                     name,
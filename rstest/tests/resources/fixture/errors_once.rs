@@ -2,23 +2,24 @@ use rstest::*;
 
 #[fixture]
 #[once]
-async fn error_async_once_fixture() {
+fn error_const_generics_once_fixture<const N: usize>() -> [u32; N] {
+    [42; N]
 }
 
 #[fixture]
 #[once]
-fn error_generics_once_fixture<T: std::fmt::Debug>() -> T {
-    42
+fn error_impl_argument_once_fixture(v: impl Into<u32>) -> u32 {
+    v.into()
 }
 
 #[fixture]
 #[once]
-fn error_generics_once_fixture() -> impl Iterator<Item = u32> {
-    std::iter::once(42)
+fn error_once_fixture_not_sync() -> std::cell::Cell<u32> {
+    std::cell::Cell::new(42)
 }
 
 #[fixture]
 #[once]
-fn error_once_fixture_not_sync() -> std::cell::Cell<u32> {
+async fn error_async_once_fixture_not_sync() -> std::cell::Cell<u32> {
     std::cell::Cell::new(42)
 }
@@ -0,0 +1,29 @@
+use rstest::{fixture, rstest};
+
+#[fixture]
+#[once]
+async fn once_fixture() -> u32 {
+    eprintln!("Exec fixture() just once");
+    42
+}
+
+#[rstest]
+#[async_std::test]
+async fn base(#[future] once_fixture: &u32) {
+    assert_eq!(&42, once_fixture.await);
+}
+
+#[rstest]
+#[case(2)]
+#[case(3)]
+#[case(7)]
+#[async_std::test]
+async fn cases(#[future(awt)] once_fixture: &u32, #[case] divisor: u32) {
+    assert_eq!(0, *once_fixture % divisor);
+}
+
+#[rstest]
+#[tokio::test]
+async fn other_runtime(#[future(awt)] once_fixture: &u32) {
+    assert_eq!(&42, once_fixture);
+}
@@ -0,0 +1,59 @@
+use rstest::{fixture, rstest};
+
+trait Codec: Default + Sync + 'static {
+    fn encode(&self, v: u32) -> String;
+}
+
+#[derive(Default)]
+struct Dec;
+
+impl Codec for Dec {
+    fn encode(&self, v: u32) -> String {
+        format!("{v}")
+    }
+}
+
+#[derive(Default)]
+struct Hex;
+
+impl Codec for Hex {
+    fn encode(&self, v: u32) -> String {
+        format!("{v:x}")
+    }
+}
+
+#[fixture]
+#[once]
+fn codec<C: Codec>() -> C {
+    let name = std::any::type_name::<C>().rsplit("::").next().unwrap();
+    eprintln!("Exec fixture() for {name}");
+    C::default()
+}
+
+#[fixture]
+#[once]
+fn digits() -> impl Iterator<Item = u32> + Clone + Sync {
+    eprintln!("Exec fixture() for digits");
+    0..10
+}
+
+#[rstest]
+#[case(42, "42")]
+#[case(255, "255")]
+fn dec(codec: &Dec, #[case] v: u32, #[case] expected: &str) {
+    assert_eq!(expected, codec.encode(v));
+}
+
+#[rstest]
+#[case(42, "2a")]
+#[case(255, "ff")]
+fn hex(codec: &Hex, #[case] v: u32, #[case] expected: &str) {
+    assert_eq!(expected, codec.encode(v));
+}
+
+#[rstest]
+#[case(5)]
+#[case(10)]
+fn count(digits: &(impl Iterator<Item = u32> + Clone), #[case] len: usize) {
+    assert_eq!(len, digits.clone().take(len).count());
+}
@@ -0,0 +1,65 @@
+use rstest::{fixture, rstest};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Kind {
+    Memory,
+    Sqlite,
+}
+
+struct Storage {
+    kind: Kind,
+    size: u32,
+}
+
+#[fixture]
+fn size() -> u32 {
+    42
+}
+
+#[fixture]
+fn backend(#[values(Kind::Memory, Kind::Sqlite)] kind: Kind, size: u32) -> Storage {
+    Storage { kind, size }
+}
+
+#[fixture]
+#[case::small(1)]
+#[case::big(1000)]
+fn limit(#[case] value: u32) -> u32 {
+    value
+}
+
+mod fixtures {
+    use super::*;
+
+    #[fixture]
+    pub fn user(#[values("root", "guest")] name: &'static str) -> String {
+        name.to_owned()
+    }
+}
+
+#[rstest]
+fn all_backends(#[params] backend: Storage) {
+    assert_eq!(42, backend.size);
+}
+
+#[rstest]
+fn first_backend_by_default(backend: Storage) {
+    assert_eq!(Kind::Memory, backend.kind);
+}
+
+#[rstest]
+fn backends_and_limits(#[params] backend: Storage, #[params] limit: u32) {
+    assert!(backend.size < limit);
+}
+
+#[rstest]
+#[case(Kind::Memory)]
+#[case(Kind::Sqlite)]
+fn with_cases(#[params] backend: Storage, #[case] expected: Kind) {
+    assert_eq!(expected, backend.kind);
+}
+
+#[rstest]
+fn from_other_module(#[params] #[from(fixtures::user)] name: String) {
+    assert!(name.len() > 0);
+}
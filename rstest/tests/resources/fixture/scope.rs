@@ -0,0 +1,58 @@
+use rstest::{fixture, rstest};
+use std::sync::Arc;
+
+struct Context(&'static str);
+
+#[fixture]
+#[scope(group)]
+#[teardown(|c: &Context| {
+    let name = c.0;
+    move || eprintln!("RELEASE context {name}")
+})]
+fn context(#[default("none")] name: &'static str) -> Context {
+    eprintln!("BUILD context {name}");
+    Context(name)
+}
+
+#[fixture]
+#[scope(module)]
+fn shared() -> u32 {
+    eprintln!("BUILD shared");
+    42
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[case(3)]
+#[case::fail(4)]
+fn cases(#[with("cases")] context: Arc<Context>, shared: Arc<u32>, #[case] n: u32) {
+    assert_eq!("cases", context.0);
+    assert_eq!(42, *shared);
+    assert!(n < 4);
+}
+
+#[rstest]
+fn matrix(
+    #[with("matrix")] context: Arc<Context>,
+    shared: Arc<u32>,
+    #[values(1, 2)] _a: u32,
+    #[values(3, 4)] _b: u32,
+) {
+    assert_eq!("matrix", context.0);
+    assert_eq!(42, *shared);
+}
+
+#[rstest]
+fn single(#[with("single")] context: Arc<Context>) {
+    assert_eq!("single", context.0);
+}
+
+mod other {
+    use super::*;
+
+    #[rstest]
+    fn other_module(shared: Arc<u32>) {
+        assert_eq!(42, *shared);
+    }
+}
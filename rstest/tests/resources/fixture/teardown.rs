@@ -0,0 +1,38 @@
+use rstest::{fixture, rstest};
+
+struct Db(&'static str);
+
+struct Server(&'static str);
+
+#[fixture]
+#[teardown]
+fn db(#[default("none")] label: &'static str) -> (Db, impl FnOnce()) {
+    (Db(label), move || eprintln!("TEARDOWN db {label}"))
+}
+
+#[fixture]
+#[teardown(|s: &Server| {
+    let label = s.0;
+    move || eprintln!("TEARDOWN server {label}")
+})]
+fn server(#[default("none")] label: &'static str, #[with(label)] db: Db) -> Server {
+    assert_eq!(label, db.0);
+    Server(label)
+}
+
+#[rstest]
+fn passed(#[with("passed")] server: Server) {
+    assert_eq!("passed", server.0);
+}
+
+#[rstest]
+fn failed(#[with("failed")] server: Server) {
+    assert_eq!("something else", server.0);
+}
+
+#[rstest]
+#[case("first")]
+#[case("second")]
+fn cases(#[case] label: &'static str, #[with(label)] server: Server) {
+    assert_eq!(label, server.0);
+}
@@ -0,0 +1,12 @@
+use rstest::rstest;
+use std::path::PathBuf;
+
+#[rstest]
+fn shared(#[files("rerun_shared/*.txt")] path: PathBuf) {
+    assert!(path.exists())
+}
+
+#[rstest]
+fn local(#[files("local/*.txt", base = "crate")] path: PathBuf) {
+    assert!(path.exists())
+}
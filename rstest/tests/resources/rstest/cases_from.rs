@@ -0,0 +1,21 @@
+use rstest::*;
+use std::net::SocketAddr;
+
+#[rstest]
+#[cases_from("cases/sum.csv")]
+fn sum(#[case] a: u32, #[case] b: u32, #[case] expected: u32) {
+    assert_eq!(expected, a + b);
+}
+
+#[rstest]
+#[cases_from("cases/addresses.json")]
+fn address_port(#[case] addr: SocketAddr, #[case] port: u16) {
+    assert_eq!(port, addr.port());
+}
+
+#[rstest]
+#[case::inline("inline", 6)]
+#[cases_from("cases/len.toml")]
+fn len(#[case] input: &str, #[case] expected: usize) {
+    assert_eq!(expected, input.len());
+}
@@ -0,0 +1,41 @@
+use rstest::*;
+
+mod generated {
+    pub fn squares() -> impl IntoIterator<Item = (String, (u32, u32))> {
+        (1..=4).map(|n| (format!("square_of_{n}"), (n, n * n)))
+    }
+
+    pub fn wrong() -> Vec<(&'static str, (u32, u32))> {
+        vec![("good", (2, 4)), ("bad", (3, 10)), ("other_bad", (4, 0))]
+    }
+
+    pub fn nothing() -> Vec<(String, (u32,))> {
+        vec![]
+    }
+}
+
+#[fixture]
+fn offset() -> u32 {
+    0
+}
+
+#[rstest]
+#[cases_from_fn(generated::squares)]
+fn all_pass(#[case] n: u32, offset: u32, #[case] expected: u32) {
+    assert_eq!(expected, n * n + offset);
+}
+
+#[rstest]
+#[cases_from_fn(generated::wrong)]
+fn report_failures(#[case] n: u32, #[case] expected: u32) -> Result<(), String> {
+    assert!(expected > 0, "zero is not a square");
+    if n * n == expected {
+        Ok(())
+    } else {
+        Err(format!("{n}^2 != {expected}"))
+    }
+}
+
+#[rstest]
+#[cases_from_fn(generated::nothing)]
+fn no_cases(#[case] _n: u32) {}
@@ -118,3 +118,10 @@ fn error_timeout_without_duration() {}
 
 #[rstest]
 fn error_absolute_path_files(#[files("/tmp/tmp.Q81idVZYAV/*.txt")] path: std::path::PathBuf) {}
+
+#[rstest]
+#[zip]
+fn error_zip_lists_of_different_lengths(#[values(1, 2, 3)] a: u32, #[values(4, 5)] b: u32) {}
+
+#[rstest(name = "with_{a}")]
+fn error_duplicated_test_names(#[values(1, 2)] a: u32, #[values(3, 4)] b: u32) {}
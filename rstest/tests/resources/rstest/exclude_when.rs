@@ -0,0 +1,49 @@
+use rstest::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Running,
+    Terminated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Event {
+    Tick,
+    Fatal,
+}
+
+fn step(state: State, event: Event) -> State {
+    match (state, event) {
+        (State::Running, Event::Tick) => State::Running,
+        (_, Event::Fatal) => State::Terminated,
+        (State::Terminated, _) => panic!("Terminated"),
+    }
+}
+
+#[rstest]
+#[exclude_when(state == State::Terminated && event == Event::Tick)]
+fn transitions(
+    #[values(State::Running, State::Terminated)] state: State,
+    #[values(Event::Tick, Event::Fatal)] event: Event,
+) {
+    step(state, event);
+}
+
+#[rstest]
+#[exclude_when(n % 2 == 1)]
+fn even(#[values(1, 2, 3, 4)] n: u32) {
+    assert_eq!(0, n % 2);
+}
+
+#[rstest]
+#[xfail]
+#[exclude_when(n > 2)]
+fn odd(#[values(1, 3)] n: u32) {
+    assert_eq!(0, n % 2);
+}
+
+#[rstest]
+#[exclude_when(n == 3)]
+fn parsed(#[values("2", "3")] n: u32) {
+    assert_ne!(3, n);
+}
@@ -0,0 +1,34 @@
+use rstest::*;
+
+#[rstest]
+#[case::pass("42", 42)]
+#[xfail("known bug: overflow")]
+#[case::known_bug("300", 255)]
+#[xfail]
+#[case::unexpected_pass("7", 7)]
+fn parse(#[case] input: &str, #[case] expected: u8) {
+    assert_eq!(expected, input.parse::<u8>().unwrap_or(44))
+}
+
+#[rstest]
+#[case::valid("42", 42)]
+#[expect_err("invalid digit")]
+#[case::invalid_digit("4x", 0)]
+#[expect_err("empty string")]
+#[case::wrong_error("4x", 0)]
+#[expect_err("invalid digit")]
+#[case::no_error("42", 42)]
+fn parse_result(#[case] input: &str, #[case] expected: u8) -> Result<(), std::num::ParseIntError> {
+    assert_eq!(expected, input.parse::<u8>()?);
+    Ok(())
+}
+
+#[rstest]
+#[case::pass("42", 42)]
+#[xfail("known bug: overflow")]
+#[case::known_bug("300", 255)]
+#[xfail]
+#[case::unexpected_pass("7", 7)]
+async fn async_parse(#[case] input: &str, #[case] expected: u8) {
+    assert_eq!(expected, async { input.parse::<u8>().unwrap_or(44) }.await)
+}
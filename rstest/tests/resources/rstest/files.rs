@@ -33,6 +33,46 @@ fn start_with_name_with_include(
     assert!(contents.starts_with(name.to_str().unwrap()))
 }
 
+#[rstest]
+fn str_contents(
+    #[files("files/**/*.txt")]
+    #[exclude("exclude")]
+    #[mode = str]
+    contents: &str,
+) {
+    assert!(contents.contains("_"), "{contents}")
+}
+
+#[rstest]
+fn bytes_contents(
+    #[files("files/**/*.txt")]
+    #[exclude("exclude")]
+    #[mode = bytes]
+    contents: &[u8],
+) {
+    assert!(contents.windows(2).any(|w| w == b"--"))
+}
+
+#[rstest]
+fn dirs(#[dirs("files/*")] path: PathBuf) {
+    assert!(path.is_dir())
+}
+
+#[rstest]
+fn just_files(#[files("files/*", kind = "file")] #[exclude("exclude")] path: PathBuf) {
+    assert!(path.is_file())
+}
+
+#[rstest]
+fn limited(#[files("files/*.txt", limit = 2)] path: PathBuf) {
+    assert!(path.is_file())
+}
+
+#[rstest]
+fn workspace_base(#[files("files_test_sub_folder/*.txt", base = "workspace")] path: PathBuf) {
+    assert!(path.is_file())
+}
+
 mod module {
     #[rstest::rstest]
     fn pathbuf_need_not_be_in_scope(
@@ -0,0 +1,19 @@
+use rstest::*;
+use std::path::PathBuf;
+
+#[rstest]
+fn upper(#[files_pair("inputs/*.txt", "expected/{stem}.out")] files: (PathBuf, PathBuf)) {
+    let (input, expected) = files;
+    let actual = std::fs::read_to_string(input).unwrap().to_uppercase();
+
+    rstest::golden::check(expected, actual);
+}
+
+#[rstest]
+fn upper_contents(
+    #[files_pair("inputs/*.txt", "expected/{stem}.out")]
+    #[mode = str]
+    files: (&str, &str),
+) {
+    assert_eq!(files.1, files.0.to_uppercase());
+}
@@ -0,0 +1,10 @@
+use rstest::*;
+use std::path::PathBuf;
+
+#[rstest]
+fn upper(#[files_pair("inputs/*.txt", "blessed/{stem}.out")] files: (PathBuf, PathBuf)) {
+    let (input, expected) = files;
+    let actual = std::fs::read_to_string(input).unwrap().to_uppercase();
+
+    rstest::golden::check(expected, actual);
+}
@@ -0,0 +1,35 @@
+use rstest::*;
+use std::path::PathBuf;
+
+#[rstest]
+fn all_files(#[files("corpus/*.txt", expand = "runtime")] path: PathBuf) {
+    assert!(path.is_file())
+}
+
+#[rstest]
+fn report_failures(
+    #[files("corpus/*.txt", expand = "runtime")]
+    #[exclude("skip")]
+    #[mode = str]
+    contents: &str,
+) {
+    assert!(contents.starts_with("ok"), "wrong contents")
+}
+
+#[rstest]
+fn with_result(#[files("corpus/*.txt", expand = "runtime")] path: PathBuf) -> Result<(), String> {
+    match path.ends_with("skip.txt") {
+        true => Err("skipped".to_owned()),
+        false => Ok(()),
+    }
+}
+
+#[rstest]
+fn with_values(
+    #[values(1, 2)] n: usize,
+    #[files("corpus/*.txt", expand = "runtime")]
+    #[mode = bytes]
+    contents: &[u8],
+) {
+    assert!(contents.len() > n)
+}
@@ -0,0 +1,21 @@
+use rstest::*;
+
+#[rstest]
+#[case::zero(0)]
+#[tag("slow")]
+#[case::overflow(u8::MAX)]
+fn add_one(#[case] n: u8, #[values(1, big => 7)] x: u32) {
+    let _ = (n, x);
+}
+
+#[rstest]
+fn single() {}
+
+#[test]
+fn dump_the_manifest() {
+    std::fs::write(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/manifest.json"),
+        rstest::manifest::to_json(),
+    )
+    .unwrap();
+}
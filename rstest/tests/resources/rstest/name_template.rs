@@ -0,0 +1,28 @@
+use rstest::*;
+
+#[derive(Debug, Clone, Copy)]
+enum Unit {
+    Celsius,
+    Fahrenheit,
+}
+use Unit::*;
+
+#[rstest(name = "{from}_to_{to}")]
+fn convert(
+    #[values(Celsius, Fahrenheit)] from: Unit,
+    #[values(Celsius, Fahrenheit)] to: Unit,
+) {
+    let _ = (from, to);
+}
+
+#[rstest(name = "{case}_user_{name}")]
+#[case::admin("root", 0)]
+#[case("alice", 1000)]
+fn users(#[case] name: &str, #[case] uid: u32) {
+    assert!(uid > 0, "{} has uid {}", name, uid)
+}
+
+#[rstest(name = "{flag}")]
+fn keyword(#[values(true, false)] flag: bool) {
+    let _ = flag;
+}
@@ -0,0 +1,10 @@
+use rstest::*;
+
+fn big_vec() -> Vec<u32> {
+    (0..1000).collect()
+}
+
+#[rstest]
+fn sum(#[values(empty => vec![], small => vec![1, 2], huge => big_vec())] values: Vec<u32>) {
+    assert!(values.iter().sum::<u32>() < 1000)
+}
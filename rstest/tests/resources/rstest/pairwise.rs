@@ -0,0 +1,13 @@
+use rstest::*;
+
+#[rstest]
+#[pairwise]
+fn pairwise(
+    #[values(1, 2, 3)] a: u32,
+    #[values(1, 2, 3)] b: u32,
+    #[values(1, 2, 3)] c: u32,
+    #[values(1, 2, 3)] d: u32,
+) {
+    assert!(a + c > 0);
+    assert!(!(b == 3 && d == 2), "bug on b = 3 and d = 2");
+}
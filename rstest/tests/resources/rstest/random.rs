@@ -0,0 +1,29 @@
+use rstest::{
+    random::{any, vec},
+    *,
+};
+
+#[fixture]
+fn offset() -> u32 {
+    100
+}
+
+#[rstest]
+fn with_fixture(offset: u32, #[random(0..100u32, cases = 64)] n: u32) {
+    assert!(n + offset >= 100)
+}
+
+#[rstest]
+fn fail_and_shrink(
+    #[random(0..1000u32, seed = 7)] n: u32,
+    #[random(vec(any::<u8>(), 0..10))] values: Vec<u8>,
+) {
+    assert!(n < 10 || values.is_empty(), "too big")
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn with_cases(#[case] factor: u32, #[random(any::<u16>())] n: u16) {
+    assert_eq!(0, (n as u32 * factor * 2) % 2)
+}
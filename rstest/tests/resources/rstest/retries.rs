@@ -0,0 +1,71 @@
+use rstest::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PASS: AtomicUsize = AtomicUsize::new(0);
+static FAIL: AtomicUsize = AtomicUsize::new(0);
+static RESULT: AtomicUsize = AtomicUsize::new(0);
+static CASE_PASS: AtomicUsize = AtomicUsize::new(0);
+static CASE_FAIL: AtomicUsize = AtomicUsize::new(0);
+
+#[fixture]
+fn pass_attempt() -> usize {
+    PASS.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+#[fixture]
+fn fail_attempt() -> usize {
+    FAIL.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+#[rstest]
+#[retries(2)]
+fn pass_at_third_attempt(pass_attempt: usize) {
+    assert!(pass_attempt >= 3, "flaky")
+}
+
+#[rstest]
+#[retries(1)]
+fn fail_if_not_enough_retries(fail_attempt: usize) {
+    assert!(fail_attempt >= 3, "flaky")
+}
+
+#[rstest]
+#[retries(1)]
+fn retry_on_error() -> Result<(), String> {
+    match RESULT.fetch_add(1, Ordering::SeqCst) {
+        0 => Err("not yet".to_owned()),
+        _ => Ok(()),
+    }
+}
+
+#[rstest]
+#[retries(1)]
+#[case::pass(&CASE_PASS)]
+#[case::fail(&CASE_FAIL)]
+fn case_retries(#[case] counter: &AtomicUsize) {
+    assert!(counter.fetch_add(1, Ordering::SeqCst) > 0, "first attempt")
+}
+
+static ASYNC_PASS: AtomicUsize = AtomicUsize::new(0);
+static ASYNC_FAIL: AtomicUsize = AtomicUsize::new(0);
+
+#[fixture]
+async fn async_attempt(#[default(&ASYNC_PASS)] counter: &AtomicUsize) -> usize {
+    counter.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+#[rstest]
+#[retries(2)]
+async fn async_pass_at_third_attempt(#[future(awt)] async_attempt: usize) {
+    assert!(async_attempt >= 3, "async flaky")
+}
+
+#[rstest]
+#[retries(1)]
+async fn async_fail_if_not_enough_retries(
+    #[future(awt)]
+    #[with(&ASYNC_FAIL)]
+    async_attempt: usize,
+) {
+    assert!(async_attempt >= 3, "async flaky")
+}
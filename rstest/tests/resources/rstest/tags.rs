@@ -0,0 +1,17 @@
+use rstest::*;
+
+#[rstest]
+#[tag("db")]
+fn query() {}
+
+#[rstest]
+#[case::small(1)]
+#[tag("slow")]
+#[case::big(1000)]
+#[tag("fast")]
+fn sum(#[case] n: u64, #[values(1, #[tag("db")] 2)] factor: u64) {
+    assert!(n * factor > 0);
+}
+
+#[rstest]
+fn untagged() {}
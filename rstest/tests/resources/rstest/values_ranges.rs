@@ -0,0 +1,29 @@
+use rstest::*;
+
+const PRIMES: [u32; 4] = [2, 3, 5, 7];
+const WORDS: &[&str] = &["one", "two", "three"];
+
+#[rstest]
+fn range(#[values(range = 0..3)] n: u32) {
+    assert!(n < 3)
+}
+
+#[rstest]
+fn range_with_step(#[values(range = 0..=255; step = 85)] byte: u8) {
+    assert_eq!(0, byte % 85)
+}
+
+#[rstest]
+fn plain_range(#[values(0..3)] r: std::ops::Range<u32>) {
+    assert_eq!(3, r.len())
+}
+
+#[rstest]
+fn from_const(#[values(from = PRIMES; len = 4)] p: u32, #[values(1, 2)] factor: u32) {
+    assert!(p * factor != 6, "{} * {} is 6", p, factor)
+}
+
+#[rstest]
+fn from_const_slice(#[values(from = WORDS; len = 3)] word: &str) {
+    assert!(word.len() <= 5)
+}
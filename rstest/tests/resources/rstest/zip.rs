@@ -0,0 +1,7 @@
+use rstest::*;
+
+#[rstest]
+#[zip]
+fn double(#[values(1, 2, 3)] input: u32, #[values(2, 4, 7)] expected: u32) {
+    assert_eq!(expected, 2 * input)
+}
@@ -74,6 +74,24 @@ fn files() {
         .ok("start_with_name_with_include::path_4_files_element_2_txt")
         .ok("start_with_name_with_include::path_5_files_element_3_txt")
         .ok("start_with_name_with_include::path_6_files_sub_sub_dir_file_txt")
+        .ok("str_contents::contents_1_files_element_0_txt")
+        .ok("str_contents::contents_2_files_element_1_txt")
+        .ok("str_contents::contents_3_files_element_2_txt")
+        .ok("str_contents::contents_4_files_element_3_txt")
+        .ok("str_contents::contents_5_files_sub_sub_dir_file_txt")
+        .ok("bytes_contents::contents_1_files_element_0_txt")
+        .ok("bytes_contents::contents_2_files_element_1_txt")
+        .ok("bytes_contents::contents_3_files_element_2_txt")
+        .ok("bytes_contents::contents_4_files_element_3_txt")
+        .ok("bytes_contents::contents_5_files_sub_sub_dir_file_txt")
+        .ok("dirs::path_1_files_sub")
+        .ok("just_files::path_1_files_element_0_txt")
+        .ok("just_files::path_2_files_element_1_txt")
+        .ok("just_files::path_3_files_element_2_txt")
+        .ok("just_files::path_4_files_element_3_txt")
+        .ok("limited::path_1_files_element_0_txt")
+        .ok("limited::path_2_files_element_1_txt")
+        .ok("workspace_base::path_1_files_test_sub_folder_from_parent_folder_txt")
         .ok("module::pathbuf_need_not_be_in_scope::path_1_files__ignore_me_txt")
         .ok("module::pathbuf_need_not_be_in_scope::path_2_files_element_0_txt")
         .ok("module::pathbuf_need_not_be_in_scope::path_3_files_element_1_txt")
@@ -83,6 +101,615 @@ fn files() {
         .assert(output);
 }
 
+#[test]
+fn rerun_build_script_for_the_globs_base_folders() {
+    let prj = prj("build_rerun.rs");
+    let workspace = prj.path().parent().unwrap().to_owned();
+    let shared = workspace.join("rerun_shared");
+    let local = prj.path().join("local");
+    for dir in [&shared, &local] {
+        std::fs::create_dir_all(dir).unwrap();
+        File::create(dir.join("a.txt")).unwrap();
+    }
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(prj.path().join("Cargo.toml"))
+        .unwrap()
+        .write_all(
+            format!(
+                r#"
+[build-dependencies]
+rstest = {{ path = "{}", features = ["build"] }}
+
+[package.metadata.rstest]
+base = "workspace"
+"#,
+                prj.exec_dir_str()
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+    File::create(prj.path().join("build.rs"))
+        .unwrap()
+        .write_all(
+            br#"
+fn main() {
+    rstest::build::rerun_if_files_changed(&["rerun_shared/*.txt"]);
+    rstest::build::rerun_if_files_changed_in(rstest::build::Base::Crate, &["local/*.txt"]);
+}
+"#,
+        )
+        .unwrap();
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("shared::path_1_rerun_shared_a_txt")
+        .ok("local::path_1_local_a_txt")
+        .assert(output);
+
+    let build_outputs = std::fs::read_dir(workspace.join("target").join("debug").join("build"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|dir| {
+            dir.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with(&format!("{}-", prj.get_name()))
+        })
+        .filter_map(|dir| std::fs::read_to_string(dir.join("output")).ok())
+        .collect::<String>();
+    assert_in!(
+        build_outputs,
+        format!("cargo:rerun-if-changed={}", shared.display())
+    );
+    assert_in!(
+        build_outputs,
+        format!("cargo:rerun-if-changed={}", local.display())
+    );
+}
+
+#[test]
+fn files_runtime() {
+    let prj = prj("files_runtime.rs");
+    prj.set_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", features=["files-runtime"]}}"#,
+            prj.exec_dir_str()
+        ),
+    );
+    let corpus = prj.path().join("corpus");
+    std::fs::create_dir(&corpus).unwrap();
+    for (name, contents) in [
+        ("a", "ok a"),
+        ("b", "bad b"),
+        ("c", "bad c"),
+        ("skip", "skip"),
+    ] {
+        File::create(corpus.join(format!("{name}.txt")))
+            .unwrap()
+            .write_all(contents.as_bytes())
+            .unwrap();
+    }
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("all_files")
+        .fail("report_failures")
+        .fail("with_result")
+        .ok("with_values::n_1_1")
+        .ok("with_values::n_2_2")
+        .assert(output.clone());
+
+    let stdout = output.stdout.str();
+    assert_in!(stdout, "2 of 3 files failed:");
+    assert_in!(stdout, "b.txt: wrong contents");
+    assert_in!(stdout, "c.txt: wrong contents");
+    assert_in!(stdout, r#"1 of 4 files failed:"#);
+    assert_in!(stdout, r#"skip.txt: Error: "skipped""#);
+}
+
+#[test]
+fn retries() {
+    let prj = prj("retries.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("pass_at_third_attempt")
+        .fail("fail_if_not_enough_retries")
+        .ok("retry_on_error")
+        .ok("case_retries::case_1_pass")
+        .fail("case_retries::case_2_fail")
+        .ok("async_pass_at_third_attempt")
+        .fail("async_fail_if_not_enough_retries")
+        .assert(output.clone());
+
+    // The harness shows just the output of the failed tests
+    assert_in!(output.stdout.str(), "Attempt 1 of 2 failed: flaky");
+    assert_in!(output.stdout.str(), "Attempt 1 of 2 failed: async flaky");
+}
+
+#[test]
+fn expect() {
+    let prj = prj("expect.rs");
+    prj.set_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", features=["expect-err"]}}"#,
+            prj.exec_dir_str()
+        ),
+    );
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("parse::case_1_pass")
+        .ok("parse::case_2_known_bug")
+        .fail("parse::case_3_unexpected_pass")
+        .ok("async_parse::case_1_pass")
+        .ok("async_parse::case_2_known_bug")
+        .fail("async_parse::case_3_unexpected_pass")
+        .ok("parse_result::case_1_valid")
+        .ok("parse_result::case_2_invalid_digit")
+        .fail("parse_result::case_3_wrong_error")
+        .fail("parse_result::case_4_no_error")
+        .assert(output.clone());
+
+    let stdout = output.stdout.str();
+    assert_in!(stdout, "Test expected to fail but it passed");
+    assert_in!(
+        stdout,
+        "Error `invalid digit found in string` doesn't match `empty string`"
+    );
+    assert_in!(
+        stdout,
+        "Expected an error that matches `invalid digit` but the test passed"
+    );
+}
+
+#[test]
+fn cases_from_fn() {
+    let prj = prj("cases_from_fn.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("all_pass")
+        .fail("report_failures")
+        .fail("no_cases")
+        .assert(output.clone());
+
+    let stdout = output.stdout.str();
+    assert_in!(stdout, "2 of 3 cases failed:");
+    assert_in!(stdout, r#"bad: Error: "3^2 != 10""#);
+    assert_in!(stdout, "other_bad: zero is not a square");
+    assert_in!(stdout, "No case found");
+}
+
+#[test]
+fn pairwise() {
+    let prj = prj("pairwise.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    let stdout = output.stdout.str();
+    let tests = stdout
+        .lines()
+        .filter(|line| line.starts_with("test pairwise::"))
+        .collect::<Vec<_>>();
+    assert!(tests.len() >= 9 && tests.len() < 81, "{stdout}");
+    assert!(
+        tests.iter().any(|test| test.contains("b_3_3")
+            && test.contains("d_2_2")
+            && test.ends_with("FAILED")),
+        "{stdout}"
+    );
+    assert!(
+        tests
+            .iter()
+            .all(|test| test.contains("b_3_3") && test.contains("d_2_2") || test.ends_with("ok")),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn zip() {
+    let prj = prj("zip.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("double::input_1_1::expected_1_2")
+        .ok("double::input_2_2::expected_2_4")
+        .fail("double::input_3_3::expected_3_7")
+        .assert(output);
+}
+
+#[test]
+fn values_ranges() {
+    let prj = prj("values_ranges.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("range::n_1_0")
+        .ok("range::n_2_1")
+        .ok("range::n_3_2")
+        .ok("range_with_step::byte_1_0")
+        .ok("range_with_step::byte_2_85")
+        .ok("range_with_step::byte_3_170")
+        .ok("range_with_step::byte_4_255")
+        .ok("plain_range::r_1_0__3")
+        .ok("from_const::p_1_PRIMES_0::factor_1_1")
+        .ok("from_const::p_1_PRIMES_0::factor_2_2")
+        .ok("from_const::p_2_PRIMES_1::factor_1_1")
+        .fail("from_const::p_2_PRIMES_1::factor_2_2")
+        .ok("from_const::p_3_PRIMES_2::factor_1_1")
+        .ok("from_const::p_3_PRIMES_2::factor_2_2")
+        .ok("from_const::p_4_PRIMES_3::factor_1_1")
+        .ok("from_const::p_4_PRIMES_3::factor_2_2")
+        .ok("from_const_slice::word_1_WORDS_0")
+        .ok("from_const_slice::word_2_WORDS_1")
+        .ok("from_const_slice::word_3_WORDS_2")
+        .assert(output.clone());
+
+    assert_in!(output.stdout.str(), "3 * 2 is 6");
+}
+
+#[test]
+fn values_from_const_with_wrong_len() {
+    let prj = prj("values_ranges.rs");
+    prj.append_code(
+        r#"
+        #[rstest]
+        fn wrong_len(#[values(from = PRIMES; len = 5)] p: u32) {}
+        "#,
+    );
+
+    let output = prj.compile().unwrap();
+
+    assert_in!(output.stderr.str(), "`PRIMES` should have 5 elements");
+}
+
+#[test]
+fn random() {
+    let prj = prj("random.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("with_fixture")
+        .fail("fail_and_shrink")
+        .ok("with_cases::case_1")
+        .ok("with_cases::case_2")
+        .assert(output.clone());
+
+    let stdout = output.stdout.str();
+    assert_in!(stdout, "(seed: 7, replay it by RSTEST_RANDOM_SEED=7)");
+    assert_in!(
+        stdout,
+        "Minimal failing input: n = 10, values = [0]\ntoo big"
+    );
+}
+
+#[test]
+fn random_with_invalid_seed() {
+    let mut prj = prj("random.rs");
+    prj.set_env("RSTEST_RANDOM_SEED", "seven");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .fail("with_fixture")
+        .fail("fail_and_shrink")
+        .fail("with_cases::case_1")
+        .fail("with_cases::case_2")
+        .assert(output.clone());
+    assert_in!(
+        output.stdout.str(),
+        "Invalid RSTEST_RANDOM_SEED=seven: it should be an unsigned integer"
+    );
+}
+
+#[test]
+fn name_template() {
+    let prj = prj("name_template.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("convert::Celsius_to_Celsius")
+        .ok("convert::Celsius_to_Fahrenheit")
+        .ok("convert::Fahrenheit_to_Celsius")
+        .ok("convert::Fahrenheit_to_Fahrenheit")
+        .fail("users::admin_user_root")
+        .ok("users::_2_user_alice")
+        .ok("keyword::flag_true")
+        .ok("keyword::flag_false")
+        .assert(output);
+}
+
+#[test]
+fn named_values() {
+    let prj = prj("named_values.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("sum::values_1_empty")
+        .ok("sum::values_2_small")
+        .fail("sum::values_3_huge")
+        .assert(output);
+}
+
+#[test]
+fn tags() {
+    let mut prj = prj("tags.rs");
+    let results = TestResults::new()
+        .ok("query_tag_db")
+        .ok("sum::case_1_small::factor_1_1_tag_fast")
+        .ok("sum::case_1_small::factor_2_2_tag_db_tag_fast")
+        .ok("sum::case_2_big::factor_1_1_tag_fast_tag_slow")
+        .ok("sum::case_2_big::factor_2_2_tag_db_tag_fast_tag_slow")
+        .ok("untagged");
+
+    let output = prj.run_tests().unwrap();
+
+    results.assert(output.clone());
+    assert_not_in!(output.stderr.str(), "filtered out by RSTEST_TAGS");
+
+    prj.set_env("RSTEST_TAGS", "slow, !db");
+    let output = prj.run_tests().unwrap();
+
+    // The test harness counts the skipped tests as passed
+    results.assert(output.clone());
+    let stderr = output.stderr.str();
+    assert_in!(
+        stderr,
+        "test query_tag_db ... skipped, filtered out by RSTEST_TAGS (tags: db)"
+    );
+    assert_in!(
+        stderr,
+        "test sum::case_1_small::factor_1_1_tag_fast ... skipped, filtered out by RSTEST_TAGS (tags: fast)"
+    );
+    assert_in!(
+        stderr,
+        "test sum::case_2_big::factor_2_2_tag_db_tag_fast_tag_slow ... skipped"
+    );
+    assert_not_in!(
+        stderr,
+        "test sum::case_2_big::factor_1_1_tag_fast_tag_slow ... skipped"
+    );
+    assert_not_in!(stderr, "test untagged ... skipped");
+}
+
+#[test]
+fn manifest() {
+    let prj = prj("manifest.rs");
+    prj.set_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", features=["manifest"]}}"#,
+            prj.exec_dir_str()
+        ),
+    );
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("add_one::case_1_zero::x_1_1")
+        .ok("add_one::case_1_zero::x_2_big")
+        .ok("add_one::case_2_overflow::x_1_1_tag_slow")
+        .ok("add_one::case_2_overflow::x_2_big_tag_slow")
+        .ok("single")
+        .ok("dump_the_manifest")
+        .assert(output);
+
+    let manifest = std::fs::read_to_string(prj.path().join("manifest.json")).unwrap();
+    let file = format!("{}/src/lib.rs", prj.get_name());
+    assert_in!(
+        manifest,
+        format!(
+            concat!(
+                r#"{{"name":"add_one::case_2_overflow::x_2_big_tag_slow","function":"add_one","file":"{}","line":7,"#,
+                r#""case":{{"index":2,"description":"overflow","args":{{"n":"u8 :: MAX"}},"line":6}},"#,
+                r#""values":[{{"arg":"x","index":2,"value":"7","description":"big","file":null,"line":7}}],"#,
+                r#""tags":["slow"]}}"#
+            ),
+            file
+        )
+    );
+    assert_in!(
+        manifest,
+        format!(
+            r#"{{"name":"single","function":"single","file":"{}","line":12,"case":null,"values":[],"tags":[]}}"#,
+            file
+        )
+    );
+    assert_not_in!(manifest, r#""name":"dump_the_manifest""#);
+}
+
+#[test]
+fn exclude_when() {
+    let mut prj = prj("exclude_when.rs");
+    let passed = [
+        "transitions::state_1_State__Running::event_1_Event__Tick",
+        "transitions::state_1_State__Running::event_2_Event__Fatal",
+        "transitions::state_2_State__Terminated::event_2_Event__Fatal",
+        "even::n_1_1",
+        "even::n_2_2",
+        "even::n_3_3",
+        "even::n_4_4",
+        "odd::n_1_1",
+        "odd::n_2_3",
+        "parsed::n_1___2__",
+        "parsed::n_2___3__",
+    ];
+
+    let output = prj.run_tests().unwrap();
+
+    let stdout = output.stdout.str();
+    assert_in!(stdout, "test result: ok. 11 passed; 0 failed; 1 ignored");
+    // The combinations excluded at runtime are skipped: the test harness counts them as
+    // passed
+    for test in passed {
+        assert_in!(stdout, format!("test {test} ... ok"));
+    }
+    assert_in!(
+        stdout,
+        "test transitions::state_2_State__Terminated::event_1_Event__Tick ... ignored, excluded by #[exclude_when(state == State :: Terminated && event == Event :: Tick)]"
+    );
+    let stderr = output.stderr.str();
+    assert_in!(
+        stderr,
+        "test even::n_1_1 ... skipped, excluded by `n % 2 == 1`"
+    );
+    assert_in!(
+        stderr,
+        "test even::n_3_3 ... skipped, excluded by `n % 2 == 1`"
+    );
+    assert_not_in!(stderr, "test even::n_2_2 ... skipped");
+    assert_in!(stderr, "test odd::n_2_3 ... skipped, excluded by `n > 2`");
+    assert_not_in!(stderr, "test odd::n_1_1 ... skipped");
+    // The string is converted to the argument's type just at runtime
+    assert_in!(
+        stderr,
+        "test parsed::n_2___3__ ... skipped, excluded by `n == 3`"
+    );
+
+    prj.add_test_arg("--include-ignored");
+    let output = prj.run_tests().unwrap();
+
+    passed
+        .iter()
+        .fold(TestResults::new(), |results, &test| results.ok(test))
+        .ok("transitions::state_2_State__Terminated::event_1_Event__Tick")
+        .assert(output.clone());
+    assert_in!(
+        output.stderr.str(),
+        "test transitions::state_2_State__Terminated::event_1_Event__Tick ... skipped, excluded by `state == State :: Terminated && event == Event :: Tick`"
+    );
+}
+
+#[test]
+fn files_pair() {
+    let prj = prj("files_pair.rs");
+    let inputs = prj.path().join("inputs");
+    let expected = prj.path().join("expected");
+    std::fs::create_dir(&inputs).unwrap();
+    std::fs::create_dir(&expected).unwrap();
+
+    for (name, input, output) in [("a", "abc", "ABC"), ("b", "def", "XXX")] {
+        File::create(inputs.join(format!("{name}.txt")))
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        File::create(expected.join(format!("{name}.out")))
+            .unwrap()
+            .write_all(output.as_bytes())
+            .unwrap();
+    }
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("upper::files_1_inputs_a_txt")
+        .fail("upper::files_2_inputs_b_txt")
+        .ok("upper_contents::files_1_inputs_a_txt")
+        .fail("upper_contents::files_2_inputs_b_txt")
+        .assert(output.clone());
+
+    assert_in!(output.stdout.str(), "Golden file");
+}
+
+#[test]
+fn files_pair_bless_missed_expected_files() {
+    let mut prj = prj("files_pair_bless.rs");
+    let inputs = prj.path().join("inputs");
+    std::fs::create_dir(&inputs).unwrap();
+    for (name, input) in [("a", "abc"), ("b", "def")] {
+        File::create(inputs.join(format!("{name}.txt")))
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+    }
+
+    let output = prj.compile().unwrap();
+
+    assert_in!(
+        output.stderr.str(),
+        "Missed expected file `blessed/a.out` for `inputs/a.txt`: run the tests with RSTEST_BLESS=1 to create it"
+    );
+
+    prj.set_env("RSTEST_BLESS", "1");
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("upper::files_1_inputs_a_txt")
+        .ok("upper::files_2_inputs_b_txt")
+        .assert(output);
+    let blessed = prj.path().join("blessed");
+    assert_eq!(
+        "ABC",
+        std::fs::read_to_string(blessed.join("a.out")).unwrap()
+    );
+    assert_eq!(
+        "DEF",
+        std::fs::read_to_string(blessed.join("b.out")).unwrap()
+    );
+
+    prj.set_env("RSTEST_BLESS", "0");
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("upper::files_1_inputs_a_txt")
+        .ok("upper::files_2_inputs_b_txt")
+        .assert(output);
+}
+
+#[test]
+fn cases_from() {
+    let prj = prj("cases_from.rs");
+    let cases_path = prj.path().join("cases");
+    std::fs::create_dir(&cases_path).unwrap();
+
+    File::create(cases_path.join("sum.csv"))
+        .unwrap()
+        .write_all(b"name,a,b,expected\nzero,0,0,0\none,0,1,1\n,2,2,5\n")
+        .unwrap();
+    File::create(cases_path.join("addresses.json"))
+        .unwrap()
+        .write_all(
+            br#"[
+                {"name": "localhost", "addr": "127.0.0.1:8080", "port": 8080},
+                {"addr": "1.2.3.4:9000", "port": 9000}
+            ]"#,
+        )
+        .unwrap();
+    File::create(cases_path.join("len.toml"))
+        .unwrap()
+        .write_all(b"[empty]\ninput = ''\nexpected = 0\n[[cases]]\ninput = 'abc'\nexpected = 3\n")
+        .unwrap();
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("sum::case_1_zero")
+        .ok("sum::case_2_one")
+        .fail("sum::case_3")
+        .ok("address_port::case_1_localhost")
+        .ok("address_port::case_2")
+        .ok("len::case_1_inline")
+        .ok("len::case_2_empty")
+        .ok("len::case_3")
+        .assert(output);
+}
+
 #[test]
 fn mutable_input() {
     let (output, _) = run_test("mut.rs");
@@ -1687,6 +2314,50 @@ mod should_show_correct_errors {
         );
     }
 
+    #[test]
+    fn if_zip_lists_have_different_lengths() {
+        let (output, name) = execute();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                "
+                error: All the #[zip] lists should have the same length: this one has 2 values instead of 3
+                   --> {}/src/lib.rs:124:77",
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            "
+            124 | fn error_zip_lists_of_different_lengths(#[values(1, 2, 3)] a: u32, #[values(4, 5)] b: u32) {}
+                |                                                                             ^^^^
+            "
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn if_name_template_generates_duplicated_names() {
+        let (output, name) = execute();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: The test name `with_1` is generated more than once: use more arguments in the name template
+                   --> {}/src/lib.rs:126:17
+                    |
+                126 | #[rstest(name = "with_{{a}}")]
+                    |                 ^^^^^^^^^^"#,
+                name
+            )
+            .unindent()
+        );
+    }
+
     #[test]
     fn if_files_contains_absolute_path() {
         let (output, name) = execute();
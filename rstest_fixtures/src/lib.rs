@@ -1,8 +1,6 @@
 use std::fmt::Debug;
 
-pub trait TearDown {
-    fn tear_down(self);
-}
+pub use rstest::teardown::{TearDown, TearDownClosure};
 
 #[derive(Default)]
 pub struct EmptyGuard {}
@@ -11,13 +9,6 @@ impl TearDown for EmptyGuard {
     fn tear_down(self) {}
 }
 
-impl<A: TearDown, B: TearDown> TearDown for (A, B) {
-    fn tear_down(self) {
-        self.0.tear_down();
-        self.1.tear_down();
-    }
-}
-
 pub struct Fixture<T, G: TearDown> {
     inner: Option<T>,
     guard: Option<G>,
@@ -59,20 +50,6 @@ impl<T> From<T> for Fixture<T, EmptyGuard> {
     }
 }
 
-pub struct TearDownClosure<F:FnOnce() -> ()>(F);
-
-impl<F: FnOnce() -> ()> TearDown for TearDownClosure<F> {
-    fn tear_down(self) {
-        self.0()
-    }
-}
-
-impl<F: FnOnce()->()> From<F> for TearDownClosure<F> {
-    fn from(closure: F) -> Self {
-        TearDownClosure(closure)
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
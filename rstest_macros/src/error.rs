@@ -2,16 +2,17 @@
 use std::collections::HashMap;
 
 use proc_macro2::TokenStream;
-use syn::{spanned::Spanned, visit::Visit};
-use syn::{visit, ItemFn};
+use syn::spanned::Spanned;
+use syn::ItemFn;
 
 use crate::parse::{
     fixture::FixtureInfo,
     rstest::{RsTestData, RsTestInfo},
+    scope::ScopeKind,
 };
 use crate::refident::MaybeIdent;
 
-use super::utils::fn_args_has_ident;
+use super::utils::{fn_args_has_ident, SearchImpl};
 
 pub(crate) fn rstest(test: &ItemFn, info: &RsTestInfo) -> TokenStream {
     missed_arguments(test, info.data.items.iter())
@@ -25,57 +26,112 @@ pub(crate) fn rstest(test: &ItemFn, info: &RsTestInfo) -> TokenStream {
 pub(crate) fn fixture(test: &ItemFn, info: &FixtureInfo) -> TokenStream {
     missed_arguments(test, info.data.items.iter())
         .chain(duplicate_arguments(info.data.items.iter()))
-        .chain(async_once(test, info))
         .chain(generics_once(test, info))
+        .chain(teardown_once(info))
+        .chain(scope_errors(test, info))
+        .chain(params_errors(info))
         .map(|e| e.to_compile_error())
         .collect()
 }
 
-fn async_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
-    match (test.sig.asyncness, info.arguments.get_once()) {
-        (Some(_asyncness), Some(once)) => Box::new(std::iter::once(syn::Error::new(
+fn has_some_generics(test: &ItemFn) -> bool {
+    !test.sig.generics.params.is_empty() || SearchImpl::function_has_some_impl(test)
+}
+
+/// `#[once]` fixtures can be generic over types (one value for every concrete type) but
+/// not over lifetimes, constants or `impl` arguments.
+fn generics_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    let generics = &test.sig.generics;
+    let not_supported = generics.lifetimes().next().is_some()
+        || generics.const_params().next().is_some()
+        || SearchImpl::inputs_have_some_impl(test);
+    match (not_supported, info.arguments.get_once()) {
+        (true, Some(once)) => Box::new(std::iter::once(syn::Error::new(
             once.span(),
-            "Cannot apply #[once] to async fixture.",
+            "Cannot apply #[once] on fixture with lifetimes, const generics or impl arguments.",
         ))),
         _ => Box::new(std::iter::empty()),
     }
 }
 
-#[derive(Default)]
-struct SearchImpl(bool);
-
-impl<'ast> Visit<'ast> for SearchImpl {
-    fn visit_type(&mut self, i: &'ast syn::Type) {
-        if self.0 {
-            return;
-        }
-        if let syn::Type::ImplTrait(_) = i {
-            self.0 = true
-        }
-        visit::visit_type(self, i);
+fn teardown_once(info: &FixtureInfo) -> Errors<'_> {
+    match (info.arguments.get_teardown(), info.arguments.get_once()) {
+        (Some(teardown), Some(_once)) => Box::new(std::iter::once(syn::Error::new(
+            teardown.attr().span(),
+            "Cannot apply #[teardown] to #[once] fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
     }
 }
 
-impl SearchImpl {
-    fn function_has_some_impl(f: &ItemFn) -> bool {
-        let mut s = SearchImpl::default();
-        visit::visit_item_fn(&mut s, f);
-        s.0
+fn scope_errors<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    let scope = match info.arguments.get_scope() {
+        Some(scope) => scope,
+        None => return Box::new(std::iter::empty()),
+    };
+    let span = scope.attr.span();
+    let mut errors = Vec::new();
+    if info.arguments.is_once() {
+        errors.push(syn::Error::new(
+            span,
+            "Cannot apply #[scope] to #[once] fixture.",
+        ));
     }
+    if test.sig.asyncness.is_some() {
+        errors.push(syn::Error::new(
+            span,
+            "Cannot apply #[scope] to async fixture.",
+        ));
+    }
+    if has_some_generics(test) {
+        errors.push(syn::Error::new(
+            span,
+            "Cannot apply #[scope] on generic fixture.",
+        ));
+    }
+    if let (ScopeKind::Module, Some(teardown)) = (scope.kind, info.arguments.get_teardown()) {
+        errors.push(syn::Error::new(
+            teardown.attr().span(),
+            "Cannot apply #[teardown] to #[scope(module)] fixture.",
+        ));
+    }
+    Box::new(errors.into_iter())
 }
 
-fn has_some_generics(test: &ItemFn) -> bool {
-    !test.sig.generics.params.is_empty() || SearchImpl::function_has_some_impl(test)
-}
-
-fn generics_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
-    match (has_some_generics(test), info.arguments.get_once()) {
-        (true, Some(once)) => Box::new(std::iter::once(syn::Error::new(
+fn params_errors(info: &FixtureInfo) -> Errors<'_> {
+    let params = info.arguments.get_params();
+    if params.is_empty() {
+        return Box::new(
+            params
+                .case_args
+                .iter()
+                .map(|a| syn::Error::new(a.span(), "No cases for this argument.")),
+        );
+    }
+    let mut errors = params
+        .cases
+        .iter()
+        .filter(|case| case.args.len() != params.case_args.len())
+        .map(|case| {
+            syn::Error::new_spanned(
+                case,
+                "Wrong case signature: should match the given parameters list.",
+            )
+        })
+        .collect::<Vec<_>>();
+    if let Some(once) = info.arguments.get_once() {
+        errors.push(syn::Error::new(
             once.span(),
-            "Cannot apply #[once] on generic fixture.",
-        ))),
-        _ => Box::new(std::iter::empty()),
+            "Cannot apply #[once] to parametrized fixture.",
+        ));
     }
+    if let Some(scope) = info.arguments.get_scope() {
+        errors.push(syn::Error::new(
+            scope.attr.span(),
+            "Cannot apply #[scope] to parametrized fixture.",
+        ));
+    }
+    Box::new(errors.into_iter())
 }
 
 #[derive(Debug, Default)]
@@ -215,7 +271,7 @@ fn invalid_cases(params: &RsTestData) -> Errors {
 }
 
 fn case_args_without_cases(params: &RsTestData) -> Errors {
-    if !params.has_cases() {
+    if !params.has_cases() && !params.has_cases_from_fn() {
         return Box::new(
             params
                 .case_args()
@@ -227,19 +283,22 @@ fn case_args_without_cases(params: &RsTestData) -> Errors {
 
 #[cfg(test)]
 mod test {
+    use crate::parse::ExtendWithFunctionAttrs;
     use crate::test::{assert_eq, *};
     use rstest_test::assert_in;
 
     use super::*;
 
     #[rstest]
-    #[case::generics("fn f<G: SomeTrait>(){}")]
     #[case::const_generics("fn f<const N: usize>(){}")]
     #[case::lifetimes("fn f<'a>(){}")]
-    #[case::use_impl_in_answer("fn f() -> impl Iterator<Item=u32>{}")]
     #[case::use_impl_in_argumets("fn f(it: impl Iterator<Item=u32>){}")]
     #[should_panic]
     #[case::sanity_check_with_no_generics("fn f() {}")]
+    #[should_panic]
+    #[case::generics("fn f<G: SomeTrait>() -> G {}")]
+    #[should_panic]
+    #[case::use_impl_in_answer("fn f() -> impl Iterator<Item=u32>{}")]
     fn generics_once_should_return_error(#[case] f: &str) {
         let f: ItemFn = f.ast();
         let info = FixtureInfo::default().with_once();
@@ -251,7 +310,10 @@ mod test {
             .collect::<Vec<_>>()
             .join("-----------------------\n");
 
-        assert_in!(out, "Cannot apply #[once] on generic fixture.");
+        assert_in!(
+            out,
+            "Cannot apply #[once] on fixture with lifetimes, const generics or impl arguments."
+        );
     }
 
     #[rstest]
@@ -268,4 +330,100 @@ mod test {
 
         assert_eq!(0, errors.count());
     }
+
+    #[rstest]
+    #[case::guard("#[teardown]")]
+    #[case::expression("#[teardown(|v| v.close())]")]
+    fn teardown_once_should_return_error(#[case] teardown: &str) {
+        let info = FixtureInfo::default().with_once().with_teardown(teardown);
+
+        let errors = teardown_once(&info);
+
+        let out = errors.map(|e| format!("{:?}", e)).collect::<Vec<_>>();
+
+        assert_eq!(1, out.len());
+        assert_in!(out[0], "Cannot apply #[teardown] to #[once] fixture.");
+    }
+
+    #[test]
+    fn teardown_once_should_not_return_error_without_once() {
+        let info = FixtureInfo::default().with_teardown("#[teardown]");
+
+        let errors = teardown_once(&info);
+
+        assert_eq!(0, errors.count());
+    }
+
+    #[rstest]
+    #[case::once(
+        "fn f() -> u32 {}",
+        "#[once]",
+        "Cannot apply #[scope] to #[once] fixture."
+    )]
+    #[case::async_fn(
+        "async fn f() -> u32 {}",
+        "",
+        "Cannot apply #[scope] to async fixture."
+    )]
+    #[case::generics("fn f<T>() -> T {}", "", "Cannot apply #[scope] on generic fixture.")]
+    #[case::use_impl(
+        "fn f(a: impl AsRef<str>) -> u32 {}",
+        "",
+        "Cannot apply #[scope] on generic fixture."
+    )]
+    fn scope_should_return_error(#[case] f: &str, #[case] once: &str, #[case] message: &str) {
+        let f: ItemFn = f.ast();
+        let mut info = FixtureInfo::default().with_scope(ScopeKind::Group);
+        if !once.is_empty() {
+            info = info.with_once();
+        }
+
+        let errors = scope_errors(&f, &info)
+            .map(|e| format!("{:?}", e))
+            .collect::<Vec<_>>();
+
+        assert_eq!(1, errors.len());
+        assert_in!(errors[0], message);
+    }
+
+    #[rstest]
+    #[case::wrong_case(
+        "#[case(1)] #[case(1, 2)] fn f(#[case] a: u32) {}",
+        "Wrong case signature: should match the given parameters list."
+    )]
+    #[case::no_cases("fn f(#[case] a: u32) {}", "No cases for this argument.")]
+    #[case::once(
+        "#[once] fn f(#[values(1, 2)] a: u32) {}",
+        "Cannot apply #[once] to parametrized fixture."
+    )]
+    #[case::scope(
+        "#[scope(group)] fn f(#[values(1, 2)] a: u32) {}",
+        "Cannot apply #[scope] to parametrized fixture."
+    )]
+    fn params_should_return_error(#[case] f: &str, #[case] message: &str) {
+        let mut f: ItemFn = f.ast();
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut f).unwrap();
+
+        let errors = params_errors(&info)
+            .map(|e| format!("{:?}", e))
+            .collect::<Vec<_>>();
+
+        assert_eq!(1, errors.len());
+        assert_in!(errors[0], message);
+    }
+
+    #[rstest]
+    #[case::group(ScopeKind::Group, 0)]
+    #[case::module(ScopeKind::Module, 1)]
+    fn scope_with_teardown(#[case] kind: ScopeKind, #[case] expected: usize) {
+        let f: ItemFn = "fn f() -> (u32, G) {}".ast();
+        let info = FixtureInfo::default()
+            .with_scope(kind)
+            .with_teardown("#[teardown]");
+
+        let errors = scope_errors(&f, &info);
+
+        assert_eq!(expected, errors.count());
+    }
 }
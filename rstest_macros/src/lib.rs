@@ -52,6 +52,7 @@ pub fn rstest(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    let (orig_args, orig_test) = (args.clone(), input.clone());
     let mut test = parse_macro_input!(input as ItemFn);
     let mut info = parse_macro_input!(args as RsTestInfo);
 
@@ -64,7 +65,11 @@ pub fn rstest(
     }
 
     if errors.is_empty() {
-        if info.data.has_list_values() {
+        if let Some(params) = info.arguments.params_arg_to_expand() {
+            render::params(orig_args.into(), orig_test.into(), params)
+        } else if info.data.name_template().is_some() {
+            render::named(test, info)
+        } else if info.data.has_list_values() {
             render::matrix(test, info)
         } else if info.data.has_cases() {
             render::parametrize(test, info)
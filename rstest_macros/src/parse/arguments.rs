@@ -2,6 +2,12 @@ use std::collections::HashMap;
 
 use syn::Ident;
 
+use super::{
+    params::{FixtureParams, ParamsArg},
+    scope::FixtureScope,
+    teardown::TearDown,
+};
+
 #[derive(PartialEq, Debug, Clone, Copy)]
 #[allow(dead_code)]
 #[derive(Default)]
@@ -55,6 +61,10 @@ pub(crate) struct ArgumentsInfo {
     args: HashMap<Ident, ArgumentInfo>,
     is_global_await: bool,
     once: Option<syn::Attribute>,
+    teardown: Option<TearDown>,
+    scope: Option<FixtureScope>,
+    params: FixtureParams,
+    params_args: Vec<ParamsArg>,
 }
 
 impl ArgumentsInfo {
@@ -108,6 +118,39 @@ impl ArgumentsInfo {
         self.get_once().is_some()
     }
 
+    pub(crate) fn set_teardown(&mut self, teardown: Option<TearDown>) {
+        self.teardown = teardown
+    }
+
+    pub(crate) fn get_teardown(&self) -> Option<&TearDown> {
+        self.teardown.as_ref()
+    }
+
+    pub(crate) fn set_scope(&mut self, scope: Option<FixtureScope>) {
+        self.scope = scope
+    }
+
+    pub(crate) fn get_scope(&self) -> Option<&FixtureScope> {
+        self.scope.as_ref()
+    }
+
+    pub(crate) fn set_params(&mut self, params: FixtureParams) {
+        self.params = params
+    }
+
+    pub(crate) fn get_params(&self) -> &FixtureParams {
+        &self.params
+    }
+
+    pub(crate) fn set_params_args(&mut self, params_args: Vec<ParamsArg>) {
+        self.params_args = params_args
+    }
+
+    /// The first `#[params]` argument that doesn't know its fixture's variants yet.
+    pub(crate) fn params_arg_to_expand(&self) -> Option<&ParamsArg> {
+        self.params_args.iter().find(|p| p.variants.is_none())
+    }
+
     pub(crate) fn set_by_ref(&mut self, ident: Ident) {
         self.args
             .entry(ident)
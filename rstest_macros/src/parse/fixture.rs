@@ -10,7 +10,11 @@ use super::{
     arguments::ArgumentsInfo,
     extract_default_return_type, extract_defaults, extract_fixtures, extract_partials_return_type,
     future::{extract_futures, extract_global_awt},
-    parse_vector_trailing_till_double_comma, Attributes, ExtendWithFunctionAttrs, Fixture,
+    params::extract_fixture_params,
+    parse_vector_trailing_till_double_comma,
+    scope::extract_scope,
+    teardown::extract_teardown,
+    Attributes, ExtendWithFunctionAttrs, Fixture,
 };
 use crate::{
     error::ErrorsVec,
@@ -63,16 +67,23 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
             default_return_type,
             partials_return_type,
             once,
+            teardown,
+            scope,
             futures,
-            global_awt
+            global_awt,
+            params
         ) = merge_errors!(
             extract_fixtures(item_fn),
             extract_defaults(item_fn),
             extract_default_return_type(item_fn),
             extract_partials_return_type(item_fn),
             extract_once(item_fn),
+            extract_teardown(item_fn),
+            extract_scope(item_fn),
             extract_futures(item_fn),
-            extract_global_awt(item_fn)
+            extract_global_awt(item_fn),
+            // Should be the last one: the attributes before a case are the case's ones
+            extract_fixture_params(item_fn)
         )?;
         self.data.items.extend(
             fixtures
@@ -87,8 +98,11 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
             self.attributes.set_partial_return_type(id, return_type);
         }
         self.arguments.set_once(once);
+        self.arguments.set_teardown(teardown);
+        self.arguments.set_scope(scope);
         self.arguments.set_global_await(global_awt);
         self.arguments.set_futures(futures.into_iter());
+        self.arguments.set_params(params);
         Ok(())
     }
 }
@@ -562,6 +576,43 @@ mod extend {
             assert!(!info.arguments.is_once());
         }
 
+        #[test]
+        fn find_teardown_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[teardown(|v| v.close())]
+                fn my_fix(f1: u32) -> Connection {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.arguments.get_teardown().is_some());
+            assert_eq!(attrs("#[simple]"), item_fn.attrs);
+        }
+
+        #[test]
+        fn find_scope_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[scope(group)]
+                fn my_fix(f1: u32) -> Context {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(
+                Some(crate::parse::scope::ScopeKind::Group),
+                info.arguments.get_scope().map(|s| s.kind)
+            );
+            assert_eq!(attrs("#[simple]"), item_fn.attrs);
+        }
+
         #[rstest]
         fn extract_future() {
             let mut item_fn = "fn f(#[future] a: u32, b: u32) {}".ast();
@@ -19,7 +19,9 @@ use quote::ToTokens;
 use testcase::TestCase;
 
 use self::{
-    expressions::Expressions, just_once::JustOnceFnArgAttributeExtractor, vlist::ValueList,
+    expressions::Expressions,
+    just_once::JustOnceFnArgAttributeExtractor,
+    vlist::{ValueList, ValuesAttr, ValuesFromConst},
 };
 
 // To use the macros this should be the first one module
@@ -32,7 +34,10 @@ pub(crate) mod expressions;
 pub(crate) mod fixture;
 pub(crate) mod future;
 pub(crate) mod just_once;
+pub(crate) mod params;
 pub(crate) mod rstest;
+pub(crate) mod scope;
+pub(crate) mod teardown;
 pub(crate) mod testcase;
 pub(crate) mod vlist;
 
@@ -392,23 +397,53 @@ pub(crate) fn extract_cases(item_fn: &mut ItemFn) -> Result<Vec<TestCase>, Error
 }
 
 pub(crate) fn extract_value_list(item_fn: &mut ItemFn) -> Result<Vec<ValueList>, ErrorsVec> {
-    struct ValueListBuilder;
-    impl AttrBuilder<Ident> for ValueListBuilder {
-        type Out = ValueList;
+    let (value_list, from_consts) = extract_values(item_fn)?;
+    if !from_consts.is_empty() {
+        return Err(from_consts
+            .iter()
+            .map(|from_const| {
+                syn::Error::new_spanned(
+                    from_const,
+                    "You can use #[values(from = ...)] just in tests",
+                )
+            })
+            .collect::<Vec<_>>()
+            .into());
+    }
+    Ok(value_list)
+}
+
+/// Extract the `#[values(...)]` arguments: the values lists, also the ones that take the
+/// elements of a constant, and the constants whose length should be checked.
+pub(crate) fn extract_values(
+    item_fn: &mut ItemFn,
+) -> Result<(Vec<ValueList>, Vec<ValuesFromConst>), ErrorsVec> {
+    struct ValuesBuilder;
+    impl AttrBuilder<Ident> for ValuesBuilder {
+        type Out = (Ident, ValuesAttr);
 
         fn build(attr: syn::Attribute, extra: &Ident) -> syn::Result<Self::Out> {
-            attr.parse_args::<Expressions>().map(|v| ValueList {
-                arg: extra.clone(),
-                values: v.take().into_iter().map(|e| e.into()).collect(),
-            })
+            attr.parse_args::<ValuesAttr>()
+                .map(|values| (extra.clone(), values))
         }
     }
-    impl Validator<FnArg> for ValueListBuilder {}
+    impl Validator<FnArg> for ValuesBuilder {}
 
-    let mut extractor = JustOnceFnArgAttributeExtractor::<ValueListBuilder>::new("values");
+    let mut extractor = JustOnceFnArgAttributeExtractor::<ValuesBuilder>::new("values");
 
     extractor.visit_item_fn_mut(item_fn);
-    extractor.take()
+    let (mut value_list, mut from_consts) = (vec![], vec![]);
+    for (arg, values) in extractor.take()? {
+        match values {
+            ValuesAttr::List(values) => value_list.push(ValueList { arg, values }),
+            ValuesAttr::FromConst(path, len) => {
+                let from_const = ValuesFromConst { arg, path, len };
+                value_list.push(from_const.value_list()?);
+                from_consts.push(from_const);
+            }
+        }
+    }
+    Ok((value_list, from_consts))
 }
 
 /// Simple struct used to visit function args attributes to extract the
@@ -533,6 +568,156 @@ pub(crate) fn check_timeout_attrs(item_fn: &mut ItemFn) -> Result<(), ErrorsVec>
     checker.take()
 }
 
+/// Check the `#[retries(n)]` attributes of the function and its cases: they should be
+/// run before extracting the cases.
+pub(crate) fn check_retries_attrs(item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
+    let errors = item_fn
+        .attrs
+        .iter()
+        .filter(|&a| attr_is(a, "retries"))
+        .map(parse_retries)
+        .filter_map(Result::err)
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// Check the `#[tag("name", ...)]` attributes of the function and its cases: they should
+/// be run before extracting the cases.
+pub(crate) fn check_tag_attrs(item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
+    let errors = item_fn
+        .attrs
+        .iter()
+        .filter(|&a| attr_is(a, "tag"))
+        .map(parse_tags)
+        .filter_map(Result::err)
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// Check the `#[xfail]`, `#[xfail("reason")]` and `#[expect_err("regex")]` attributes of
+/// the function and its cases: they should be run before extracting the cases.
+pub(crate) fn check_expect_attrs(item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
+    let mut errors = vec![];
+    for attr in item_fn.attrs.iter() {
+        if attr_is(attr, "xfail") {
+            if let Err(e) = parse_xfail(attr) {
+                errors.push(e);
+            }
+        } else if attr_is(attr, "expect_err") {
+            if !cfg!(feature = "expect-err") {
+                errors.push(syn::Error::new_spanned(
+                    attr,
+                    "Enable expect-err feature to use #[expect_err(...)]",
+                ));
+            }
+            if let Err(e) = parse_expect_err(attr) {
+                errors.push(e);
+            }
+            if item_fn.sig.output == syn::ReturnType::Default {
+                errors.push(syn::Error::new_spanned(
+                    attr,
+                    "You can use #[expect_err(...)] just in tests that return a Result",
+                ));
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// Extract a function attribute without arguments like `#[pairwise]` or `#[zip]`: it should
+/// be run before extracting the cases.
+pub(crate) fn extract_flag_attr(
+    item_fn: &mut ItemFn,
+    name: &str,
+) -> Result<Option<Ident>, ErrorsVec> {
+    let (flags, remain): (Vec<_>, Vec<_>) = std::mem::take(&mut item_fn.attrs)
+        .into_iter()
+        .partition(|attr| attr_is(attr, name));
+    item_fn.attrs = remain;
+
+    let errors = flags
+        .iter()
+        .filter(|attr| !matches!(attr.meta, syn::Meta::Path(_)))
+        .map(|attr| syn::Error::new_spanned(attr, format!("Use #[{name}] without arguments")))
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        return Err(errors.into());
+    }
+    Ok(flags
+        .first()
+        .and_then(|attr| attr.path().get_ident().cloned()))
+}
+
+/// Parse the optional reason in `#[xfail]` or `#[xfail("reason")]`.
+pub(crate) fn parse_xfail(attr: &syn::Attribute) -> syn::Result<Option<syn::LitStr>> {
+    match &attr.meta {
+        syn::Meta::Path(_) => Ok(None),
+        _ => attr
+            .parse_args::<syn::LitStr>()
+            .map(Some)
+            .map_err(|_| syn::Error::new_spanned(attr, r#"Use #[xfail] or #[xfail("reason")]"#)),
+    }
+}
+
+/// Parse the regular expression in `#[expect_err("regex")]`.
+pub(crate) fn parse_expect_err(attr: &syn::Attribute) -> syn::Result<syn::LitStr> {
+    let pattern = attr
+        .parse_args::<syn::LitStr>()
+        .map_err(|_| syn::Error::new_spanned(attr, r#"Use #[expect_err("regex")]"#))?;
+    regex::Regex::new(&pattern.value()).map_err(|e| {
+        syn::Error::new_spanned(
+            &pattern,
+            format!(r#""{}" Should be a valid regex: {e}"#, pattern.value()),
+        )
+    })?;
+    Ok(pattern)
+}
+
+/// Parse the number of retries in `#[retries(n)]`.
+pub(crate) fn parse_retries(attr: &syn::Attribute) -> syn::Result<usize> {
+    attr.parse_args::<syn::LitInt>()
+        .and_then(|n| n.base10_parse())
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| syn::Error::new_spanned(attr, "Use #[retries(n)] with n a positive integer"))
+}
+
+/// Parse the tags in `#[tag("name", ...)]`: they are made of letters, digits, `_` and `-`
+/// because they are part of the tests' names.
+pub(crate) fn parse_tags(attr: &syn::Attribute) -> syn::Result<Vec<syn::LitStr>> {
+    attr.parse_args_with(Punctuated::<syn::LitStr, Token![,]>::parse_terminated)
+        .ok()
+        .map(|tags| tags.into_iter().collect::<Vec<_>>())
+        .filter(|tags| {
+            !tags.is_empty()
+                && tags.iter().all(|tag| {
+                    let tag = tag.value();
+                    !tag.is_empty()
+                        && tag
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                })
+        })
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                attr,
+                r#"Use #[tag("name", ...)] with names made of letters, digits, `_` and `-`"#,
+            )
+        })
+}
+
 #[cfg(test)]
 mod should {
     use super::*;
@@ -0,0 +1,347 @@
+/// Parametrized fixtures: the fixture's cases and values lists and the test arguments that
+/// take all the fixture's variants.
+use std::collections::HashMap;
+
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    visit_mut::VisitMut,
+    Expr, FnArg, Ident, ItemFn, LitStr, Token,
+};
+
+use super::{
+    extract_case_args, extract_cases, extract_value_list,
+    testcase::TestCase,
+    vlist::{Value, ValueList},
+};
+use crate::{
+    error::ErrorsVec,
+    refident::MaybeIdent,
+    utils::{attr_is, sanitize_ident},
+};
+
+/// The cases and the values lists of a fixture: every combination of them is a variant of
+/// the fixture.
+#[derive(PartialEq, Debug, Default, Clone)]
+pub(crate) struct FixtureParams {
+    pub(crate) case_args: Vec<Ident>,
+    pub(crate) cases: Vec<TestCase>,
+    pub(crate) values: Vec<ValueList>,
+}
+
+/// A fixture's variant: its name and the values of the fixture's parametrized arguments.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct Variant {
+    pub(crate) name: String,
+    pub(crate) values: HashMap<String, Expr>,
+}
+
+impl FixtureParams {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.cases.is_empty() && self.values.is_empty()
+    }
+
+    pub(crate) fn variants(&self) -> Vec<Variant> {
+        let cases = match self.cases.is_empty() {
+            true => vec![(vec![], HashMap::new())],
+            false => self
+                .cases
+                .iter()
+                .enumerate()
+                .map(|(n, case)| {
+                    let name = case
+                        .description
+                        .as_ref()
+                        .map(|d| d.to_string())
+                        .unwrap_or_else(|| format!("case_{}", n + 1));
+                    let values = self
+                        .case_args
+                        .iter()
+                        .map(|a| a.to_string())
+                        .zip(case.args.iter().cloned())
+                        .collect();
+                    (vec![name], values)
+                })
+                .collect(),
+        };
+        self.values
+            .iter()
+            .fold(cases, |variants, list| {
+                variants
+                    .into_iter()
+                    .flat_map(|(names, values)| {
+                        list.values.iter().map(move |value| {
+                            let mut names = names.clone();
+                            let mut values = values.clone();
+                            names.push(sanitize_ident(&value.description()));
+                            values.insert(list.arg.to_string(), value.expr.clone());
+                            (names, values)
+                        })
+                    })
+                    .collect()
+            })
+            .into_iter()
+            .map(|(names, values)| Variant {
+                name: names.join("_"),
+                values,
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn extract_fixture_params(item_fn: &mut ItemFn) -> Result<FixtureParams, ErrorsVec> {
+    let composed_tuple!(case_args, cases, values) = merge_errors!(
+        extract_case_args(item_fn),
+        extract_cases(item_fn),
+        extract_value_list(item_fn)
+    )?;
+    let mut cases = cases;
+    // The attributes before a fixture's case belong to the fixture
+    let mut attrs = cases
+        .iter_mut()
+        .flat_map(|case| std::mem::take(&mut case.attrs))
+        .collect::<Vec<_>>();
+    attrs.append(&mut item_fn.attrs);
+    item_fn.attrs = attrs;
+    Ok(FixtureParams {
+        case_args,
+        cases,
+        values,
+    })
+}
+
+/// A test argument that takes all the variants of a parametrized fixture.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct ParamsArg {
+    pub(crate) arg: Ident,
+    pub(crate) fixture: syn::Path,
+    /// The variants' names: `None` till the fixture expands the test with them.
+    pub(crate) variants: Option<Vec<String>>,
+}
+
+impl ParamsArg {
+    pub(crate) fn value_list(&self) -> Option<ValueList> {
+        let fixture = &self.fixture;
+        self.variants.as_ref().map(|variants| ValueList {
+            arg: self.arg.clone(),
+            values: variants
+                .iter()
+                .enumerate()
+                .map(|(index, name)| {
+                    Value::new(parse_quote! { #fixture::param(#index) }, Some(name.clone()))
+                })
+                .collect(),
+        })
+    }
+}
+
+/// The attribute that a parametrized fixture adds to the test with the names of its
+/// variants.
+pub(crate) const VARIANTS_ATTR: &str = "__rstest_params";
+
+struct Variants {
+    arg: Ident,
+    names: Vec<String>,
+}
+
+impl Parse for Variants {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let arg = input.parse()?;
+        let _colon: Token![:] = input.parse()?;
+        let names = Punctuated::<LitStr, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .map(|name| name.value())
+            .collect();
+        Ok(Self { arg, names })
+    }
+}
+
+/// Simple struct used to visit the test arguments marked by `#[params]` and the variants
+/// that the fixtures already provided.
+#[derive(Default)]
+struct ParamsArgsExtractor(Vec<ParamsArg>, Vec<syn::Error>);
+
+impl VisitMut for ParamsArgsExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let (variants, remain): (Vec<_>, Vec<_>) = std::mem::take(&mut node.attrs)
+            .into_iter()
+            .partition(|attr| attr_is(attr, VARIANTS_ATTR));
+        node.attrs = remain;
+
+        syn::visit_mut::visit_item_fn_mut(self, node);
+
+        for attr in variants {
+            match attr.parse_args::<Variants>() {
+                Ok(variants) => match self.0.iter_mut().find(|p| p.arg == variants.arg) {
+                    Some(params) => params.variants = Some(variants.names),
+                    None => self.1.push(syn::Error::new_spanned(
+                        attr,
+                        format!("Missed #[params] argument '{}'.", variants.arg),
+                    )),
+                },
+                Err(err) => self.1.push(err),
+            }
+        }
+    }
+
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        let name = match node.maybe_ident().cloned() {
+            Some(name) => name,
+            None => return,
+        };
+        let arg = match node {
+            FnArg::Typed(arg) => arg,
+            _ => return,
+        };
+        if !arg.attrs.iter().any(|attr| attr_is(attr, "params")) {
+            return;
+        }
+        let (extracted, remain): (Vec<_>, Vec<_>) = std::mem::take(&mut arg.attrs)
+            .into_iter()
+            .partition(|attr| attr_is(attr, "params") || attr_is(attr, "from"));
+        arg.attrs = remain;
+
+        let mut fixture = name.clone().into();
+        for attr in extracted {
+            let parsed = match attr_is(&attr, "from") {
+                true => attr.parse_args::<syn::Path>().map(|path| fixture = path),
+                false => attr.meta.require_path_only().map(|_| ()),
+            };
+            if let Err(err) = parsed {
+                self.1.push(err);
+            }
+        }
+        self.0.push(ParamsArg {
+            arg: name,
+            fixture,
+            variants: None,
+        });
+    }
+}
+
+pub(crate) fn extract_params_args(item_fn: &mut ItemFn) -> Result<Vec<ParamsArg>, ErrorsVec> {
+    let mut extractor = ParamsArgsExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+
+    if extractor.1.is_empty() {
+        Ok(extractor.0)
+    } else {
+        Err(extractor.1.into())
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::{assert_in, assert_not_in};
+
+    #[test]
+    fn extract_fixture_values_and_cases() {
+        let mut item_fn: ItemFn = r#"
+            #[doc = "a backend"]
+            #[case::memory(Kind::Memory)]
+            #[case(Kind::Sqlite)]
+            fn backend(#[case] kind: Kind, #[values(1, 2)] size: u32) -> Storage {}
+        "#
+        .ast();
+
+        let params = extract_fixture_params(&mut item_fn).unwrap();
+
+        assert_eq!(
+            item_fn,
+            r#"
+            #[doc = "a backend"]
+            fn backend(kind: Kind, size: u32) -> Storage {}
+        "#
+            .ast()
+        );
+        assert_eq!(vec![ident("kind")], params.case_args);
+        assert_eq!(2, params.cases.len());
+        assert_eq!(vec![values_list("size", &["1", "2"])], params.values);
+    }
+
+    #[test]
+    fn name_the_variants_by_cases_and_values() {
+        let mut item_fn: ItemFn = r#"
+            #[case::memory(Kind::Memory)]
+            #[case(Kind::Sqlite)]
+            fn backend(#[case] kind: Kind, #[values(1, 2)] size: u32) -> Storage {}
+        "#
+        .ast();
+
+        let variants = extract_fixture_params(&mut item_fn).unwrap().variants();
+
+        assert_eq!(
+            vec!["memory_1", "memory_2", "case_2_1", "case_2_2"],
+            variants.iter().map(|v| v.name.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(expr("Kind::Sqlite"), variants[3].values["kind"]);
+        assert_eq!(expr("2"), variants[3].values["size"]);
+    }
+
+    #[test]
+    fn not_have_variants_without_values_or_cases() {
+        let mut item_fn: ItemFn = "fn backend(kind: Kind) -> Storage {}".ast();
+
+        let params = extract_fixture_params(&mut item_fn).unwrap();
+
+        assert!(params.is_empty());
+    }
+
+    #[rstest]
+    #[case::by_name("#[params] backend: Storage", "backend")]
+    #[case::by_from(
+        "#[params] #[from(fixtures::backend)] storage: Storage",
+        "fixtures::backend"
+    )]
+    fn extract_params_argument(#[case] arg: &str, #[case] fixture: &str) {
+        let mut item_fn: ItemFn = format!("fn test(a: u32, {arg}) {{}}").ast();
+
+        let params = extract_params_args(&mut item_fn).unwrap();
+
+        assert_eq!(1, params.len());
+        assert_eq!(path(fixture), params[0].fixture);
+        assert!(params[0].variants.is_none());
+        assert_not_in!(item_fn.display_code(), "params");
+        assert_not_in!(item_fn.display_code(), "from");
+    }
+
+    #[test]
+    fn use_the_variants_provided_by_the_fixture() {
+        let mut item_fn: ItemFn = r#"
+            #[__rstest_params(backend: "memory", "sqlite")]
+            fn test(#[params] backend: Storage) {}
+        "#
+        .ast();
+
+        let params = extract_params_args(&mut item_fn).unwrap();
+
+        assert_eq!(item_fn, "fn test(backend: Storage) {}".ast());
+        assert_eq!(
+            Some(ValueList {
+                arg: ident("backend"),
+                values: vec![
+                    Value::new(expr("backend::param(0usize)"), Some("memory".to_owned())),
+                    Value::new(expr("backend::param(1usize)"), Some("sqlite".to_owned())),
+                ],
+            }),
+            params[0].value_list()
+        );
+    }
+
+    #[rstest]
+    #[case::no_argument(
+        r#"#[__rstest_params(other: "a")] fn test(#[params] backend: Storage) {}"#,
+        "Missed #[params] argument 'other'"
+    )]
+    #[case::params_with_args("fn test(#[params(a)] backend: Storage) {}", "unexpected token")]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let err = extract_params_args(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
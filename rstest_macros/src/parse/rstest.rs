@@ -3,27 +3,39 @@ use syn::{
     Ident, ItemFn, Token,
 };
 
-use self::files::{extract_files, ValueListFromFiles};
+use self::{
+    cases_from::{extract_cases_from, extract_cases_from_fn, CasesFromFiles, CasesFromFn},
+    exclude_when::{extract_exclude_when, ExcludeWhen},
+    files::{extract_files, RuntimeFiles, Tracked, ValueListFromFiles},
+    name_template::NameTemplate,
+    random::{extract_random, RandomArg},
+};
 
 use super::{
     arguments::ArgumentsInfo,
     by_ref::extract_by_ref,
-    check_timeout_attrs, extract_case_args, extract_cases, extract_excluded_trace,
-    extract_fixtures, extract_value_list,
+    check_expect_attrs, check_retries_attrs, check_tag_attrs, check_timeout_attrs,
+    extract_case_args, extract_cases, extract_excluded_trace, extract_fixtures, extract_flag_attr,
+    extract_values,
     future::{extract_futures, extract_global_awt},
+    params::extract_params_args,
     parse_vector_trailing_till_double_comma,
     testcase::TestCase,
     Attribute, Attributes, ExtendWithFunctionAttrs, Fixture,
 };
-use crate::parse::vlist::ValueList;
+use crate::parse::vlist::{ValueList, ValuesFromConst};
 use crate::{
     error::ErrorsVec,
     refident::{MaybeIdent, RefIdent},
 };
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, ToTokens};
+use quote::{format_ident, quote, ToTokens};
 
+pub(crate) mod cases_from;
+pub(crate) mod exclude_when;
 pub(crate) mod files;
+pub(crate) mod name_template;
+pub(crate) mod random;
 
 #[derive(PartialEq, Debug, Default)]
 pub(crate) struct RsTestInfo {
@@ -51,7 +63,32 @@ impl Parse for RsTestInfo {
 
 impl ExtendWithFunctionAttrs for RsTestInfo {
     fn extend_with_function_attrs(&mut self, item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let composed_tuple!(_inner, excluded, _timeout, futures, global_awt, by_refs) = merge_errors!(
+        let composed_tuple!(
+            _retries,
+            _tags,
+            _expect,
+            pairwise,
+            zip,
+            exclude_when,
+            params_args,
+            _inner,
+            excluded,
+            _timeout,
+            futures,
+            global_awt,
+            by_refs
+        ) = merge_errors!(
+            // Before the cases: they take their `#[retries(n)]`, `#[tag(...)]`, `#[xfail]`,
+            // `#[expect_err(...)]`, `#[pairwise]`, `#[zip]` and `#[exclude_when(...)]`
+            // attributes
+            check_retries_attrs(item_fn),
+            check_tag_attrs(item_fn),
+            check_expect_attrs(item_fn),
+            extract_flag_attr(item_fn, "pairwise"),
+            extract_flag_attr(item_fn, "zip"),
+            extract_exclude_when(item_fn),
+            // Before the fixtures: `#[params]` arguments can use `#[from]` too
+            extract_params_args(item_fn),
             self.data.extend_with_function_attrs(item_fn),
             extract_excluded_trace(item_fn),
             check_timeout_attrs(item_fn),
@@ -63,6 +100,87 @@ impl ExtendWithFunctionAttrs for RsTestInfo {
         self.arguments.set_global_await(global_awt);
         self.arguments.set_futures(futures.into_iter());
         self.arguments.set_by_refs(by_refs.into_iter());
+        self.data.items.extend(
+            params_args
+                .iter()
+                .filter_map(|p| p.value_list())
+                .map(|l| l.into()),
+        );
+        self.arguments.set_params_args(params_args);
+        if !exclude_when.is_empty() && !self.data.has_list_values() {
+            return Err(exclude_when
+                .iter()
+                .map(|e| {
+                    syn::Error::new_spanned(
+                        e,
+                        "You can use #[exclude_when(...)] just in tests with #[values(...)] or #[files(...)] arguments",
+                    )
+                })
+                .collect::<Vec<_>>()
+                .into());
+        }
+        self.data
+            .items
+            .extend(exclude_when.into_iter().map(RsTestItem::ExcludeWhen));
+        self.set_combinations(pairwise, zip)
+    }
+}
+
+impl RsTestInfo {
+    /// Check the `#[pairwise]` or `#[zip]` attribute that selects the values lists'
+    /// combinations to render.
+    fn set_combinations(
+        &mut self,
+        pairwise: Option<Ident>,
+        zip: Option<Ident>,
+    ) -> Result<(), ErrorsVec> {
+        if let (Some(_), Some(zip)) = (&pairwise, &zip) {
+            return Err(syn::Error::new(
+                zip.span(),
+                "You cannot use #[zip] together with #[pairwise]",
+            )
+            .into());
+        }
+        let attr = match pairwise.or(zip) {
+            Some(attr) => attr,
+            None => return Ok(()),
+        };
+        if !self.data.has_list_values() {
+            return Err(syn::Error::new(
+                attr.span(),
+                format!(
+                    "You can use #[{attr}] just in tests with #[values(...)] or #[files(...)] arguments"
+                ),
+            )
+            .into());
+        }
+        if attr == RsTestAttributes::ZIP_ATTR {
+            let len = self
+                .data
+                .list_values()
+                .map(|list| list.values.len())
+                .max()
+                .unwrap_or_default();
+            let errors = self
+                .data
+                .list_values()
+                .filter(|list| list.values.len() < len)
+                .map(|list| {
+                    let values = list.values.iter().map(|v| &v.expr);
+                    syn::Error::new_spanned(
+                        quote! { #(#values),* },
+                        format!(
+                            "All the #[zip] lists should have the same length: this one has {} values instead of {len}",
+                            list.values.len()
+                        ),
+                    )
+                })
+                .collect::<Vec<_>>();
+            if !errors.is_empty() {
+                return Err(errors.into());
+            }
+        }
+        self.attributes.add_combinations(attr);
         Ok(())
     }
 }
@@ -118,6 +236,59 @@ impl RsTestData {
     pub(crate) fn has_list_values(&self) -> bool {
         self.list_values().next().is_some()
     }
+
+    pub(crate) fn runtime_files(&self) -> impl Iterator<Item = &RuntimeFiles> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::RuntimeFiles(ref files) => Some(files),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn values_from_consts(&self) -> impl Iterator<Item = &ValuesFromConst> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::ValuesFromConst(ref from_const) => Some(from_const),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn randoms(&self) -> impl Iterator<Item = &RandomArg> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::Random(ref random) => Some(random),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn name_template(&self) -> Option<&NameTemplate> {
+        self.items.iter().find_map(|it| match it {
+            RsTestItem::NameTemplate(ref template) => Some(template),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn cases_from_fn(&self) -> Option<&CasesFromFn> {
+        self.items.iter().find_map(|it| match it {
+            RsTestItem::CasesFromFn(ref cases_from_fn) => Some(cases_from_fn),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn has_cases_from_fn(&self) -> bool {
+        self.cases_from_fn().is_some()
+    }
+
+    pub(crate) fn tracked(&self) -> impl Iterator<Item = &Tracked> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::Tracked(ref tracked) => Some(tracked),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn exclusions(&self) -> impl Iterator<Item = &ExcludeWhen> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::ExcludeWhen(ref exclude_when) => Some(exclude_when),
+            _ => None,
+        })
+    }
 }
 
 impl Parse for RsTestData {
@@ -134,24 +305,79 @@ impl Parse for RsTestData {
 
 impl ExtendWithFunctionAttrs for RsTestData {
     fn extend_with_function_attrs(&mut self, item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let composed_tuple!(fixtures, case_args, cases, value_list, files) = merge_errors!(
+        let composed_tuple!(
+            fixtures,
+            case_args,
+            cases,
+            cases_from,
+            cases_from_fn,
+            values,
+            files,
+            randoms
+        ) = merge_errors!(
             extract_fixtures(item_fn),
             extract_case_args(item_fn),
             extract_cases(item_fn),
-            extract_value_list(item_fn),
-            extract_files(item_fn)
+            extract_cases_from(item_fn),
+            extract_cases_from_fn(item_fn),
+            extract_values(item_fn),
+            extract_files(item_fn),
+            extract_random(item_fn)
         )?;
+        let (value_list, from_consts) = values;
+        if let Some(cases_from_fn) = &cases_from_fn {
+            cases_from_fn.check(
+                &case_args,
+                !cases.is_empty()
+                    || !cases_from.is_empty()
+                    || !value_list.is_empty()
+                    || files.iter().any(|(_, refs)| !refs.is_runtime()),
+            )?;
+        }
+        let (cases_from, mut tracked) =
+            CasesFromFiles::default().to_cases(cases_from, &case_args)?;
+        if files.iter().any(|(_, refs)| refs.is_sharded()) {
+            tracked.push(Tracked::shard_env());
+        }
+        if files.iter().any(|(_, refs)| refs.is_blessable()) {
+            tracked.push(Tracked::bless_env());
+        }
+        let (runtime_files, files): (Vec<_>, Vec<_>) =
+            files.into_iter().partition(|(_, refs)| refs.is_runtime());
 
         self.items.extend(fixtures.into_iter().map(|f| f.into()));
         self.items.extend(case_args.into_iter().map(|f| f.into()));
         self.items.extend(cases.into_iter().map(|f| f.into()));
+        self.items.extend(cases_from.into_iter().map(|f| f.into()));
+        self.items
+            .extend(tracked.into_iter().map(RsTestItem::Tracked));
+        self.items
+            .extend(cases_from_fn.into_iter().map(RsTestItem::CasesFromFn));
         self.items.extend(value_list.into_iter().map(|f| f.into()));
+        self.items
+            .extend(from_consts.into_iter().map(RsTestItem::ValuesFromConst));
         self.items.extend(
             ValueListFromFiles::default()
                 .to_value_list(files)?
                 .into_iter()
                 .map(|f| f.into()),
         );
+        self.items.extend(
+            ValueListFromFiles::default()
+                .to_runtime_files(runtime_files)?
+                .into_iter()
+                .map(RsTestItem::RuntimeFiles),
+        );
+        self.items
+            .extend(randoms.into_iter().map(RsTestItem::Random));
+        if let Some(template) = self.name_template() {
+            template.check(
+                &self
+                    .case_args()
+                    .chain(self.list_values().map(|list| list.ident()))
+                    .collect::<Vec<_>>(),
+            )?;
+        }
         Ok(())
     }
 }
@@ -162,6 +388,13 @@ pub(crate) enum RsTestItem {
     CaseArgName(Ident),
     TestCase(TestCase),
     ValueList(ValueList),
+    RuntimeFiles(RuntimeFiles),
+    ValuesFromConst(ValuesFromConst),
+    Random(RandomArg),
+    NameTemplate(NameTemplate),
+    CasesFromFn(CasesFromFn),
+    ExcludeWhen(ExcludeWhen),
+    Tracked(Tracked),
 }
 
 impl From<Fixture> for RsTestItem {
@@ -194,6 +427,8 @@ impl Parse for RsTestItem {
             input.parse::<TestCase>().map(RsTestItem::TestCase)
         } else if input.peek2(Token![=>]) {
             input.parse::<ValueList>().map(RsTestItem::ValueList)
+        } else if input.peek2(Token![=]) {
+            input.parse::<NameTemplate>().map(RsTestItem::NameTemplate)
         } else if input.fork().parse::<Fixture>().is_ok() {
             input.parse::<Fixture>().map(RsTestItem::Fixture)
         } else if input.fork().parse::<Ident>().is_ok() {
@@ -211,7 +446,11 @@ impl MaybeIdent for RsTestItem {
             Fixture(ref fixture) => Some(fixture.ident()),
             CaseArgName(ref case_arg) => Some(case_arg),
             ValueList(ref value_list) => Some(value_list.ident()),
-            TestCase(_) => None,
+            RuntimeFiles(ref files) => Some(&files.arg),
+            Random(ref random) => Some(&random.arg),
+            // The constant's elements are resolved by their values list
+            TestCase(_) | NameTemplate(_) | CasesFromFn(_) | ExcludeWhen(_) | Tracked(_)
+            | ValuesFromConst(_) => None,
         }
     }
 }
@@ -224,6 +463,13 @@ impl ToTokens for RsTestItem {
             CaseArgName(ref case_arg) => case_arg.to_tokens(tokens),
             TestCase(ref case) => case.to_tokens(tokens),
             ValueList(ref list) => list.to_tokens(tokens),
+            RuntimeFiles(ref files) => files.to_tokens(tokens),
+            ValuesFromConst(ref from_const) => from_const.to_tokens(tokens),
+            Random(ref random) => random.to_tokens(tokens),
+            NameTemplate(ref template) => template.to_tokens(tokens),
+            CasesFromFn(ref cases_from_fn) => cases_from_fn.to_tokens(tokens),
+            ExcludeWhen(ref exclude_when) => exclude_when.to_tokens(tokens),
+            Tracked(ref tracked) => tracked.to_tokens(tokens),
         }
     }
 }
@@ -233,6 +479,8 @@ wrap_attributes!(RsTestAttributes);
 impl RsTestAttributes {
     const TRACE_VARIABLE_ATTR: &'static str = "trace";
     const NOTRACE_VARIABLE_ATTR: &'static str = "notrace";
+    const PAIRWISE_ATTR: &'static str = "pairwise";
+    const ZIP_ATTR: &'static str = "zip";
 
     pub(crate) fn trace_me(&self, ident: &Ident) -> bool {
         if self.should_trace() {
@@ -269,6 +517,21 @@ impl RsTestAttributes {
         ));
     }
 
+    pub(crate) fn pairwise(&self) -> bool {
+        self.iter()
+            .any(|m| matches!(m, Attribute::Attr(i) if i == Self::PAIRWISE_ATTR))
+    }
+
+    pub(crate) fn zip(&self) -> bool {
+        self.iter()
+            .any(|m| matches!(m, Attribute::Attr(i) if i == Self::ZIP_ATTR))
+    }
+
+    /// Add the `pairwise` or `zip` attribute.
+    pub(crate) fn add_combinations(&mut self, combinations: Ident) {
+        self.inner.attributes.push(Attribute::Attr(combinations));
+    }
+
     fn is_trace(m: &Attribute) -> bool {
         matches!(m, Attribute::Attr(i) if i == Self::TRACE_VARIABLE_ATTR)
     }
@@ -358,6 +621,280 @@ mod test {
         assert!(format!("{:?}", errors).contains("async-timeout feature"))
     }
 
+    #[test]
+    fn should_check_all_retries_to_catch_the_right_errors() {
+        let mut item_fn = r#"
+            #[retries(-1)]
+            #[case(1)]
+            #[retries(2)]
+            #[case(2)]
+            #[retries(some)]
+            #[case(3)]
+            #[retries(0)]
+            fn test_fn(#[case] arg: u32) {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_eq!(3, errors.len());
+        assert_in!(format!("{:?}", errors), "Use #[retries(n)]");
+    }
+
+    #[test]
+    fn should_check_all_tags_to_catch_the_right_errors() {
+        let mut item_fn = r#"
+            #[tag]
+            #[case(1)]
+            #[tag("db", "slow")]
+            #[case(2)]
+            #[tag("no spaces")]
+            #[tag(42)]
+            fn test_fn(#[case] arg: u32) {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_eq!(3, errors.len());
+        assert_in!(format!("{:?}", errors), r#"Use #[tag(\"name\", ...)]"#);
+    }
+
+    #[test]
+    fn should_accept_async_retries() {
+        let mut item_fn = r#"
+            #[retries(2)]
+            async fn test_fn() {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+    }
+
+    #[rstest]
+    #[case::xfail_with_reason(r#"#[xfail("known bug")] fn test_fn() {}"#)]
+    #[case::xfail_on_case(r#"#[xfail] #[case(1)] fn test_fn(#[case] a: u32) {}"#)]
+    #[case::xfail_in_async_test(r#"#[xfail] async fn test_fn() {}"#)]
+    #[case::expect_err(
+        r#"#[expect_err("invalid (digit|port)")] fn test_fn() -> Result<(), String> { Ok(()) }"#
+    )]
+    fn should_parse_expect_attributes(#[case] item_fn: &str) {
+        let mut item_fn = item_fn.ast();
+
+        let mut info = RsTestInfo::default();
+
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+    }
+
+    #[rstest]
+    #[case::xfail_not_a_string(r#"#[xfail(42)] fn test_fn() {}"#, "Use #[xfail]")]
+    #[case::expect_err_without_pattern(
+        r#"#[expect_err] fn test_fn() -> Result<(), String> { Ok(()) }"#,
+        "Use #[expect_err"
+    )]
+    #[case::expect_err_invalid_regex(
+        r#"#[expect_err("(unclosed")] fn test_fn() -> Result<(), String> { Ok(()) }"#,
+        "Should be a valid regex"
+    )]
+    #[case::expect_err_without_result(
+        r#"#[expect_err("invalid")] #[case(1)] fn test_fn(#[case] a: u32) {}"#,
+        "tests that return a Result"
+    )]
+    fn should_return_error_for_invalid_expect_attributes(
+        #[case] item_fn: &str,
+        #[case] message: &str,
+    ) {
+        let mut item_fn = item_fn.ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", errors), message);
+    }
+
+    #[cfg(not(feature = "expect-err"))]
+    #[test]
+    fn should_return_error_for_expect_err_without_the_feature() {
+        let mut item_fn =
+            r#"#[expect_err("invalid")] fn test_fn() -> Result<(), String> { Ok(()) }"#.ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", errors), "expect-err feature");
+    }
+
+    #[test]
+    fn should_extract_cases_from_fn() {
+        let mut item_fn = r#"
+            #[cases_from_fn(generated::cases)]
+            fn test_fn(#[case] a: u32, #[case] b: &str, fixture: u32) {}
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        assert_eq!(
+            "generated :: cases",
+            info.data
+                .cases_from_fn()
+                .unwrap()
+                .to_token_stream()
+                .to_string()
+        );
+        assert_eq!(
+            vec!["a", "b"],
+            info.data
+                .case_args()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+        assert!(!info.data.has_cases());
+        assert_eq!(
+            item_fn,
+            "fn test_fn(a: u32, b: &str, fixture: u32) {}".ast()
+        );
+    }
+
+    #[rstest]
+    #[case::without_case_args(
+        r#"#[cases_from_fn(cases)] fn test_fn(a: u32) {}"#,
+        "No #[case] arguments"
+    )]
+    #[case::with_cases(
+        r#"#[case(1)] #[cases_from_fn(cases)] fn test_fn(#[case] a: u32) {}"#,
+        "together with #[case(...)]"
+    )]
+    #[case::with_values(
+        r#"#[cases_from_fn(cases)] fn test_fn(#[case] a: u32, #[values(1, 2)] b: u32) {}"#,
+        "together with #[case(...)]"
+    )]
+    #[case::more_than_once(
+        r#"#[cases_from_fn(cases)] #[cases_from_fn(others)] fn test_fn(#[case] a: u32) {}"#,
+        "just once"
+    )]
+    #[case::not_a_path(
+        r#"#[cases_from_fn("cases")] fn test_fn(#[case] a: u32) {}"#,
+        "expected identifier"
+    )]
+    #[case::in_async_test(
+        r#"#[cases_from_fn(cases)] async fn test_fn(#[case] a: u32) {}"#,
+        "async tests"
+    )]
+    fn should_return_error_for_invalid_cases_from_fn(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn = item_fn.ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", errors), message);
+    }
+
+    #[rstest]
+    #[case::pairwise("pairwise", true, false)]
+    #[case::zip("zip", false, true)]
+    fn should_extract_combinations_attributes_also_after_the_cases(
+        #[case] attr: &str,
+        #[case] pairwise: bool,
+        #[case] zip: bool,
+    ) {
+        let mut item_fn = format!(
+            r#"
+            #[{attr}]
+            #[case(1)]
+            #[case(2)]
+            fn test_fn(#[case] a: u32, #[values(1, 2)] b: u32, #[values(3, 4)] c: u32) {{}}
+        "#
+        )
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        assert_eq!(pairwise, info.attributes.pairwise());
+        assert_eq!(zip, info.attributes.zip());
+        assert!(info.data.cases().all(|case| case.attrs.is_empty()));
+    }
+
+    #[rstest]
+    #[case::with_arguments(
+        r#"#[pairwise(3)] fn test_fn(#[values(1, 2)] a: u32) {}"#,
+        "without arguments"
+    )]
+    #[case::without_values(
+        r#"#[pairwise] #[case(1)] fn test_fn(#[case] a: u32) {}"#,
+        "#[values(...)] or #[files(...)] arguments"
+    )]
+    #[case::zip_with_arguments(
+        r#"#[zip(a, b)] fn test_fn(#[values(1, 2)] a: u32, #[values(1, 2)] b: u32) {}"#,
+        "Use #[zip] without arguments"
+    )]
+    #[case::zip_without_values(r#"#[zip] fn test_fn(a: u32) {}"#, "You can use #[zip] just")]
+    #[case::zip_and_pairwise(
+        r#"#[pairwise] #[zip] fn test_fn(#[values(1, 2)] a: u32, #[values(1, 2)] b: u32) {}"#,
+        "together with #[pairwise]"
+    )]
+    #[case::zip_lists_of_different_lengths(
+        r#"#[zip] fn test_fn(#[values(1, 2, 3)] a: u32, #[values(1, 2)] b: u32) {}"#,
+        "this one has 2 values instead of 3"
+    )]
+    #[case::exclude_when_without_values(
+        r#"#[exclude_when(a == 1)] #[case(1)] fn test_fn(#[case] a: u32) {}"#,
+        "You can use #[exclude_when(...)] just"
+    )]
+    fn should_return_error_for_invalid_combinations(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn = item_fn.ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", errors), message);
+    }
+
+    #[test]
+    fn should_extract_values_from_consts() {
+        let mut item_fn = r#"
+            #[exclude_when(a == 3)]
+            fn test_fn(#[values(from = data::A; len = 2)] a: u32, #[values(range = 0..2)] b: u32) {}
+            "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let from_consts = info.data.values_from_consts().collect::<Vec<_>>();
+        assert_eq!(1, from_consts.len());
+        assert_eq!("a", &from_consts[0].arg.to_string());
+        assert_eq!(from_consts[0].path, "data::A".ast());
+        let list_values = info.data.list_values().collect::<Vec<_>>();
+        assert_eq!(
+            to_args!([
+                "::core::clone::Clone::clone(&data::A[0usize])",
+                "::core::clone::Clone::clone(&data::A[1usize])"
+            ]),
+            list_values[0].args()
+        );
+        assert_eq!(to_args!(["0", "1"]), list_values[1].args());
+        assert_eq!(1, info.data.exclusions().count());
+    }
+
     fn parse_rstest<S: AsRef<str>>(rstest_data: S) -> RsTestInfo {
         parse_meta(rstest_data)
     }
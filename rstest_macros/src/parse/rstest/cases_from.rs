@@ -0,0 +1,580 @@
+use std::{collections::HashMap, path::Path};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use relative_path::RelativePath;
+use syn::{Attribute, Expr, Ident, ItemFn, LitStr, Path as FnPath};
+
+use super::files::{BaseDir, DefaultBaseDir, Tracked};
+use crate::{
+    error::ErrorsVec,
+    parse::testcase::TestCase,
+    render::crate_resolver::crate_name,
+    utils::{attr_is, sanitize_ident},
+};
+
+/// The column that contains the case description.
+const NAME_COLUMN: &str = "name";
+
+/// The `#[cases_from("path")]` attribute
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CasesFrom {
+    attr: Attribute,
+    path: LitStr,
+}
+
+impl CasesFrom {
+    fn error(&self, msg: &str) -> syn::Error {
+        syn::Error::new_spanned(&self.attr, msg)
+    }
+}
+
+impl TryFrom<Attribute> for CasesFrom {
+    type Error = syn::Error;
+
+    fn try_from(attr: Attribute) -> Result<Self, Self::Error> {
+        let path = attr.parse_args::<LitStr>()?;
+        Ok(Self { attr, path })
+    }
+}
+
+/// Entry point function to extract the `#[cases_from(...)]` function attributes
+pub(crate) fn extract_cases_from(item_fn: &mut ItemFn) -> Result<Vec<CasesFrom>, ErrorsVec> {
+    let (cases_from, remain): (Vec<_>, Vec<_>) = std::mem::take(&mut item_fn.attrs)
+        .into_iter()
+        .partition(|attr| attr_is(attr, "cases_from"));
+    item_fn.attrs = remain;
+
+    let (cases_from, errors): (Vec<_>, Vec<_>) = cases_from
+        .into_iter()
+        .map(CasesFrom::try_from)
+        .partition(Result::is_ok);
+    if errors.is_empty() {
+        Ok(cases_from.into_iter().map(Result::unwrap).collect())
+    } else {
+        Err(errors
+            .into_iter()
+            .map(Result::unwrap_err)
+            .collect::<Vec<_>>()
+            .into())
+    }
+}
+
+/// The `#[cases_from_fn(path::to::fn)]` attribute: the function returns the named cases
+/// that the test runs at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CasesFromFn {
+    attr: Attribute,
+    path: FnPath,
+}
+
+impl CasesFromFn {
+    fn error(&self, msg: &str) -> syn::Error {
+        syn::Error::new_spanned(&self.attr, msg)
+    }
+
+    /// Check that the cases can fill the `#[case]` arguments and that no other attributes
+    /// define the test cases.
+    pub(crate) fn check(&self, case_args: &[Ident], other_cases: bool) -> Result<(), syn::Error> {
+        if case_args.is_empty() {
+            return Err(self.error(
+                "No #[case] arguments: mark the arguments to fill with the case's values by #[case]",
+            ));
+        }
+        if other_cases {
+            return Err(self.error(
+                "You cannot use #[cases_from_fn(...)] together with #[case(...)], #[cases_from(...)] or #[values(...)]",
+            ));
+        }
+        Ok(())
+    }
+
+    /// The runtime name of the case argument `arg`.
+    pub(crate) fn value(arg: &Ident) -> Expr {
+        let ident = format_ident!("__rstest_case_{}", arg);
+        syn::parse_quote! { #ident }
+    }
+
+    /// Wrap `body` in a closure that the runtime calls for each case.
+    pub(crate) fn render<'a>(
+        &self,
+        case_args: impl Iterator<Item = &'a Ident>,
+        body: TokenStream,
+    ) -> TokenStream {
+        let path = &self.path;
+        let rstest_path = crate_name();
+        let args = case_args.map(Self::value);
+        quote! {
+            #rstest_path::cases::execute_cases(#path(), |(#(#args,)*)| {
+                #body
+            })
+        }
+    }
+}
+
+impl TryFrom<Attribute> for CasesFromFn {
+    type Error = syn::Error;
+
+    fn try_from(attr: Attribute) -> Result<Self, Self::Error> {
+        let path = attr.parse_args::<FnPath>()?;
+        Ok(Self { attr, path })
+    }
+}
+
+impl ToTokens for CasesFromFn {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.path.to_tokens(tokens)
+    }
+}
+
+/// Entry point function to extract the `#[cases_from_fn(...)]` function attribute
+pub(crate) fn extract_cases_from_fn(
+    item_fn: &mut ItemFn,
+) -> Result<Option<CasesFromFn>, ErrorsVec> {
+    let (cases_from_fn, remain): (Vec<_>, Vec<_>) = std::mem::take(&mut item_fn.attrs)
+        .into_iter()
+        .partition(|attr| attr_is(attr, "cases_from_fn"));
+    item_fn.attrs = remain;
+
+    let mut cases_from_fn = cases_from_fn
+        .into_iter()
+        .map(CasesFromFn::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(ErrorsVec::from)?
+        .into_iter();
+    let first = cases_from_fn.next();
+    let errors = cases_from_fn
+        .map(|other| other.error("You can use #[cases_from_fn(...)] just once"))
+        .collect::<Vec<_>>();
+    if let Some(asyncness) = item_fn.sig.asyncness.filter(|_| first.is_some()) {
+        return Err(vec![syn::Error::new_spanned(
+            asyncness,
+            "You cannot use #[cases_from_fn(...)] in async tests",
+        )]
+        .into());
+    }
+    if errors.is_empty() {
+        Ok(first)
+    } else {
+        Err(errors.into())
+    }
+}
+
+/// The supported tabular formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Csv,
+    Json,
+    Toml,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    /// Parse the file content in a list of rows: the map from column name to the raw value.
+    fn rows(&self, content: &str) -> Result<Vec<HashMap<String, String>>, String> {
+        match self {
+            Self::Csv => csv_rows(content),
+            Self::Json => json_rows(content),
+            Self::Toml => toml_rows(content),
+        }
+    }
+}
+
+fn csv_rows(content: &str) -> Result<Vec<HashMap<String, String>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::Headers)
+        .from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV file: {e}"))?
+        .clone();
+    reader
+        .records()
+        .map(|record| {
+            record
+                .map_err(|e| format!("Invalid CSV file: {e}"))
+                .map(|record| {
+                    headers
+                        .iter()
+                        .map(ToOwned::to_owned)
+                        .zip(record.iter().map(ToOwned::to_owned))
+                        .collect()
+                })
+        })
+        .collect()
+}
+
+fn json_rows(content: &str) -> Result<Vec<HashMap<String, String>>, String> {
+    use serde_json::Value;
+    let rows = match serde_json::from_str(content).map_err(|e| format!("Invalid JSON file: {e}"))? {
+        Value::Array(rows) => rows,
+        _ => return Err("The JSON file should contain an array of objects".to_owned()),
+    };
+    rows.into_iter()
+        .map(|row| match row {
+            Value::Object(fields) => fields
+                .into_iter()
+                .map(|(column, value)| {
+                    match value {
+                        Value::String(s) => Ok(s),
+                        Value::Number(n) => Ok(n.to_string()),
+                        Value::Bool(b) => Ok(b.to_string()),
+                        _ => Err(format!(
+                            "Unsupported value for '{column}': use strings, numbers or booleans"
+                        )),
+                    }
+                    .map(|value| (column, value))
+                })
+                .collect(),
+            _ => Err("The JSON file should contain an array of objects".to_owned()),
+        })
+        .collect()
+}
+
+fn toml_rows(content: &str) -> Result<Vec<HashMap<String, String>>, String> {
+    use toml::Value;
+    fn row(table: toml::Table, name: Option<&str>) -> Result<HashMap<String, String>, String> {
+        let mut row = table
+            .into_iter()
+            .map(|(column, value)| {
+                match value {
+                    Value::String(s) => Ok(s),
+                    Value::Integer(i) => Ok(i.to_string()),
+                    Value::Float(f) => Ok(f.to_string()),
+                    Value::Boolean(b) => Ok(b.to_string()),
+                    Value::Datetime(d) => Ok(d.to_string()),
+                    _ => Err(format!(
+                        "Unsupported value for '{column}': use strings, numbers, booleans or dates"
+                    )),
+                }
+                .map(|value| (column, value))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        if let Some(name) = name {
+            row.entry(NAME_COLUMN.to_owned())
+                .or_insert_with(|| name.to_owned());
+        }
+        Ok(row)
+    }
+
+    let document = content
+        .parse::<toml::Table>()
+        .map_err(|e| format!("Invalid TOML file: {e}"))?;
+    let mut rows = vec![];
+    for (key, value) in document {
+        match value {
+            Value::Table(table) => rows.push(row(table, Some(&key))?),
+            Value::Array(tables) => {
+                for table in tables {
+                    match table {
+                        Value::Table(table) => rows.push(row(table, None)?),
+                        _ => return Err(format!("'{key}' should be an array of tables")),
+                    }
+                }
+            }
+            _ => return Err(format!("'{key}' should be a table or an array of tables")),
+        }
+    }
+    Ok(rows)
+}
+
+trait FileReader {
+    fn read(&self, path: &Path) -> Result<String, String> {
+        std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read cases file {}: {e}", path.display()))
+    }
+}
+
+struct DefaultFileReader;
+
+impl FileReader for DefaultFileReader {}
+
+/// The struct used to get the test cases from the `#[cases_from(...)]` attributes. You can
+/// inject the base dir resolver and the file reader implementation.
+pub(crate) struct CasesFromFiles<'a> {
+    base_dir: Box<dyn BaseDir + 'a>,
+    reader: Box<dyn FileReader + 'a>,
+}
+
+impl<'a> Default for CasesFromFiles<'a> {
+    fn default() -> Self {
+        Self {
+            base_dir: Box::new(DefaultBaseDir),
+            reader: Box::new(DefaultFileReader),
+        }
+    }
+}
+
+impl<'a> CasesFromFiles<'a> {
+    /// Return the cases and the files to track.
+    pub fn to_cases(
+        &self,
+        cases_from: Vec<CasesFrom>,
+        case_args: &[Ident],
+    ) -> Result<(Vec<TestCase>, Vec<Tracked>), syn::Error> {
+        let mut cases = vec![];
+        let mut tracked = vec![];
+        for refs in cases_from {
+            let (file_cases, file) = self.file_cases(refs, case_args)?;
+            cases.extend(file_cases);
+            tracked.push(file);
+        }
+        Ok((cases, tracked))
+    }
+
+    fn file_cases(
+        &self,
+        refs: CasesFrom,
+        case_args: &[Ident],
+    ) -> Result<(Vec<TestCase>, Tracked), syn::Error> {
+        if case_args.is_empty() {
+            return Err(refs.error(
+                "No #[case] arguments: mark the arguments to fill with the file's columns by #[case]",
+            ));
+        }
+        let path = refs.path.value();
+        let format = Format::from_path(Path::new(&path)).ok_or_else(|| {
+            refs.error("Unsupported cases file format: use a .csv, .json or .toml file")
+        })?;
+        let base_dir = self.base_dir.base_dir().map_err(|msg| refs.error(&msg))?;
+        let abs_path = RelativePath::from_path(&path)
+            .map_err(|e| refs.error(&format!("Invalid cases file path: {e}")))?
+            .to_logical_path(base_dir);
+        let rows = self
+            .reader
+            .read(&abs_path)
+            .and_then(|content| format.rows(&content))
+            .map_err(|msg| refs.error(&format!("{msg} [{path}]")))?;
+        if rows.is_empty() {
+            return Err(refs.error(&format!("No cases found in {path}")));
+        }
+        let tracked = Tracked::File(LitStr::new(&abs_path.to_string_lossy(), refs.path.span()));
+
+        let cases = rows
+            .into_iter()
+            .enumerate()
+            .map(|(n, mut row)| {
+                let args = case_args
+                    .iter()
+                    .map(|arg| {
+                        row.get(&arg.to_string())
+                            .map(|value| -> Expr {
+                                let value = LitStr::new(value, refs.path.span());
+                                syn::parse_quote! { #value }
+                            })
+                            .ok_or_else(|| {
+                                refs.error(&format!(
+                                    "Missed column '{arg}' in the row {} of {path}",
+                                    n + 1
+                                ))
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(TestCase {
+                    args,
+                    attrs: vec![],
+                    description: row
+                        .remove(NAME_COLUMN)
+                        .and_then(|name| case_description(&name, refs.path.span())),
+                })
+            })
+            .collect::<Result<Vec<_>, syn::Error>>()?;
+        Ok((cases, tracked))
+    }
+}
+
+fn case_description(name: &str, span: Span) -> Option<Ident> {
+    let name = sanitize_ident(name);
+    match name.chars().next() {
+        None => None,
+        Some(c) if c.is_ascii_digit() => Some(Ident::new(&format!("_{name}"), span)),
+        Some(_) => Some(Ident::new(&name, span)),
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    struct FakeBaseDir;
+
+    impl BaseDir for FakeBaseDir {
+        fn base_dir(&self) -> Result<PathBuf, String> {
+            Ok(PathBuf::from("/base"))
+        }
+    }
+
+    struct FakeReader(&'static str);
+
+    impl FileReader for FakeReader {
+        fn read(&self, path: &Path) -> Result<String, String> {
+            assert!(path.starts_with("/base"));
+            Ok(self.0.to_owned())
+        }
+    }
+
+    fn cases(file: &str, content: &'static str, args: &[&str]) -> Result<Vec<TestCase>, String> {
+        let mut item_fn: ItemFn = format!(r#"#[cases_from("{file}")] fn test() {{}}"#).ast();
+        let cases_from = extract_cases_from(&mut item_fn).unwrap();
+        CasesFromFiles {
+            base_dir: Box::new(FakeBaseDir),
+            reader: Box::new(FakeReader(content)),
+        }
+        .to_cases(
+            cases_from,
+            &args.iter().map(|&a| ident(a)).collect::<Vec<_>>(),
+        )
+        .map(|(cases, _)| cases)
+        .map_err(|e| e.to_string())
+    }
+
+    fn args_and_descriptions(cases: Vec<TestCase>) -> Vec<(Vec<Expr>, Option<String>)> {
+        cases
+            .into_iter()
+            .map(|c| (c.args, c.description.map(|d| d.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn extract_the_attributes() {
+        let mut item_fn: ItemFn = r#"
+            #[cases_from("first.csv")]
+            #[other]
+            #[cases_from("second.json")]
+            fn test(#[case] a: u32) {}
+        "#
+        .ast();
+
+        let cases_from = extract_cases_from(&mut item_fn).unwrap();
+
+        assert_eq!(
+            vec!["first.csv", "second.json"],
+            cases_from
+                .iter()
+                .map(|c| c.path.value())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(item_fn, "#[other] fn test(#[case] a: u32) {}".ast());
+    }
+
+    #[rstest]
+    #[case::csv("cases.csv", "name, input,expected\nsmall,1,2\n,3,4\n")]
+    #[case::json(
+        "cases.json",
+        r#"[{"name": "small", "input": 1, "expected": "2"}, {"input": 3, "expected": 4}]"#
+    )]
+    #[case::toml_array(
+        "cases.toml",
+        "[[cases]]\nname = 'small'\ninput = 1\nexpected = '2'\n[[cases]]\ninput = 3\nexpected = 4\n"
+    )]
+    fn map_the_columns_to_the_case_arguments(#[case] file: &str, #[case] content: &'static str) {
+        let cases = cases(file, content, &["expected", "input"]).unwrap();
+
+        assert_eq!(
+            vec![
+                (
+                    vec![expr(r#""2""#), expr(r#""1""#)],
+                    Some("small".to_owned())
+                ),
+                (vec![expr(r#""4""#), expr(r#""3""#)], None),
+            ],
+            args_and_descriptions(cases)
+        );
+    }
+
+    #[test]
+    fn use_the_toml_table_names_as_descriptions() {
+        let cases = cases(
+            "cases.toml",
+            "[first]\ninput = 1\n[second]\ninput = 2\nname = 'other name'\n",
+            &["input"],
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                (vec![expr(r#""1""#)], Some("first".to_owned())),
+                (vec![expr(r#""2""#)], Some("othername".to_owned())),
+            ],
+            args_and_descriptions(cases)
+        );
+    }
+
+    #[test]
+    fn track_the_cases_files() {
+        let mut item_fn: ItemFn =
+            r#"#[cases_from("a.csv")] #[cases_from("b.csv")] fn test() {}"#.ast();
+        let cases_from = extract_cases_from(&mut item_fn).unwrap();
+
+        let (_, tracked) = CasesFromFiles {
+            base_dir: Box::new(FakeBaseDir),
+            reader: Box::new(FakeReader("a\n1\n")),
+        }
+        .to_cases(cases_from, &[ident("a")])
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                r#"const _ : & [u8] = include_bytes ! ("/base/a.csv") ;"#,
+                r#"const _ : & [u8] = include_bytes ! ("/base/b.csv") ;"#,
+            ],
+            tracked
+                .iter()
+                .map(|t| t.to_token_stream().to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::no_case_args("cases.csv", "a\n1\n", &[], "No #[case] arguments")]
+    #[case::unknown_format("cases.yaml", "a: 1", &["a"], "Unsupported cases file format")]
+    #[case::missed_column("cases.csv", "a,b\n1,2\n", &["a", "c"], "Missed column 'c' in the row 1")]
+    #[case::empty("cases.csv", "a,b\n", &["a"], "No cases found")]
+    #[case::invalid_json("cases.json", "[{", &["a"], "Invalid JSON file")]
+    #[case::not_a_json_array("cases.json", r#"{"a": 1}"#, &["a"], "array of objects")]
+    #[case::nested_json_value("cases.json", r#"[{"a": [1]}]"#, &["a"], "Unsupported value for 'a'")]
+    #[case::invalid_toml("cases.toml", "a = ", &["a"], "Invalid TOML file")]
+    #[case::toml_value("cases.toml", "a = 1", &["a"], "'a' should be a table")]
+    fn raise_error(
+        #[case] file: &str,
+        #[case] content: &'static str,
+        #[case] args: &[&str],
+        #[case] message: &str,
+    ) {
+        let err = cases(file, content, args).unwrap_err();
+
+        assert_in!(err, message);
+    }
+
+    #[test]
+    fn raise_error_if_attribute_has_no_string_literal() {
+        let mut item_fn: ItemFn = "#[cases_from(some::path)] fn test() {}".ast();
+
+        let err = extract_cases_from(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), "string literal");
+    }
+
+    #[rstest]
+    #[case("small case", Some("smallcase"))]
+    #[case("42", Some("_42"))]
+    #[case("  ", None)]
+    fn sanitize_the_description(#[case] name: &str, #[case] expected: Option<&str>) {
+        assert_eq!(
+            expected.map(ToOwned::to_owned),
+            case_description(name, Span::call_site()).map(|d| d.to_string())
+        );
+    }
+}
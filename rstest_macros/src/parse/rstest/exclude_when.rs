@@ -0,0 +1,357 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Attribute, BinOp, Expr, Ident, ItemFn, Lit, Type, UnOp};
+
+use crate::{
+    error::ErrorsVec,
+    refident::{MaybeIdent, MaybeType},
+    render::crate_resolver::crate_name,
+    resolver::Resolver,
+    utils::{attr_is, fn_args},
+};
+
+/// The `#[exclude_when(condition)]` attribute: skip the values' combinations where the
+/// condition is true.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExcludeWhen {
+    pub(crate) condition: Expr,
+    /// The test's arguments and their types: the condition can use them just when they are
+    /// resolved to a value.
+    args: Vec<(Ident, Type)>,
+}
+
+impl ExcludeWhen {
+    /// Evaluate the condition at compile time with the values given by `resolver`: `None`
+    /// if it's not possible.
+    ///
+    /// The condition can combine by `&&`, `||` and `!` the comparisons (`==` and `!=`) of
+    /// literals and paths. Two literals are compared just if they have the same kind and are
+    /// not floats, and a string value is not a literal if the argument is not a string (the
+    /// magic conversion parses it at runtime). Two paths are equal if they are the same path and are different
+    /// if they are two variants of the same enum (`Enum::Variant`, with camel case variants):
+    /// the other paths are not comparable at compile time (they can be two constants with
+    /// the same value), so their comparison is left to the runtime check.
+    pub(crate) fn evaluate(&self, resolver: &dyn Resolver) -> Option<bool> {
+        self.condition(&self.condition, resolver)
+    }
+
+    fn condition(&self, expr: &Expr, resolver: &dyn Resolver) -> Option<bool> {
+        match expr {
+            Expr::Paren(e) => self.condition(&e.expr, resolver),
+            Expr::Group(e) => self.condition(&e.expr, resolver),
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Bool(b), ..
+            }) => Some(b.value),
+            Expr::Unary(e) if matches!(e.op, UnOp::Not(_)) => {
+                self.condition(&e.expr, resolver).map(|b| !b)
+            }
+            Expr::Binary(e) => {
+                let left = || self.condition(&e.left, resolver);
+                let right = || self.condition(&e.right, resolver);
+                match e.op {
+                    BinOp::And(_) => match (left(), right()) {
+                        (Some(false), _) | (_, Some(false)) => Some(false),
+                        (Some(true), Some(true)) => Some(true),
+                        _ => None,
+                    },
+                    BinOp::Or(_) => match (left(), right()) {
+                        (Some(true), _) | (_, Some(true)) => Some(true),
+                        (Some(false), Some(false)) => Some(false),
+                        _ => None,
+                    },
+                    BinOp::Eq(_) => self.equal(&e.left, &e.right, resolver),
+                    BinOp::Ne(_) => self.equal(&e.left, &e.right, resolver).map(|eq| !eq),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn equal(&self, left: &Expr, right: &Expr, resolver: &dyn Resolver) -> Option<bool> {
+        match (self.value(left, resolver)?, self.value(right, resolver)?) {
+            (Constant::Lit(lk, l), Constant::Lit(rk, r)) if lk == rk => Some(l == r),
+            (Constant::Path(l), Constant::Path(r)) if l == r => Some(true),
+            (Constant::Path(l), Constant::Path(r)) => {
+                (is_variant(&l) && is_variant(&r) && l[..l.len() - 1] == r[..r.len() - 1])
+                    .then_some(false)
+            }
+            _ => None,
+        }
+    }
+
+    fn value(&self, expr: &Expr, resolver: &dyn Resolver) -> Option<Constant> {
+        match expr {
+            Expr::Path(p) => match p
+                .path
+                .get_ident()
+                .and_then(|ident| self.args.iter().find(|(arg, _)| arg == ident))
+            {
+                Some((arg, ty)) => match Constant::new(resolver.resolve(arg)?.as_ref())? {
+                    Constant::Lit(LitKind::Str, _) if !is_str(ty) => None,
+                    value => Some(value),
+                },
+                _ => Constant::new(expr),
+            },
+            _ => Constant::new(expr),
+        }
+    }
+
+    /// The runtime check for the combinations that are not known to be kept at compile
+    /// time: it should be rendered after the arguments' resolution and skips the test when
+    /// the condition is true.
+    pub(crate) fn render_check(&self) -> TokenStream {
+        let condition = &self.condition;
+        let rstest_path = crate_name();
+        quote! {
+            if #condition {
+                return #rstest_path::exclude::skip(stringify!(#condition));
+            }
+        }
+    }
+
+    /// The `#[ignore]` attribute of the tests excluded at compile time.
+    pub(crate) fn render_ignore<'a>(exclusions: impl IntoIterator<Item = &'a Self>) -> Attribute {
+        let reason = exclusions
+            .into_iter()
+            .map(|exclusion| format!("#[exclude_when({})]", exclusion.condition.to_token_stream()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let reason = format!("excluded by {reason}");
+        syn::parse_quote! { #[ignore = #reason] }
+    }
+}
+
+/// A value known at compile time.
+#[derive(Debug, PartialEq)]
+enum Constant {
+    Lit(LitKind, String),
+    Path(Vec<String>),
+}
+
+/// The kinds of the literals that can be compared at compile time.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum LitKind {
+    Str,
+    Char,
+    Int,
+    Bool,
+    Other,
+}
+
+impl Constant {
+    fn new(expr: &Expr) -> Option<Self> {
+        match expr {
+            Expr::Paren(e) => Self::new(&e.expr),
+            Expr::Group(e) => Self::new(&e.expr),
+            Expr::Lit(e) => literal(&e.lit).map(|(kind, value)| Self::Lit(kind, value)),
+            Expr::Unary(e) if matches!(e.op, UnOp::Neg(_)) => match Self::new(&e.expr)? {
+                Self::Lit(kind, value) => Some(Self::Lit(kind, format!("-{value}"))),
+                Self::Path(_) => None,
+            },
+            Expr::Path(p) if p.qself.is_none() => p
+                .path
+                .segments
+                .iter()
+                .map(|segment| {
+                    segment
+                        .arguments
+                        .is_none()
+                        .then(|| segment.ident.to_string())
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(Self::Path),
+            _ => None,
+        }
+    }
+}
+
+/// An enum variant path: `Enum::Variant` where the variant is in camel case and not a
+/// constant in upper case.
+fn is_variant(path: &[String]) -> bool {
+    match path {
+        [.., _, last] => {
+            last.starts_with(|c: char| c.is_ascii_uppercase())
+                && last.contains(|c: char| c.is_ascii_lowercase())
+        }
+        _ => false,
+    }
+}
+
+/// The literal's kind and value without the type suffix: `None` for the floats, whose
+/// different literals can be the same value (`1.0` and `1.00`).
+fn literal(lit: &Lit) -> Option<(LitKind, String)> {
+    Some(match lit {
+        Lit::Str(s) => (LitKind::Str, s.value()),
+        Lit::Char(c) => (LitKind::Char, c.value().to_string()),
+        Lit::Int(i) => (LitKind::Int, i.base10_digits().to_owned()),
+        Lit::Float(_) => return None,
+        Lit::Bool(b) => (LitKind::Bool, b.value.to_string()),
+        _ => (LitKind::Other, lit.to_token_stream().to_string()),
+    })
+}
+
+/// A string type: `&str` or `String`.
+fn is_str(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(r) => is_str(&r.elem),
+        Type::Group(g) => is_str(&g.elem),
+        Type::Paren(p) => is_str(&p.elem),
+        Type::Path(p) => p.path.segments.last().map_or(false, |segment| {
+            segment.ident == "str" || segment.ident == "String"
+        }),
+        _ => false,
+    }
+}
+
+impl TryFrom<(Attribute, &ItemFn)> for ExcludeWhen {
+    type Error = syn::Error;
+
+    fn try_from((attr, item_fn): (Attribute, &ItemFn)) -> Result<Self, Self::Error> {
+        let condition = attr.parse_args::<Expr>().map_err(|_| {
+            syn::Error::new_spanned(
+                &attr,
+                "Use #[exclude_when(condition)] with a bool expression",
+            )
+        })?;
+        Ok(Self {
+            condition,
+            args: fn_args(item_fn)
+                .filter_map(|arg| Some((arg.maybe_ident()?.clone(), arg.maybe_type()?.clone())))
+                .collect(),
+        })
+    }
+}
+
+impl ToTokens for ExcludeWhen {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.condition.to_tokens(tokens)
+    }
+}
+
+/// Entry point function to extract the `#[exclude_when(...)]` function attributes: it
+/// should be run before extracting the cases.
+pub(crate) fn extract_exclude_when(item_fn: &mut ItemFn) -> Result<Vec<ExcludeWhen>, ErrorsVec> {
+    let (exclude_when, remain): (Vec<_>, Vec<_>) = std::mem::take(&mut item_fn.attrs)
+        .into_iter()
+        .partition(|attr| attr_is(attr, "exclude_when"));
+    item_fn.attrs = remain;
+
+    let (exclude_when, errors): (Vec<_>, Vec<_>) = exclude_when
+        .into_iter()
+        .map(|attr| ExcludeWhen::try_from((attr, &*item_fn)))
+        .partition(Result::is_ok);
+    if errors.is_empty() {
+        Ok(exclude_when.into_iter().map(Result::unwrap).collect())
+    } else {
+        Err(errors
+            .into_iter()
+            .map(Result::unwrap_err)
+            .collect::<Vec<_>>()
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    fn exclude_when(condition: &str) -> ExcludeWhen {
+        let mut item_fn: ItemFn = format!(
+            "#[exclude_when({condition})] fn test(state: State, event: Event, n: u32, fix: u32, s: &str, x: f64) {{}}"
+        )
+        .ast();
+        extract_exclude_when(&mut item_fn).unwrap().pop().unwrap()
+    }
+
+    fn values(values: &[(&str, &str)]) -> HashMap<String, Expr> {
+        values
+            .iter()
+            .map(|&(arg, value)| (arg.to_owned(), value.ast()))
+            .collect()
+    }
+
+    #[rstest]
+    #[case::path_eq("state == State::Terminated", &[("state", "State::Terminated")], Some(true))]
+    #[case::path_ne("State::Terminated != state", &[("state", "State::Terminated")], Some(false))]
+    #[case::other_variant("state == State::Terminated", &[("state", "State::Running")], Some(false))]
+    #[case::other_variant_ne("state != State::Terminated", &[("state", "State::Running")], Some(true))]
+    #[case::other_const("n == Limits::MAX", &[("n", "Limits::MIN")], None)]
+    #[case::other_lowercase_item("f == module::first", &[("f", "module::second")], None)]
+    #[case::same_last_segment("state == Terminated", &[("state", "State::Terminated")], None)]
+    #[case::other_enum("state == Other::Terminated", &[("state", "State::Terminated")], None)]
+    #[case::different_consts("n == A", &[("n", "B")], None)]
+    #[case::int("n == 3", &[("n", "3u32")], Some(true))]
+    #[case::negative("n == -3", &[("n", "-3")], Some(true))]
+    #[case::str(r#"s == "a""#, &[("s", r#""a""#)], Some(true))]
+    #[case::other_str(r#"s != "a""#, &[("s", r#""b""#)], Some(true))]
+    #[case::different_kinds(r#"n == "3""#, &[("n", "3")], None)]
+    #[case::magic_conversion("n == 3", &[("n", r#""3""#)], None)]
+    #[case::magic_conversion_to_str_literal(r#"n == "3""#, &[("n", r#""3""#)], None)]
+    #[case::float("x == 1.0", &[("x", "1.00")], None)]
+    #[case::same_float("x == 1.0", &[("x", "1.0")], None)]
+    #[case::and(
+        "state == State::Terminated && event == Event::Fatal",
+        &[("state", "State::Terminated"), ("event", "Event::Fatal")],
+        Some(true)
+    )]
+    #[case::and_false_also_if_unknown(
+        "state != State::Running && event == Event::Fatal",
+        &[("state", "State::Running")],
+        Some(false)
+    )]
+    #[case::or_true_also_if_unknown(
+        "(state == State::Terminated) || !(event == Event::Fatal)",
+        &[("state", "State::Terminated")],
+        Some(true)
+    )]
+    #[case::not_resolved_arg("event == Event::Fatal", &[("state", "State::Running")], None)]
+    #[case::not_a_constant("n == 3", &[("n", "compute(3)")], None)]
+    #[case::not_a_comparison("n > 3", &[("n", "4")], None)]
+    #[case::fixture("fix == 3", &[("fix", "fix::default()")], None)]
+    fn evaluate_the_condition_at_compile_time(
+        #[case] condition: &str,
+        #[case] resolved: &[(&str, &str)],
+        #[case] expected: Option<bool>,
+    ) {
+        assert_eq!(
+            expected,
+            exclude_when(condition).evaluate(&values(resolved))
+        );
+    }
+
+    #[test]
+    fn extract_all_the_conditions() {
+        let mut item_fn: ItemFn = r#"
+            #[exclude_when(a == 1)]
+            #[other]
+            #[exclude_when(a == 2 || b)]
+            fn test(a: u32, b: bool) {}
+        "#
+        .ast();
+
+        let conditions = extract_exclude_when(&mut item_fn).unwrap();
+
+        assert_eq!(
+            vec!["a == 1", "a == 2 || b"],
+            conditions
+                .iter()
+                .map(|c| c.condition.display_code())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(item_fn, "#[other] fn test(a: u32, b: bool) {}".ast());
+    }
+
+    #[test]
+    fn reject_invalid_conditions() {
+        let mut item_fn: ItemFn = "#[exclude_when] fn test(a: u32) {}".ast();
+
+        let errors = extract_exclude_when(&mut item_fn).unwrap_err();
+
+        assert_in!(errors[0].to_string(), "Use #[exclude_when(condition)]");
+    }
+}
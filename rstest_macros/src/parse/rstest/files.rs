@@ -1,10 +1,17 @@
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
 use glob::glob;
-use quote::ToTokens;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
 use regex::Regex;
-use relative_path::RelativePath;
-use syn::{parse_quote, visit_mut::VisitMut, Attribute, Expr, FnArg, Ident, ItemFn, LitStr};
+use relative_path::{RelativePath, RelativePathBuf};
+use syn::{
+    parse::ParseStream, parse_quote, visit_mut::VisitMut, Attribute, Expr, FnArg, Ident, ItemFn,
+    LitStr, MetaNameValue, Token,
+};
 
 use crate::{
     error::ErrorsVec,
@@ -13,6 +20,7 @@ use crate::{
         vlist::{Value, ValueList},
     },
     refident::MaybeIdent,
+    render::crate_resolver::crate_name,
     utils::attr_is,
 };
 
@@ -21,6 +29,323 @@ pub(crate) struct FilesGlobReferences {
     glob: Vec<LitStrAttr>,
     exclude: Vec<Exclude>,
     ignore_dot_files: bool,
+    mode: FilesMode,
+    expected: Option<LitStr>,
+    kind: FilesKind,
+    base: Option<FilesBase>,
+    selection: FilesSelection,
+    runtime: bool,
+}
+
+/// Something read while expanding the macro: the generated tests use it, so the compiler
+/// rebuilds them when it changes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Tracked {
+    /// A file: the tests include its bytes
+    File(LitStr),
+    /// An environment variable: the tests read it by `option_env!`
+    Env(LitStr),
+}
+
+impl Tracked {
+    pub(crate) fn shard_env() -> Self {
+        Self::Env(LitStr::new(SHARD_ENV, proc_macro2::Span::call_site()))
+    }
+
+    pub(crate) fn bless_env() -> Self {
+        Self::Env(LitStr::new(BLESS_ENV, proc_macro2::Span::call_site()))
+    }
+}
+
+impl ToTokens for Tracked {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::File(path) => quote! { const _: &[u8] = include_bytes!(#path); },
+            Self::Env(name) => quote! { const _: Option<&str> = option_env!(#name); },
+        })
+    }
+}
+
+/// The environment variable that selects the shard to expand when `shards = N` is used.
+pub(crate) const SHARD_ENV: &str = "RSTEST_FILES_SHARD";
+
+/// The environment variable that enables the bless mode of `rstest::golden`: the expected
+/// file paths of `files_pair` can be missed just in this mode.
+pub(crate) const BLESS_ENV: &str = "RSTEST_BLESS";
+
+/// Take just a subset of the matched files: `limit = N`, `sample = N` (with an optional
+/// `seed = S`) and `shards = N` options.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct FilesSelection {
+    limit: Option<usize>,
+    sample: Option<usize>,
+    seed: Option<u64>,
+    shards: Option<usize>,
+}
+
+impl FilesSelection {
+    /// Select the items (identified by their relative paths) in the given shard, if any.
+    /// The chosen files depend just on their paths and so they don't change between runs
+    /// and machines; the items order is preserved.
+    fn select<T>(&self, items: Vec<(String, T)>, shard: Option<usize>) -> Vec<T> {
+        let mut items = items.into_iter().enumerate().collect::<Vec<_>>();
+        if let (Some(shards), Some(shard)) = (self.shards, shard) {
+            items.retain(|(_, (key, _))| stable_hash(0, key) % shards as u64 == shard as u64);
+        }
+        if let Some(sample) = self.sample {
+            let seed = self.seed.unwrap_or_default();
+            items.sort_by_cached_key(|(_, (key, _))| stable_hash(seed, key));
+            items.truncate(sample);
+            items.sort_by_key(|(pos, _)| *pos);
+        }
+        if let Some(limit) = self.limit {
+            items.truncate(limit);
+        }
+        items.into_iter().map(|(_, (_, item))| item).collect()
+    }
+}
+
+/// FNV-1a hash: unlike the std hashers it's stable between Rust versions.
+fn stable_hash(seed: u64, key: &str) -> u64 {
+    seed.to_le_bytes()
+        .iter()
+        .chain(key.as_bytes())
+        .fold(0xcbf29ce484222325, |hash, &b| {
+            (hash ^ b as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+/// Parse the `expand = "compile"|"runtime"` option's value: `true` for runtime.
+fn expand_at_runtime(value: &Expr) -> syn::Result<bool> {
+    match value {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) if s.value() == "compile" => Ok(false),
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) if s.value() == "runtime" && cfg!(feature = "files-runtime") => Ok(true),
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) if s.value() == "runtime" => Err(syn::Error::new_spanned(
+            s,
+            r#"Enable files-runtime feature to use expand = "runtime""#,
+        )),
+        e => Err(syn::Error::new_spanned(
+            e,
+            r#"Invalid expand: use expand = "compile" or expand = "runtime""#,
+        )),
+    }
+}
+
+/// Parse an integer option's value.
+fn int_option<N: std::str::FromStr>(option: &MetaNameValue) -> syn::Result<N>
+where
+    N::Err: std::fmt::Display,
+{
+    match &option.value {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(i),
+            ..
+        }) => i.base10_parse::<N>().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        syn::Error::new_spanned(
+            &option.value,
+            format!(
+                "Invalid {}: should be an integer",
+                option.path.to_token_stream()
+            ),
+        )
+    })
+}
+
+/// Parse a positive integer option's value.
+fn positive_int(option: &MetaNameValue) -> syn::Result<usize> {
+    match int_option(option)? {
+        0 => Err(syn::Error::new_spanned(
+            &option.value,
+            format!(
+                "Invalid {}: should be greater than 0",
+                option.path.to_token_stream()
+            ),
+        )),
+        n => Ok(n),
+    }
+}
+
+/// The folder where the globs are resolved: set by the `base = ...` option or by the
+/// `base` key in the `[package.metadata.rstest]` table of the crate manifest.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) enum FilesBase {
+    /// The crate root (`CARGO_MANIFEST_DIR`)
+    #[default]
+    Crate,
+    /// The workspace root
+    Workspace,
+    /// The build script output folder (`OUT_DIR`)
+    OutDir,
+    /// The folder in the given environment variable: relative paths are resolved from
+    /// the crate root
+    Env(String),
+}
+
+impl FilesBase {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "crate" => Some(Self::Crate),
+            "workspace" => Some(Self::Workspace),
+            "out_dir" => Some(Self::OutDir),
+            _ => None,
+        }
+    }
+
+    fn resolve(&self, dirs: &dyn BaseDir) -> Result<PathBuf, String> {
+        match self {
+            Self::Crate => dirs.base_dir(),
+            Self::Workspace => dirs.workspace_dir(),
+            Self::OutDir => dirs.var("OUT_DIR").map(PathBuf::from),
+            Self::Env(var) => Ok(dirs.base_dir()?.join(dirs.var(var)?)),
+        }
+    }
+}
+
+impl TryFrom<&Expr> for FilesBase {
+    type Error = syn::Error;
+
+    fn try_from(value: &Expr) -> Result<Self, Self::Error> {
+        let lit_str = |e: &Expr| match e {
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(s),
+                ..
+            }) => Some(s.value()),
+            _ => None,
+        };
+        let base = match value {
+            Expr::Call(call) if matches!(&*call.func, Expr::Path(p) if p.path.is_ident("env")) => {
+                match call.args.len() {
+                    1 => call.args.first().and_then(lit_str).map(Self::Env),
+                    _ => None,
+                }
+            }
+            e => lit_str(e).as_deref().and_then(Self::from_name),
+        };
+        base.ok_or_else(|| {
+            syn::Error::new_spanned(
+                value,
+                r#"Invalid base: use base = "crate", base = "workspace", base = "out_dir" or base = env("VAR")"#,
+            )
+        })
+    }
+}
+
+impl TryFrom<&toml::Value> for FilesBase {
+    type Error = String;
+
+    fn try_from(value: &toml::Value) -> Result<Self, Self::Error> {
+        let base = match value {
+            toml::Value::String(name) => Self::from_name(name),
+            toml::Value::Table(t) if t.len() == 1 => t
+                .get("env")
+                .and_then(|v| v.as_str())
+                .map(|v| Self::Env(v.to_owned())),
+            _ => None,
+        };
+        base.ok_or_else(|| {
+            format!(
+                r#"Invalid base `{value}` in [package.metadata.rstest]: use "crate", "workspace", "out_dir" or {{ env = "VAR" }}"#
+            )
+        })
+    }
+}
+
+/// Which kind of glob matches are used: set by `#[dirs(...)]` or the `kind = "..."` option.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum FilesKind {
+    #[default]
+    Any,
+    File,
+    Dir,
+    Symlink,
+}
+
+impl FilesKind {
+    fn matches(&self, path: &Path) -> bool {
+        match self {
+            Self::Any => true,
+            Self::File => path.is_file(),
+            Self::Dir => path.is_dir(),
+            Self::Symlink => path.is_symlink(),
+        }
+    }
+}
+
+impl TryFrom<&Expr> for FilesKind {
+    type Error = syn::Error;
+
+    fn try_from(value: &Expr) -> Result<Self, Self::Error> {
+        let kind = match value {
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(kind),
+                ..
+            }) => kind.value(),
+            _ => String::new(),
+        };
+        match kind.as_str() {
+            "file" => Ok(Self::File),
+            "dir" => Ok(Self::Dir),
+            "symlink" => Ok(Self::Symlink),
+            _ => Err(syn::Error::new_spanned(
+                value,
+                r#"Invalid kind: use kind = "file", kind = "dir" or kind = "symlink""#,
+            )),
+        }
+    }
+}
+
+/// What the argument receives for each file: set by the `#[mode = ...]` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum FilesMode {
+    /// The file's absolute path as `PathBuf`
+    #[default]
+    Path,
+    /// The file's content as `&'static str`
+    Str,
+    /// The file's content as `&'static [u8]`
+    Bytes,
+}
+
+impl FilesMode {
+    fn render(&self, path: &str) -> Expr {
+        match self {
+            Self::Path => parse_quote! {
+                <::std::path::PathBuf as std::str::FromStr>::from_str(#path).unwrap()
+            },
+            Self::Str => parse_quote! { include_str!(#path) },
+            Self::Bytes => parse_quote! { include_bytes!(#path) as &'static [u8] },
+        }
+    }
+}
+
+impl TryFrom<Attribute> for FilesMode {
+    type Error = syn::Error;
+
+    fn try_from(attr: Attribute) -> Result<Self, Self::Error> {
+        let error = || attr.error("Use #[mode = path], #[mode = str] or #[mode = bytes]");
+        let mode = match &attr.meta.require_name_value().map_err(|_| error())?.value {
+            Expr::Path(p) => p.path.get_ident().map(ToString::to_string),
+            _ => None,
+        };
+        match mode.as_deref() {
+            Some("path") => Ok(Self::Path),
+            Some("str") => Ok(Self::Str),
+            Some("bytes") => Ok(Self::Bytes),
+            _ => Err(error()),
+        }
+    }
 }
 
 impl FilesGlobReferences {
@@ -58,9 +383,60 @@ impl FilesGlobReferences {
             glob,
             exclude,
             ignore_dot_files,
+            mode: Default::default(),
+            expected: None,
+            kind: Default::default(),
+            base: None,
+            selection: Default::default(),
+            runtime: false,
         }
     }
 
+    fn with_runtime(mut self, runtime: bool) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    pub(crate) fn is_runtime(&self) -> bool {
+        self.runtime
+    }
+
+    /// The expansion depends on the `RSTEST_FILES_SHARD` environment variable.
+    pub(crate) fn is_sharded(&self) -> bool {
+        self.selection.shards.is_some()
+    }
+
+    /// The expansion depends on the `RSTEST_BLESS` environment variable: the expected
+    /// paths can be missed in bless mode.
+    pub(crate) fn is_blessable(&self) -> bool {
+        self.expected.is_some() && self.mode == FilesMode::Path
+    }
+
+    fn with_selection(mut self, selection: FilesSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    fn with_base(mut self, base: FilesBase) -> Self {
+        self.base = Some(base);
+        self
+    }
+
+    fn with_kind(mut self, kind: FilesKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    fn with_mode(mut self, mode: FilesMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    fn with_expected(mut self, template: LitStr) -> Self {
+        self.expected = Some(template);
+        self
+    }
+
     fn is_valid(&self, p: &RelativePath) -> bool {
         if self.ignore_dot_files
             && p.components()
@@ -85,6 +461,108 @@ impl LitStrAttr {
     }
 }
 
+/// Store the option's value and check that it's the same if it's used more than once.
+fn same_option<T: PartialEq>(
+    option: &MetaNameValue,
+    stored: &mut Option<T>,
+    value: T,
+) -> syn::Result<()> {
+    match stored {
+        Some(v) if v != &value => Err(syn::Error::new_spanned(
+            option,
+            format!(
+                "You cannot use different {}s for the same argument",
+                option.path.to_token_stream()
+            ),
+        )),
+        _ => {
+            *stored = Some(value);
+            Ok(())
+        }
+    }
+}
+
+/// A glob attribute: `#[files("glob", options...)]`, `#[dirs("glob", options...)]` or
+/// `#[files_pair("glob", "expected template", options...)]` where the options are in the
+/// `name = value` form.
+#[derive(Debug, Clone)]
+struct GlobAttr {
+    glob: LitStrAttr,
+    template: Option<LitStr>,
+    options: Vec<MetaNameValue>,
+}
+
+impl GlobAttr {
+    fn parse(attr: Attribute, pair: bool) -> syn::Result<Self> {
+        let (glob, template, options) = attr
+            .parse_args_with(|input: ParseStream| {
+                let glob = input.parse::<LitStr>()?;
+                let template = if pair {
+                    input.parse::<Token![,]>()?;
+                    Some(input.parse::<LitStr>()?)
+                } else {
+                    None
+                };
+                let mut options = vec![];
+                while !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                    if input.is_empty() {
+                        break;
+                    }
+                    options.push(input.parse::<MetaNameValue>()?);
+                }
+                Ok((glob, template, options))
+            })
+            .map_err(|e| match pair {
+                true => attr.error(r#"Use #[files_pair("glob", "expected path template")]: e.g. #[files_pair("inputs/*.txt", "expected/{stem}.out")]"#),
+                false => e,
+            })?;
+        Ok(Self {
+            glob: LitStrAttr { attr, value: glob },
+            template,
+            options,
+        })
+    }
+}
+
+/// Render the expected file path template for the given input file: the template can
+/// contain the `{name}`, `{stem}` and `{ext}` placeholders.
+fn render_expected(template: &str, input: &Path) -> Result<String, String> {
+    let part = |p: Option<&std::ffi::OsStr>| p.map(|p| p.to_string_lossy().into_owned());
+    let placeholders = [
+        ("name", part(input.file_name())),
+        ("stem", part(input.file_stem())),
+        ("ext", part(input.extension())),
+    ];
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("Unclosed placeholder in `{template}`"))?
+            + start;
+        let key = &rest[start + 1..end];
+        match placeholders.iter().find(|(k, _)| *k == key) {
+            Some((_, Some(value))) => rendered.push_str(value),
+            Some((_, None)) => {
+                return Err(format!(
+                    "Cannot resolve {{{key}}} for `{}`",
+                    input.display()
+                ))
+            }
+            None => {
+                return Err(format!(
+                    "Unknown placeholder {{{key}}}: use {{name}}, {{stem}} or {{ext}}"
+                ))
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
 impl TryFrom<Attribute> for LitStrAttr {
     type Error = syn::Error;
 
@@ -134,6 +612,14 @@ pub(crate) fn extract_files(
 ) -> Result<Vec<(Ident, FilesGlobReferences)>, ErrorsVec> {
     let mut extractor = ValueFilesExtractor::default();
     extractor.visit_item_fn_mut(item_fn);
+    if let Some(asyncness) = item_fn.sig.asyncness {
+        if extractor.files.iter().any(|(_, refs)| refs.is_runtime()) {
+            extractor.errors.push(syn::Error::new_spanned(
+                asyncness,
+                r#"You cannot use expand = "runtime" in async tests"#,
+            ));
+        }
+    }
     extractor.take()
 }
 
@@ -175,8 +661,104 @@ impl ValueFilesExtractor {
         )
     }
 
-    fn extract_files(&mut self, node: &mut FnArg) -> Vec<LitStrAttr> {
-        self.extract_argument_attrs(node, |a| attr_is(a, "files"), |attr, _| attr.try_into())
+    fn extract_files(&mut self, node: &mut FnArg) -> Vec<GlobAttr> {
+        self.extract_argument_attrs(
+            node,
+            |a| attr_is(a, "files"),
+            |attr, _| GlobAttr::parse(attr, false),
+        )
+    }
+
+    fn extract_dirs(&mut self, node: &mut FnArg) -> Vec<GlobAttr> {
+        self.extract_argument_attrs(
+            node,
+            |a| attr_is(a, "dirs"),
+            |attr, _| GlobAttr::parse(attr, false),
+        )
+    }
+
+    fn extract_files_pair(&mut self, node: &mut FnArg) -> Vec<GlobAttr> {
+        self.extract_argument_attrs(
+            node,
+            |a| attr_is(a, "files_pair"),
+            |attr, _| GlobAttr::parse(attr, true),
+        )
+    }
+
+    /// Apply the `name = value` options of the glob attributes
+    fn apply_options(
+        &mut self,
+        mut refs: FilesGlobReferences,
+        source: &str,
+        globs: &[GlobAttr],
+    ) -> FilesGlobReferences {
+        let mut kind = None;
+        let mut base = None;
+        let mut selection = FilesSelection::default();
+        let mut runtime = None;
+        for option in globs.iter().flat_map(|g| g.options.iter()) {
+            let name = option.path.to_token_stream().to_string();
+            let result = match name.as_str() {
+                "kind" if source == "dirs" => Err(syn::Error::new_spanned(
+                    option,
+                    "You cannot use the kind option in #[dirs(...)]",
+                )),
+                "kind" => FilesKind::try_from(&option.value)
+                    .and_then(|k| same_option(option, &mut kind, k)),
+                "base" => FilesBase::try_from(&option.value)
+                    .and_then(|b| same_option(option, &mut base, b)),
+                "limit" => positive_int(option)
+                    .and_then(|n| same_option(option, &mut selection.limit, n)),
+                "sample" => positive_int(option)
+                    .and_then(|n| same_option(option, &mut selection.sample, n)),
+                "shards" => positive_int(option)
+                    .and_then(|n| same_option(option, &mut selection.shards, n)),
+                "seed" => {
+                    int_option(option).and_then(|n| same_option(option, &mut selection.seed, n))
+                }
+                "expand" if source == "files_pair" => Err(syn::Error::new_spanned(
+                    option,
+                    "You cannot use the expand option in #[files_pair(...)]",
+                )),
+                "expand" => expand_at_runtime(&option.value)
+                    .and_then(|r| same_option(option, &mut runtime, r)),
+                _ => Err(syn::Error::new_spanned(
+                    &option.path,
+                    format!(
+                        "Unknown option `{name}`: the valid options are kind, base, limit, sample, seed, shards and expand"
+                    ),
+                )),
+            };
+            if let Err(e) = result {
+                self.errors.push(e)
+            }
+        }
+        if let Some(kind) = kind {
+            refs = refs.with_kind(kind);
+        }
+        if let Some(base) = base {
+            refs = refs.with_base(base);
+        }
+        if selection.seed.is_some() && selection.sample.is_none() {
+            if let Some(seed) = globs
+                .iter()
+                .flat_map(|g| g.options.iter())
+                .find(|o| o.path.is_ident("seed"))
+            {
+                self.errors.push(syn::Error::new_spanned(
+                    seed,
+                    "You cannot use the seed option without sample = N",
+                ));
+            }
+        }
+        let runtime = runtime.unwrap_or_default();
+        if runtime && selection != FilesSelection::default() {
+            self.errors.push(syn::Error::new_spanned(
+                &globs[0].glob,
+                r#"You cannot use limit, sample or shards with expand = "runtime""#,
+            ));
+        }
+        refs.with_selection(selection).with_runtime(runtime)
     }
 
     fn extract_exclude(&mut self, node: &mut FnArg) -> Vec<Exclude> {
@@ -187,6 +769,14 @@ impl ValueFilesExtractor {
         )
     }
 
+    fn extract_mode(&mut self, node: &mut FnArg) -> Vec<(Attribute, FilesMode)> {
+        self.extract_argument_attrs(
+            node,
+            |a| attr_is(a, "mode"),
+            |attr, _| FilesMode::try_from(attr.clone()).map(|mode| (attr, mode)),
+        )
+    }
+
     fn extract_include_dot_files(&mut self, node: &mut FnArg) -> Vec<Attribute> {
         self.extract_argument_attrs(
             node,
@@ -208,20 +798,57 @@ impl VisitMut for ValueFilesExtractor {
             return;
         }
         let name = name.unwrap();
-        let files = self.extract_files(node);
+        let sources = [
+            ("files", self.extract_files(node)),
+            ("dirs", self.extract_dirs(node)),
+            ("files_pair", self.extract_files_pair(node)),
+        ];
         let excludes = self.extract_exclude(node);
         let include_dot_files = self.extract_include_dot_files(node);
+        let modes = self.extract_mode(node);
         if !include_dot_files.is_empty() {
             include_dot_files.iter().skip(1).for_each(|attr| {
                 self.errors
                     .push(attr.error("Cannot use #[include_dot_files] more than once"))
             })
         }
-        if !files.is_empty() {
-            self.files.push((
-                name,
-                FilesGlobReferences::new(files, excludes, include_dot_files.is_empty()),
-            ))
+        modes.iter().skip(1).for_each(|(attr, _)| {
+            self.errors
+                .push(attr.error("Cannot use #[mode = ...] more than once"))
+        });
+        let mode = modes.first().map(|(_, m)| *m).unwrap_or_default();
+        let mut sources = sources.into_iter().filter(|(_, globs)| !globs.is_empty());
+        let source = sources.next();
+        for (other, globs) in sources {
+            let first = source.as_ref().map(|(s, _)| *s).unwrap_or_default();
+            globs.iter().for_each(|g| {
+                self.errors.push(g.glob.error(&format!(
+                    "You cannot use #[{first}(...)] and #[{other}(...)] on the same argument"
+                )))
+            });
+        }
+        if let Some((source, globs)) = source {
+            if source == "files_pair" {
+                globs.iter().skip(1).for_each(|g| {
+                    self.errors
+                        .push(g.glob.error("Cannot use #[files_pair(...)] more than once"))
+                });
+            }
+            let mut refs = FilesGlobReferences::new(
+                globs.iter().map(|g| g.glob.clone()).collect(),
+                excludes,
+                include_dot_files.is_empty(),
+            )
+            .with_mode(mode);
+            if let Some(template) = globs[0].template.clone() {
+                refs = refs.with_expected(template);
+                refs.glob.truncate(1);
+            }
+            if source == "dirs" {
+                refs = refs.with_kind(FilesKind::Dir);
+            }
+            let refs = self.apply_options(refs, source, &globs);
+            self.files.push((name, refs))
         } else {
             excludes.into_iter().for_each(|e| {
                 self.errors.push(
@@ -233,31 +860,78 @@ impl VisitMut for ValueFilesExtractor {
                 self.errors
                     .push(attr.error("You cannot use #[include_dot_files] without #[files(...)]"))
             });
+            modes.into_iter().for_each(|(attr, _)| {
+                self.errors
+                    .push(attr.error("You cannot use #[mode = ...] without #[files(...)]"))
+            });
         }
     }
 }
 
-trait BaseDir {
+pub(crate) trait BaseDir {
     fn base_dir(&self) -> Result<PathBuf, String> {
         env::var("CARGO_MANIFEST_DIR")
             .map(PathBuf::from)
             .map_err(|_|
-                "Rstest's #[files(...)] requires that CARGO_MANIFEST_DIR is defined to define glob the relative path".to_string()
+                "Rstest's #[files(...)] and #[cases_from(...)] require that CARGO_MANIFEST_DIR is defined to resolve the relative paths".to_string()
             )
     }
+
+    fn var(&self, name: &str) -> Result<String, String> {
+        env::var(name).map_err(|_| format!("The environment variable {name} is not defined"))
+    }
+
+    /// The nearest folder that contains a workspace manifest or the crate root if the
+    /// crate is not in a workspace.
+    fn workspace_dir(&self) -> Result<PathBuf, String> {
+        let base_dir = self.base_dir()?;
+        Ok(base_dir
+            .ancestors()
+            .find(|dir| {
+                manifest(dir)
+                    .map(|m| m.contains_key("workspace"))
+                    .unwrap_or_default()
+            })
+            .unwrap_or(&base_dir)
+            .to_owned())
+    }
+
+    /// The crate's default base folder for globs: the `base` key in the
+    /// `[package.metadata.rstest]` table.
+    fn default_base(&self) -> Result<FilesBase, String> {
+        manifest(&self.base_dir()?)
+            .as_ref()
+            .and_then(|m| {
+                m.get("package")?
+                    .get("metadata")?
+                    .get("rstest")?
+                    .get("base")
+            })
+            .map(FilesBase::try_from)
+            .unwrap_or(Ok(FilesBase::Crate))
+    }
+}
+
+/// Read and parse the `Cargo.toml` in the given folder, if any.
+fn manifest(dir: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()?
+        .parse()
+        .ok()
 }
 
-struct DefaultBaseDir;
+pub(crate) struct DefaultBaseDir;
 
 impl BaseDir for DefaultBaseDir {}
 
 trait GlobResolver {
-    fn glob(&self, pattern: &str) -> Result<Vec<PathBuf>, String> {
+    fn glob(&self, pattern: &str, kind: FilesKind) -> Result<Vec<PathBuf>, String> {
         let globs =
             glob(pattern).map_err(|e| format!("glob failed for whole path `{pattern}` due {e}"))?;
         globs
             .into_iter()
             .map(|p| p.map_err(|e| format!("glob failed for file due {e}")))
+            .filter(|r| r.as_ref().map(|p| kind.matches(p)).unwrap_or(true))
             .map(|r| {
                 r.and_then(|p| {
                     p.canonicalize()
@@ -266,10 +940,72 @@ trait GlobResolver {
             })
             .collect()
     }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
 }
 
 struct DefaultGlobResolver;
 
+/// A `#[files(...)]` argument expanded at runtime: the test resolves the globs and calls
+/// the test function for each file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RuntimeFiles {
+    pub(crate) arg: Ident,
+    files: Expr,
+    mode: FilesMode,
+}
+
+impl RuntimeFiles {
+    fn param(&self) -> Ident {
+        format_ident!("__rstest_file_{}", self.arg)
+    }
+
+    fn contents(&self) -> Ident {
+        format_ident!("__rstest_contents_{}", self.arg)
+    }
+
+    /// The expression that resolves the argument in the test body.
+    pub(crate) fn value(&self) -> Expr {
+        let param = self.param();
+        let contents = self.contents();
+        match self.mode {
+            FilesMode::Path => parse_quote! { #param.to_path_buf() },
+            FilesMode::Str => parse_quote! { #contents.as_str() },
+            FilesMode::Bytes => parse_quote! { #contents.as_slice() },
+        }
+    }
+
+    /// Wrap the test body to call it for each file.
+    pub(crate) fn render(&self, body: TokenStream) -> TokenStream {
+        let files = &self.files;
+        let param = self.param();
+        let contents = self.contents();
+        let read = match self.mode {
+            FilesMode::Path => None,
+            FilesMode::Str => Some(quote! {
+                let #contents = std::fs::read_to_string(#param).unwrap();
+            }),
+            FilesMode::Bytes => Some(quote! {
+                let #contents = std::fs::read(#param).unwrap();
+            }),
+        };
+        quote! {
+            #files.run(|#param: &std::path::Path| {
+                #read
+                #body
+            })
+        }
+    }
+}
+
+impl ToTokens for RuntimeFiles {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.arg.to_tokens(tokens)
+    }
+}
+
 impl GlobResolver for DefaultGlobResolver {}
 
 /// The struct used to gel te values from the files attributes. You can inject
@@ -302,10 +1038,54 @@ impl<'a> ValueListFromFiles<'a> {
             .collect::<Result<Vec<ValueList>, _>>()
     }
 
+    pub fn to_runtime_files(
+        &self,
+        files: Vec<(Ident, FilesGlobReferences)>,
+    ) -> Result<Vec<RuntimeFiles>, syn::Error> {
+        files
+            .into_iter()
+            .map(|(arg, refs)| {
+                self.runtime_files(&refs).map(|files| RuntimeFiles {
+                    arg,
+                    files,
+                    mode: refs.mode,
+                })
+            })
+            .collect()
+    }
+
+    fn runtime_files(&self, refs: &FilesGlobReferences) -> Result<Expr, syn::Error> {
+        let base_dir = self
+            .resolve_base_dir(refs)
+            .map_err(|msg| refs.glob[0].error(&msg))?;
+        let base_dir = base_dir.to_string_lossy();
+        let globs = refs.glob.iter().map(|g| &g.value);
+        let rstest_path = crate_name();
+        let mut files: Expr = parse_quote! {
+            #rstest_path::files::Files::new(#base_dir, &[#(#globs),*])
+        };
+        for exclude in &refs.exclude {
+            let regex = &exclude.attr.value;
+            files = parse_quote! { #files.exclude(#regex) };
+        }
+        if !refs.ignore_dot_files {
+            files = parse_quote! { #files.include_dot_files() };
+        }
+        let kind = match refs.kind {
+            FilesKind::Any => None,
+            FilesKind::File => Some(format_ident!("File")),
+            FilesKind::Dir => Some(format_ident!("Dir")),
+            FilesKind::Symlink => Some(format_ident!("Symlink")),
+        };
+        if let Some(kind) = kind {
+            files = parse_quote! { #files.kind(#rstest_path::files::Kind::#kind) };
+        }
+        Ok(files)
+    }
+
     fn file_list_values(&self, refs: FilesGlobReferences) -> Result<Vec<Value>, syn::Error> {
         let base_dir = self
-            .base_dir
-            .base_dir()
+            .resolve_base_dir(&refs)
             .map_err(|msg| refs.glob[0].error(&msg))?;
         let resolved_paths = refs.paths(&base_dir)?;
         let base_dir = base_dir
@@ -313,8 +1093,8 @@ impl<'a> ValueListFromFiles<'a> {
             .into_string()
             .map_err(|p| refs.glob[0].error(&format!("Cannot get a valid string from {p:?}")))?;
 
-        let mut values: Vec<(Expr, String)> = vec![];
-        for (attr, abs_path) in self.all_files_path(resolved_paths)? {
+        let mut files = vec![];
+        for (attr, abs_path) in self.all_files_path(resolved_paths, refs.kind)? {
             let relative_path = abs_path
                 .clone()
                 .into_os_string()
@@ -327,36 +1107,111 @@ impl<'a> ValueListFromFiles<'a> {
             if !refs.is_valid(&relative_path) {
                 continue;
             }
+            files.push((relative_path.to_string(), (attr, abs_path, relative_path)));
+        }
+
+        if files.is_empty() {
+            Err(refs.glob[0].error("No file found"))?;
+        }
+        // Number the files before the selection to keep the test names
+        let total = files.len();
+        let files = files
+            .into_iter()
+            .enumerate()
+            .map(|(n, (key, file))| (key, (n + 1, file)))
+            .collect();
 
+        let shard = self
+            .shard(&refs.selection)
+            .map_err(|msg| refs.glob[0].error(&msg))?;
+        let mut values: Vec<(Expr, RelativePathBuf, usize)> = vec![];
+        for (position, (attr, abs_path, relative_path)) in refs.selection.select(files, shard) {
             let path_str = abs_path.to_string_lossy();
-            values.push((
-                parse_quote! {
-                    <::std::path::PathBuf as std::str::FromStr>::from_str(#path_str).unwrap()
-                },
-                render_file_description(&relative_path),
-            ));
+            let mut value = refs.mode.render(&path_str);
+            if let Some(template) = &refs.expected {
+                let expected = render_expected(&template.value(), &abs_path)
+                    .map_err(|msg| attr.error(&msg))?;
+                let expected_path = RelativePath::from_path(&expected)
+                    .map_err(|e| attr.error(&format!("Invalid expected path: {e}")))?
+                    .to_logical_path(&base_dir);
+                // A missed expected file path is fine in bless mode: `golden::check()`
+                // creates it. Its contents cannot be included instead.
+                if !self.g_resolver.exists(&expected_path) {
+                    if refs.mode != FilesMode::Path {
+                        return Err(attr.error(&format!(
+                            "Missed expected file `{expected}` for `{relative_path}`"
+                        )));
+                    }
+                    if !self.is_bless() {
+                        return Err(attr.error(&format!(
+                            "Missed expected file `{expected}` for `{relative_path}`: run the tests with {BLESS_ENV}=1 to create it"
+                        )));
+                    }
+                }
+                let expected_value = refs.mode.render(&expected_path.to_string_lossy());
+                value = parse_quote! { (#value, #expected_value) };
+            }
+            values.push((value, relative_path, position));
         }
 
         if values.is_empty() {
-            Err(refs.glob[0].error("No file found"))?;
+            Err(refs.glob[0].error("No file selected in this shard"))?;
         }
 
         Ok(values
             .into_iter()
-            .map(|(e, desc)| Value::new(e, Some(desc)))
+            .map(|(e, path, position)| Value {
+                file: Some(path.to_string()),
+                position: Some((position, total)),
+                ..Value::new(e, Some(render_file_description(&path)))
+            })
             .collect())
     }
 
+    /// The bless mode is enabled by `RSTEST_BLESS` with any value except `0` or empty, like
+    /// `rstest::golden` does at runtime.
+    fn is_bless(&self) -> bool {
+        self.base_dir
+            .var(BLESS_ENV)
+            .map_or(false, |value| !matches!(value.trim(), "" | "0"))
+    }
+
+    /// The shard index from the `RSTEST_FILES_SHARD` environment variable: if it's not
+    /// defined all the shards are expanded.
+    fn shard(&self, selection: &FilesSelection) -> Result<Option<usize>, String> {
+        match (selection.shards, self.base_dir.var(SHARD_ENV)) {
+            (Some(shards), Ok(value)) => match value.trim().parse::<usize>() {
+                Ok(shard) if shard < shards => Ok(Some(shard)),
+                _ => Err(format!(
+                    "Invalid {SHARD_ENV}={value}: should be a shard index between 0 and {}",
+                    shards - 1
+                )),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn resolve_base_dir(&self, refs: &FilesGlobReferences) -> Result<PathBuf, String> {
+        match &refs.base {
+            Some(base) => base.resolve(self.base_dir.as_ref()),
+            None => self
+                .base_dir
+                .default_base()?
+                .resolve(self.base_dir.as_ref()),
+        }
+    }
+
     /// Return the tuples of attribute, file path resolved via glob resolver, sorted by path and without duplications.
     fn all_files_path<'b>(
         &self,
         resolved_paths: Vec<(&'b LitStrAttr, String)>,
+        kind: FilesKind,
     ) -> Result<Vec<(&'b LitStrAttr, PathBuf)>, syn::Error> {
         let mut paths = resolved_paths
             .iter()
             .map(|(attr, pattern)| {
                 self.g_resolver
-                    .glob(pattern.as_ref())
+                    .glob(pattern.as_ref(), kind)
                     .map_err(|msg| attr.error(&msg))
                     .map(|p| (attr, p))
             })
@@ -391,7 +1246,7 @@ mod should {
     use super::*;
     use crate::test::{assert_eq, *};
     use maplit::hashmap;
-    use rstest_test::assert_in;
+    use rstest_test::{assert_in, assert_not_in};
 
     fn lit_str_attr(name: &str, value: impl AsRef<str>) -> LitStrAttr {
         attrs(&format!(r#"#[{name}("{}")]"#, value.as_ref()))
@@ -477,6 +1332,26 @@ mod should {
         );
     }
 
+    #[rstest]
+    #[case::default(r#"fn f(#[files("some")] a: PathBuf) {}"#, FilesMode::Path)]
+    #[case::path(
+        r#"fn f(#[files("some")] #[mode = path] a: PathBuf) {}"#,
+        FilesMode::Path
+    )]
+    #[case::str(r#"fn f(#[files("some")] #[mode = str] a: &str) {}"#, FilesMode::Str)]
+    #[case::bytes(
+        r#"fn f(#[mode = bytes] #[files("some")] a: &[u8]) {}"#,
+        FilesMode::Bytes
+    )]
+    fn extract_mode(#[case] item_fn: &str, #[case] expected: FilesMode) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let files = extract_files(&mut item_fn).unwrap();
+
+        assert_eq!(expected, files[0].1.mode);
+        assert_not_in!(item_fn.display_code(), "mode");
+    }
+
     #[rstest]
     #[case::no_files_arg("fn f(#[files] a: PathBuf) {}", "#[files(...)]")]
     #[case::invalid_files_inner("fn f(#[files(a::b::c)] a: PathBuf) {}", "string literal")]
@@ -508,6 +1383,108 @@ mod should {
         r#"fn f(#[files("some")] #[include_dot_files] #[include_dot_files] a: PathBuf) {}"#,
         "more than once"
     )]
+    #[case::invalid_mode(
+        r#"fn f(#[files("some")] #[mode = string] a: PathBuf) {}"#,
+        "#[mode = str]"
+    )]
+    #[case::mode_as_list(
+        r#"fn f(#[files("some")] #[mode(str)] a: PathBuf) {}"#,
+        "#[mode = str]"
+    )]
+    #[case::mode_more_than_once(
+        r#"fn f(#[files("some")] #[mode = str] #[mode = bytes] a: PathBuf) {}"#,
+        "#[mode = ...] more than once"
+    )]
+    #[case::mode_without_files(
+        r#"fn f(#[mode = str] a: PathBuf) {}"#,
+        "#[mode = ...] without #[files(...)]"
+    )]
+    #[case::files_pair_with_one_arg(
+        r#"fn f(#[files_pair("in/*")] a: PathBuf) {}"#,
+        "expected path template"
+    )]
+    #[case::files_pair_with_three_args(
+        r#"fn f(#[files_pair("in/*", "a", "b")] a: PathBuf) {}"#,
+        "expected path template"
+    )]
+    #[case::files_pair_more_than_once(
+        r#"fn f(#[files_pair("in/*", "a")] #[files_pair("other/*", "b")] a: PathBuf) {}"#,
+        "#[files_pair(...)] more than once"
+    )]
+    #[case::files_and_dirs(
+        r#"fn f(#[files("other/*")] #[dirs("in/*")] a: PathBuf) {}"#,
+        "#[files(...)] and #[dirs(...)] on the same argument"
+    )]
+    #[case::unknown_option(
+        r#"fn f(#[files("other/*", other = 42)] a: PathBuf) {}"#,
+        "Unknown option `other`"
+    )]
+    #[case::invalid_kind(
+        r#"fn f(#[files("other/*", kind = "folder")] a: PathBuf) {}"#,
+        "Invalid kind"
+    )]
+    #[case::kind_not_a_string(
+        r#"fn f(#[files("other/*", kind = dir)] a: PathBuf) {}"#,
+        "Invalid kind"
+    )]
+    #[case::different_kinds(
+        r#"fn f(#[files("a/*", kind = "dir")] #[files("b/*", kind = "file")] a: PathBuf) {}"#,
+        "different kinds"
+    )]
+    #[case::kind_in_dirs(
+        r#"fn f(#[dirs("a/*", kind = "file")] a: PathBuf) {}"#,
+        "kind option in #[dirs(...)]"
+    )]
+    #[case::option_without_value(r#"fn f(#[files("a/*", kind)] a: PathBuf) {}"#, "expected `=`")]
+    #[case::zero_limit(r#"fn f(#[files("a/*", limit = 0)] a: PathBuf) {}"#, "greater than 0")]
+    #[case::limit_not_an_integer(
+        r#"fn f(#[files("a/*", limit = "10")] a: PathBuf) {}"#,
+        "Invalid limit"
+    )]
+    #[case::negative_shards(
+        r#"fn f(#[files("a/*", shards = -2)] a: PathBuf) {}"#,
+        "Invalid shards"
+    )]
+    #[case::seed_without_sample(
+        r#"fn f(#[files("a/*", seed = 42)] a: PathBuf) {}"#,
+        "seed option without sample"
+    )]
+    #[case::different_limits(
+        r#"fn f(#[files("a/*", limit = 2)] #[files("b/*", limit = 3)] a: PathBuf) {}"#,
+        "different limits"
+    )]
+    #[case::invalid_expand(
+        r#"fn f(#[files("a/*", expand = "lazy")] a: PathBuf) {}"#,
+        "Invalid expand"
+    )]
+    #[case::expand_in_files_pair(
+        r#"fn f(#[files_pair("a/*", "b/{name}", expand = "runtime")] a: (PathBuf, PathBuf)) {}"#,
+        "expand option in #[files_pair(...)]"
+    )]
+    #[case::runtime_with_limit(
+        r#"fn f(#[files("a/*", expand = "runtime", limit = 2)] a: PathBuf) {}"#,
+        "limit, sample or shards"
+    )]
+    #[case::runtime_in_async_test(
+        r#"async fn f(#[files("a/*", expand = "runtime")] a: PathBuf) {}"#,
+        "async tests"
+    )]
+    #[case::invalid_base(
+        r#"fn f(#[files("a/*", base = "root")] a: PathBuf) {}"#,
+        "Invalid base"
+    )]
+    #[case::env_base_without_var(
+        r#"fn f(#[files("a/*", base = env())] a: PathBuf) {}"#,
+        "Invalid base"
+    )]
+    #[case::different_bases(
+        r#"fn f(#[files("a/*", base = "workspace")] #[files("b/*")] #[files("c/*", base = "out_dir")] a: PathBuf) {}"#,
+        "different bases"
+    )]
+    #[case::files_and_files_pair(
+        r#"fn f(#[files("other/*")] #[files_pair("in/*", "a")] a: PathBuf) {}"#,
+        "#[files(...)] and #[files_pair(...)] on the same argument"
+    )]
     fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
         let mut item_fn: ItemFn = item_fn.ast();
 
@@ -516,6 +1493,17 @@ mod should {
         assert_in!(format!("{:?}", err), message);
     }
 
+    #[cfg(not(feature = "files-runtime"))]
+    #[test]
+    fn raise_error_for_runtime_expand_without_the_feature() {
+        let mut item_fn: ItemFn =
+            r#"fn f(#[files("a/*", expand = "runtime")] a: PathBuf) {}"#.ast();
+
+        let err = extract_files(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), "files-runtime feature");
+    }
+
     #[derive(Default)]
     struct FakeBaseDir(PathBuf);
     impl From<&str> for FakeBaseDir {
@@ -548,7 +1536,7 @@ mod should {
     }
 
     impl GlobResolver for FakeResolver {
-        fn glob(&self, _pattern: &str) -> Result<Vec<PathBuf>, String> {
+        fn glob(&self, _pattern: &str, _kind: FilesKind) -> Result<Vec<PathBuf>, String> {
             Ok(self.0.iter().map(PathBuf::from).collect())
         }
     }
@@ -578,10 +1566,14 @@ mod should {
     }
 
     impl GlobResolver for FakeMapResolver {
-        fn glob(&self, pattern: &str) -> Result<Vec<PathBuf>, String> {
+        fn glob(&self, pattern: &str, _kind: FilesKind) -> Result<Vec<PathBuf>, String> {
             let pattern = pattern.strip_prefix(&format!("{}/", self.0)).unwrap();
             Ok(self.1.get(pattern).cloned().unwrap_or_default())
         }
+
+        fn exists(&self, path: &Path) -> bool {
+            self.1.values().flatten().any(|p| p == path)
+        }
     }
 
     #[rstest]
@@ -652,14 +1644,547 @@ mod should {
             .values
             .iter_mut()
             .zip(expected.iter())
-            .for_each(|(v, &ex)| {
-                v.description = Some(render_file_description(
-                    &RelativePath::from_path(ex).unwrap(),
-                ))
+            .enumerate()
+            .for_each(|(n, (v, &ex))| {
+                let path = RelativePath::from_path(ex).unwrap();
+                v.description = Some(render_file_description(path));
+                v.file = Some(path.to_string());
+                v.position = Some((n + 1, expected.len()));
             });
         assert_eq!(vec![v_list], values);
     }
 
+    #[rstest]
+    #[case::files_default(r#"fn f(#[files("a/*")] a: PathBuf) {}"#, FilesKind::Any)]
+    #[case::files_file(
+        r#"fn f(#[files("a/*", kind = "file")] a: PathBuf) {}"#,
+        FilesKind::File
+    )]
+    #[case::files_dir(r#"fn f(#[files("a/*", kind = "dir")] a: PathBuf) {}"#, FilesKind::Dir)]
+    #[case::files_symlink(
+        r#"fn f(#[files("a/*", kind = "symlink",)] a: PathBuf) {}"#,
+        FilesKind::Symlink
+    )]
+    #[case::same_kind_more_times(
+        r#"fn f(#[files("a/*", kind = "file")] #[files("b/*", kind = "file")] a: PathBuf) {}"#,
+        FilesKind::File
+    )]
+    #[case::dirs(
+        r#"fn f(#[dirs("a/*")] #[exclude("b")] a: PathBuf) {}"#,
+        FilesKind::Dir
+    )]
+    #[case::files_pair(
+        r#"fn f(#[files_pair("a/*", "b/{name}", kind = "file")] a: (PathBuf, PathBuf)) {}"#,
+        FilesKind::File
+    )]
+    fn extract_kind(#[case] item_fn: &str, #[case] expected: FilesKind) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let files = extract_files(&mut item_fn).unwrap();
+
+        assert_eq!(1, files.len());
+        assert_eq!(expected, files[0].1.kind);
+    }
+
+    #[rstest]
+    #[case::default(r#"fn f(#[files("a/*")] a: PathBuf) {}"#, None)]
+    #[case::crate_root(
+        r#"fn f(#[files("a/*", base = "crate")] a: PathBuf) {}"#,
+        Some(FilesBase::Crate)
+    )]
+    #[case::workspace(
+        r#"fn f(#[files("a/*", base = "workspace")] a: PathBuf) {}"#,
+        Some(FilesBase::Workspace)
+    )]
+    #[case::out_dir(
+        r#"fn f(#[dirs("a/*", base = "out_dir")] a: PathBuf) {}"#,
+        Some(FilesBase::OutDir)
+    )]
+    #[case::env(
+        r#"fn f(#[files("a/*", kind = "file", base = env("DATA_DIR"))] a: PathBuf) {}"#,
+        Some(FilesBase::Env("DATA_DIR".to_owned()))
+    )]
+    fn extract_base(#[case] item_fn: &str, #[case] expected: Option<FilesBase>) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let files = extract_files(&mut item_fn).unwrap();
+
+        assert_eq!(expected, files[0].1.base);
+    }
+
+    #[rstest]
+    #[case::none(r#"fn f(#[files("a/*")] a: PathBuf) {}"#, FilesSelection::default())]
+    #[case::limit(
+        r#"fn f(#[files("a/*", limit = 10)] a: PathBuf) {}"#,
+        FilesSelection { limit: Some(10), ..Default::default() }
+    )]
+    #[case::sample(
+        r#"fn f(#[files("a/*", sample = 5, seed = 42)] #[files("b/*", sample = 5)] a: PathBuf) {}"#,
+        FilesSelection { sample: Some(5), seed: Some(42), ..Default::default() }
+    )]
+    #[case::shards(
+        r#"fn f(#[dirs("a/*", shards = 4, limit = 100)] a: PathBuf) {}"#,
+        FilesSelection { shards: Some(4), limit: Some(100), ..Default::default() }
+    )]
+    fn extract_selection(#[case] item_fn: &str, #[case] expected: FilesSelection) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let files = extract_files(&mut item_fn).unwrap();
+
+        assert_eq!(expected, files[0].1.selection);
+    }
+
+    fn items(n: usize) -> Vec<(String, usize)> {
+        (0..n).map(|i| (format!("corpus/{i}.bin"), i)).collect()
+    }
+
+    #[test]
+    fn select_the_first_files_by_limit() {
+        let selection = FilesSelection {
+            limit: Some(3),
+            ..Default::default()
+        };
+
+        assert_eq!(vec![0, 1, 2], selection.select(items(10), None));
+    }
+
+    #[rstest]
+    fn select_a_stable_sample(#[values(None, Some(42))] seed: Option<u64>) {
+        let selection = FilesSelection {
+            sample: Some(10),
+            seed,
+            ..Default::default()
+        };
+
+        let sample = selection.select(items(100), None);
+
+        assert_eq!(10, sample.len());
+        assert!(sample.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(sample, selection.select(items(100), None));
+        // Adding files doesn't change the already chosen ones a lot
+        let more = selection.select(items(110), None);
+        assert!(sample.iter().filter(|i| more.contains(i)).count() >= 8);
+    }
+
+    #[test]
+    fn select_different_samples_by_seed() {
+        let sample = |seed| {
+            FilesSelection {
+                sample: Some(10),
+                seed: Some(seed),
+                ..Default::default()
+            }
+            .select(items(100), None)
+        };
+
+        assert_ne!(sample(1), sample(2));
+    }
+
+    #[test]
+    fn select_all_files_once_in_shards() {
+        let selection = FilesSelection {
+            shards: Some(3),
+            ..Default::default()
+        };
+
+        let mut all = (0..3)
+            .flat_map(|shard| {
+                let selected = selection.select(items(30), Some(shard));
+                assert!(!selected.is_empty());
+                selected
+            })
+            .collect::<Vec<_>>();
+        all.sort();
+
+        assert_eq!((0..30).collect::<Vec<_>>(), all);
+        assert_eq!(
+            (0..30).collect::<Vec<_>>(),
+            selection.select(items(30), None)
+        );
+    }
+
+    #[rstest]
+    #[case::default(r#"fn f(#[files("a/*")] a: PathBuf) {}"#, false)]
+    #[case::compile(r#"fn f(#[files("a/*", expand = "compile")] a: PathBuf) {}"#, false)]
+    #[case::runtime(r#"fn f(#[files("a/*", expand = "runtime")] a: PathBuf) {}"#, true)]
+    #[case::runtime_dirs(r#"fn f(#[dirs("a/*", expand = "runtime")] a: PathBuf) {}"#, true)]
+    fn extract_expand(#[case] item_fn: &str, #[case] expected: bool) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let files = extract_files(&mut item_fn).unwrap();
+
+        assert_eq!(expected, files[0].1.is_runtime());
+    }
+
+    #[test]
+    fn render_the_runtime_files() {
+        let mut item_fn: ItemFn = r#"
+            fn f(
+                #[files("in/*.txt", base = "workspace", expand = "runtime")]
+                #[files("more/*.txt")]
+                #[exclude("skip")]
+                #[include_dot_files]
+                #[mode = str]
+                a: &str
+            ) {}
+        "#
+        .ast();
+        let files = extract_files(&mut item_fn).unwrap();
+
+        let runtime = ValueListFromFiles::new(fake_dirs(), FakeResolver::default())
+            .to_runtime_files(files)
+            .unwrap();
+
+        assert_eq!(
+            expr(
+                r#"
+                rstest::files::Files::new("/ws", &["in/*.txt", "more/*.txt"])
+                    .exclude("skip")
+                    .include_dot_files()
+                "#
+            ),
+            runtime[0].files
+        );
+        assert_eq!(expr("__rstest_contents_a.as_str()"), runtime[0].value());
+    }
+
+    #[derive(Default)]
+    struct FakeDirs {
+        root: PathBuf,
+        workspace: PathBuf,
+        vars: HashMap<String, String>,
+    }
+
+    impl BaseDir for FakeDirs {
+        fn base_dir(&self) -> Result<PathBuf, String> {
+            Ok(self.root.clone())
+        }
+
+        fn var(&self, name: &str) -> Result<String, String> {
+            self.vars
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("Missed {name}"))
+        }
+
+        fn workspace_dir(&self) -> Result<PathBuf, String> {
+            Ok(self.workspace.clone())
+        }
+    }
+
+    fn fake_dirs() -> FakeDirs {
+        FakeDirs {
+            root: PathBuf::from("/ws/crate"),
+            workspace: PathBuf::from("/ws"),
+            vars: hashmap!(
+                "OUT_DIR".to_owned() => "/target/out".to_owned(),
+                "ABS".to_owned() => "/data".to_owned(),
+                "REL".to_owned() => "../data".to_owned()
+            ),
+        }
+    }
+
+    #[rstest]
+    #[case::crate_root(FilesBase::Crate, "/ws/crate")]
+    #[case::workspace(FilesBase::Workspace, "/ws")]
+    #[case::out_dir(FilesBase::OutDir, "/target/out")]
+    #[case::env_absolute(FilesBase::Env("ABS".to_owned()), "/data")]
+    #[case::env_relative(FilesBase::Env("REL".to_owned()), "/ws/crate/../data")]
+    fn resolve_base(#[case] base: FilesBase, #[case] expected: &str) {
+        assert_eq!(PathBuf::from(expected), base.resolve(&fake_dirs()).unwrap());
+    }
+
+    fn shard_values(shard: &str) -> Result<Vec<Value>, syn::Error> {
+        let mut dirs = fake_dirs();
+        dirs.vars.insert(SHARD_ENV.to_owned(), shard.to_owned());
+        let files = (0..20)
+            .map(|i| format!("/ws/crate/{i}.txt"))
+            .collect::<Vec<_>>();
+        ValueListFromFiles::new(dirs, FakeResolver(files))
+            .to_value_list(vec![(
+                ident("a"),
+                FilesGlobReferences::new(vec![files_attr("no_mater")], Default::default(), true)
+                    .with_selection(FilesSelection {
+                        shards: Some(2),
+                        ..Default::default()
+                    }),
+            )])
+            .map(|mut v| v.remove(0).values)
+    }
+
+    #[test]
+    fn expand_the_shard_in_the_environment_variable() {
+        let first = shard_values("0").unwrap();
+        let second = shard_values("1").unwrap();
+
+        assert_eq!(20, first.len() + second.len());
+        assert!(first.iter().all(|v| !second.contains(v)));
+    }
+
+    #[test]
+    fn number_the_files_before_selecting_the_shard() {
+        let mut positions = shard_values("0")
+            .unwrap()
+            .into_iter()
+            .chain(shard_values("1").unwrap())
+            .map(|v| v.position.unwrap())
+            .collect::<Vec<_>>();
+        positions.sort();
+
+        assert_eq!((1..=20).map(|p| (p, 20)).collect::<Vec<_>>(), positions);
+    }
+
+    #[test]
+    fn track_the_shard_environment_variable() {
+        assert_eq!(
+            r#"const _ : Option < & str > = option_env ! ("RSTEST_FILES_SHARD") ;"#,
+            Tracked::shard_env().to_token_stream().to_string()
+        );
+    }
+
+    #[rstest]
+    #[case::out_of_range("2")]
+    #[case::not_a_number("first")]
+    fn raise_error_if_shard_is_invalid(#[case] shard: &str) {
+        let err = shard_values(shard).unwrap_err();
+
+        assert_in!(err.to_string(), "should be a shard index between 0 and 1");
+    }
+
+    #[test]
+    fn raise_error_if_base_var_is_not_defined() {
+        let err = FilesBase::Env("MISSED".to_owned())
+            .resolve(&fake_dirs())
+            .unwrap_err();
+
+        assert_in!(err, "Missed MISSED");
+    }
+
+    #[test]
+    fn resolve_globs_and_descriptions_from_base() {
+        let resolver =
+            FakeMapResolver::from(("/ws", &hashmap!("data/*" => ["data/first"].as_slice())));
+
+        let values = ValueListFromFiles::new(fake_dirs(), resolver)
+            .to_value_list(vec![(
+                ident("a"),
+                FilesGlobReferences::new(vec![files_attr("data/*")], Default::default(), true)
+                    .with_base(FilesBase::Workspace),
+            )])
+            .unwrap();
+
+        assert_eq!(
+            expr(
+                r#"<::std::path::PathBuf as std::str::FromStr>::from_str("/ws/data/first").unwrap()"#
+            ),
+            values[0].values[0].expr
+        );
+        assert_eq!(
+            Some("data/first".to_owned()),
+            values[0].values[0].description
+        );
+    }
+
+    #[rstest]
+    #[case::no_metadata("[package]\nname = \"a\"", FilesBase::Crate)]
+    #[case::name(
+        "[package.metadata.rstest]\nbase = \"workspace\"",
+        FilesBase::Workspace
+    )]
+    #[case::env(
+        "[package.metadata.rstest]\nbase = { env = \"DATA\" }",
+        FilesBase::Env("DATA".to_owned())
+    )]
+    fn read_default_base_from_manifest(#[case] manifest: &str, #[case] expected: FilesBase) {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        manifest.hash(&mut hasher);
+        let root = env::temp_dir().join(format!(
+            "rstest_files_base_{}_{:x}",
+            std::process::id(),
+            hasher.finish()
+        ));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("Cargo.toml"), manifest).unwrap();
+
+        let base = FakeBaseDir(root.clone()).default_base();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(expected, base.unwrap());
+    }
+
+    #[test]
+    fn raise_error_if_default_base_is_invalid() {
+        let err = FilesBase::try_from(&toml::Value::String("root".to_owned())).unwrap_err();
+
+        assert_in!(err, "[package.metadata.rstest]");
+    }
+
+    #[test]
+    fn extract_files_pair() {
+        let mut item_fn: ItemFn = r#"
+            fn f(#[files_pair("in/*.txt", "out/{stem}.out")] #[exclude("skip")] a: (PathBuf, PathBuf)) {}
+        "#
+        .ast();
+
+        let files = extract_files(&mut item_fn).unwrap();
+
+        assert_eq!(item_fn, "fn f(a: (PathBuf, PathBuf)) {}".ast());
+        assert_eq!(
+            vec!["in/*.txt"],
+            files[0]
+                .1
+                .glob
+                .iter()
+                .map(|g| g.value())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some("out/{stem}.out".to_owned()),
+            files[0].1.expected.as_ref().map(|t| t.value())
+        );
+        assert_eq!(1, files[0].1.exclude.len());
+    }
+
+    #[rstest]
+    #[case::name("expected/{name}", "expected/a.b.txt")]
+    #[case::stem("expected/{stem}.out", "expected/a.b.out")]
+    #[case::ext("{stem}_{ext}/x", "a.b_txt/x")]
+    #[case::no_placeholders("expected/all.out", "expected/all.out")]
+    fn render_the_expected_path(#[case] template: &str, #[case] expected: &str) {
+        assert_eq!(
+            expected,
+            render_expected(template, Path::new("/base/in/a.b.txt")).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::unknown("expected/{other}", "Unknown placeholder {other}")]
+    #[case::unclosed("expected/{stem", "Unclosed placeholder")]
+    #[case::no_extension("expected/{ext}", "Cannot resolve {ext}")]
+    fn raise_error_rendering_expected_path(#[case] template: &str, #[case] message: &str) {
+        let err = render_expected(template, Path::new("/base/in/name")).unwrap_err();
+
+        assert_in!(err, message);
+    }
+
+    fn pair_references(template: &str) -> FilesGlobReferences {
+        FilesGlobReferences::new(vec![files_attr("in/*.txt")], Default::default(), true)
+            .with_expected(LitStr::new(template, proc_macro2::Span::call_site()))
+    }
+
+    #[test]
+    fn render_the_input_and_expected_paths_pair() {
+        let resolver = FakeMapResolver::from((
+            "/base",
+            &hashmap!(
+                "in/*.txt" => ["in/a.txt"].as_slice(),
+                "out" => ["out/a.out"].as_slice()
+            ),
+        ));
+
+        let values = ValueListFromFiles::new(FakeBaseDir::from("/base"), resolver)
+            .to_value_list(vec![(ident("a"), pair_references("out/{stem}.out"))])
+            .unwrap();
+
+        assert_eq!(
+            expr(
+                r#"(
+                    <::std::path::PathBuf as std::str::FromStr>::from_str("/base/in/a.txt").unwrap(),
+                    <::std::path::PathBuf as std::str::FromStr>::from_str("/base/out/a.out").unwrap()
+                )"#
+            ),
+            values[0].values[0].expr
+        );
+        assert_eq!(Some("in/a.txt".to_owned()), values[0].values[0].description);
+    }
+
+    fn missed_expected_path(bless: Option<&str>) -> Result<Vec<Value>, syn::Error> {
+        let resolver =
+            FakeMapResolver::from(("/base", &hashmap!("in/*.txt" => ["in/a.txt"].as_slice())));
+        let mut dirs = FakeDirs {
+            root: PathBuf::from("/base"),
+            ..Default::default()
+        };
+        if let Some(bless) = bless {
+            dirs.vars.insert(BLESS_ENV.to_owned(), bless.to_owned());
+        }
+
+        ValueListFromFiles::new(dirs, resolver)
+            .to_value_list(vec![(ident("a"), pair_references("out/{stem}.out"))])
+            .map(|mut v| v.remove(0).values)
+    }
+
+    #[test]
+    fn render_the_missed_expected_path_to_bless_it() {
+        let values = missed_expected_path(Some("1")).unwrap();
+
+        assert_in!(
+            values[0].expr.display_code(),
+            r#"from_str ("/base/out/a.out")"#
+        );
+    }
+
+    #[rstest]
+    #[case::not_defined(None)]
+    #[case::empty(Some(""))]
+    #[case::zero(Some("0"))]
+    fn raise_error_if_the_missed_expected_path_is_not_blessed(#[case] bless: Option<&str>) {
+        let err = missed_expected_path(bless).unwrap_err();
+
+        assert_in!(
+            format!("{:?}", err),
+            "Missed expected file `out/a.out` for `in/a.txt`: run the tests with RSTEST_BLESS=1"
+        );
+    }
+
+    #[test]
+    fn track_the_bless_environment_variable() {
+        assert_eq!(
+            r#"const _ : Option < & str > = option_env ! ("RSTEST_BLESS") ;"#,
+            Tracked::bless_env().to_token_stream().to_string()
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "Missed expected file `out/a.out` for `in/a.txt`")]
+    fn raise_error_if_the_missed_expected_file_should_be_included(
+        #[values(FilesMode::Str, FilesMode::Bytes)] mode: FilesMode,
+    ) {
+        let resolver =
+            FakeMapResolver::from(("/base", &hashmap!("in/*.txt" => ["in/a.txt"].as_slice())));
+
+        ValueListFromFiles::new(FakeBaseDir::from("/base"), resolver)
+            .to_value_list(vec![(
+                ident("a"),
+                pair_references("out/{stem}.out").with_mode(mode),
+            )])
+            .unwrap();
+    }
+
+    #[rstest]
+    #[case::path(
+        FilesMode::Path,
+        r#"<::std::path::PathBuf as std::str::FromStr>::from_str("/base/first").unwrap()"#
+    )]
+    #[case::str(FilesMode::Str, r#"include_str!("/base/first")"#)]
+    #[case::bytes(FilesMode::Bytes, r#"include_bytes!("/base/first") as &'static [u8]"#)]
+    fn render_the_value_by_mode(#[case] mode: FilesMode, #[case] expected: &str) {
+        let values = ValueListFromFiles::new(
+            FakeBaseDir::from("/base"),
+            FakeResolver::from(["/base/first"].as_slice()),
+        )
+        .to_value_list(vec![(
+            ident("a"),
+            FilesGlobReferences::new(vec![files_attr("no_mater")], Default::default(), true)
+                .with_mode(mode),
+        )])
+        .unwrap();
+
+        assert_eq!(expr(expected), values[0].values[0].expr);
+        assert_eq!(Some("first".to_owned()), values[0].values[0].description);
+    }
+
     #[rstest]
     #[case::file("name.txt", "name.txt")]
     #[case::in_folder("some/folder/name.txt", "some/folder/name.txt")]
@@ -703,9 +2228,38 @@ mod should {
             .unwrap();
     }
 
+    #[test]
+    fn default_glob_resolver_filter_by_kind() {
+        let root = env::temp_dir().join(format!("rstest_files_kind_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("dir")).unwrap();
+        std::fs::write(root.join("file"), "").unwrap();
+        let pattern = format!("{}/*", root.display());
+        let names = |kind| {
+            DefaultGlobResolver
+                .glob(&pattern, kind)
+                .unwrap()
+                .into_iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+
+        let (any, files, dirs) = (
+            names(FilesKind::Any),
+            names(FilesKind::File),
+            names(FilesKind::Dir),
+        );
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(vec!["dir", "file"], any);
+        assert_eq!(vec!["file"], files);
+        assert_eq!(vec!["dir"], dirs);
+    }
+
     #[test]
     #[should_panic(expected = "glob failed")]
     fn default_glob_resolver_raise_error_if_invalid_glob_path() {
-        DefaultGlobResolver.glob("/invalid/path/***").unwrap();
+        DefaultGlobResolver
+            .glob("/invalid/path/***", Default::default())
+            .unwrap();
     }
 }
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, LitStr, Token,
+};
+
+use crate::utils::sanitize_ident;
+
+/// The placeholder for the case's description or number.
+pub(crate) const CASE_PLACEHOLDER: &str = "case";
+
+/// The `name = "template"` `#[rstest(...)]` argument: the tests' names are the template
+/// where the `{arg}` placeholders are replaced by the arguments' values.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NameTemplate {
+    template: LitStr,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    Placeholder(String),
+}
+
+impl NameTemplate {
+    fn error(&self, msg: impl std::fmt::Display) -> syn::Error {
+        syn::Error::new_spanned(&self.template, msg)
+    }
+
+    fn placeholders(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Placeholder(name) => Some(name.as_str()),
+            Part::Text(_) => None,
+        })
+    }
+
+    /// Check that all the placeholders are `{case}` or one of the `args` known at compile
+    /// time.
+    pub(crate) fn check(&self, args: &[&Ident]) -> Result<(), syn::Error> {
+        match self
+            .placeholders()
+            .find(|&p| p != CASE_PLACEHOLDER && !args.iter().any(|arg| *arg == p))
+        {
+            Some(unknown) => Err(self.error(format!(
+                "Unknown `{{{unknown}}}` in the test name template: use `{{{CASE_PLACEHOLDER}}}` or the #[case] and #[values] arguments"
+            ))),
+            None if args.is_empty() => Err(self.error(
+                "You can use a test name template just in tests with #[case] or #[values] arguments",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// The test name for the given placeholders' descriptions: a valid identifier. A name
+    /// that is a keyword is prefixed by the placeholders' names (`{flag}` is `flag_true`
+    /// instead of `true`).
+    ///
+    /// # Errors
+    ///
+    /// If the name is empty.
+    pub(crate) fn render(&self, descriptions: &HashMap<String, String>) -> syn::Result<String> {
+        let name = self
+            .parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => sanitize_ident(text),
+                Part::Placeholder(name) => descriptions
+                    .get(name)
+                    .map(|d| sanitize_ident(d).trim_matches('_').to_owned())
+                    .unwrap_or_default(),
+            })
+            .collect::<String>();
+        let name =
+            match name.chars().next() {
+                Some(c) if c.is_ascii_digit() => format!("_{name}"),
+                Some(_) => name,
+                None => return Err(self.error(
+                    "The test name template generates an empty name: add some text to the template",
+                )),
+            };
+        if syn::parse_str::<Ident>(&name).is_ok() {
+            return Ok(name);
+        }
+        Ok(self
+            .placeholders()
+            .chain(std::iter::once(name.as_str()))
+            .collect::<Vec<_>>()
+            .join("_"))
+    }
+
+    /// The error for the `name` generated by more than one test.
+    pub(crate) fn duplicated(&self, name: &str) -> syn::Error {
+        self.error(format!(
+            "The test name `{name}` is generated more than once: use more arguments in the name template"
+        ))
+    }
+}
+
+impl Parse for NameTemplate {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        if name != "name" {
+            return Err(syn::Error::new_spanned(name, "Expected `name`"));
+        }
+        let _eq: Token![=] = input.parse()?;
+        let template: LitStr = input.parse()?;
+        let mut parts = vec![];
+        let mut rest = template.value();
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .map(|end| start + end)
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(&template, "Unclosed `{` in the test name template")
+                })?;
+            if start > 0 {
+                parts.push(Part::Text(rest[..start].to_owned()));
+            }
+            parts.push(Part::Placeholder(rest[start + 1..end].trim().to_owned()));
+            rest = rest[end + 1..].to_owned();
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Text(rest));
+        }
+        Ok(Self { template, parts })
+    }
+}
+
+impl ToTokens for NameTemplate {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.template.to_tokens(tokens)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    fn template(s: &str) -> NameTemplate {
+        format!(r#"name = "{s}""#).ast()
+    }
+
+    fn descriptions(values: &[(&str, &str)]) -> HashMap<String, String> {
+        values
+            .iter()
+            .map(|&(k, v)| (k.to_owned(), v.to_owned()))
+            .collect()
+    }
+
+    #[rstest]
+    #[case::args("{from}_to_{to}", &[("from", "1"), ("to", "2")], "_1_to_2")]
+    #[case::strings("user_{name}", &[("name", r#""John""#)], "user_John")]
+    #[case::expressions("{value}", &[("value", "State::Running")], "State__Running")]
+    #[case::starts_with_a_digit("{n}_bits", &[("n", "8")], "_8_bits")]
+    #[case::text_sanitized("{a} plus-{b}", &[("a", "1"), ("b", "2")], "_1plus_2")]
+    #[case::case("{case}_{n}", &[("case", "small"), ("n", "1")], "small_1")]
+    #[case::keyword("{flag}", &[("flag", "true")], "flag_true")]
+    #[case::keyword_from_more_args("{a}{b}", &[("a", "lo"), ("b", "op")], "a_b_loop")]
+    #[case::underscore("{a}_", &[("a", "\"\"")], "a__")]
+    #[case::keyword_from_text("fn{a}", &[("a", "\"\"")], "a_fn")]
+    fn render_the_test_name(
+        #[case] t: &str,
+        #[case] values: &[(&str, &str)],
+        #[case] expected: &str,
+    ) {
+        assert_eq!(expected, template(t).render(&descriptions(values)).unwrap());
+    }
+
+    #[rstest]
+    #[case::empty_description("{a}", &[("a", "\"\"")])]
+    #[case::just_underscores("{a}", &[("a", "__")])]
+    fn reject_the_empty_test_name(#[case] t: &str, #[case] values: &[(&str, &str)]) {
+        let error = template(t).render(&descriptions(values)).unwrap_err();
+
+        assert_in!(error.to_string(), "generates an empty name");
+    }
+
+    fn check(t: &str, args: &[&str]) -> Result<(), syn::Error> {
+        let args = args.iter().map(|&arg| arg.ast()).collect::<Vec<Ident>>();
+        template(t).check(&args.iter().collect::<Vec<_>>())
+    }
+
+    #[rstest]
+    #[case::unknown("{a}_{c}", &["a", "b"], "Unknown `{c}`")]
+    #[case::no_args("plain", &[], "just in tests with #[case] or #[values]")]
+    fn reject_invalid_templates(#[case] t: &str, #[case] args: &[&str], #[case] message: &str) {
+        let error = check(t, args).unwrap_err();
+
+        assert_in!(error.to_string(), message);
+    }
+
+    #[test]
+    fn accept_the_case_placeholder() {
+        assert!(check("{case}_{a}", &["a"]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "Unclosed")]
+    fn reject_unclosed_placeholders() {
+        template("{a}_{b");
+    }
+}
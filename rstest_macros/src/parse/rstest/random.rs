@@ -0,0 +1,245 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::ParseStream, visit_mut::VisitMut, Attribute, Expr, Ident, ItemFn, MetaNameValue, Token,
+};
+
+use crate::{
+    error::ErrorsVec,
+    parse::just_once::{AttrBuilder, JustOnceFnArgAttributeExtractor, Validator},
+    render::crate_resolver::crate_name,
+};
+
+/// The `#[random(...)]` options.
+const OPTIONS: [&str; 2] = ["cases", "seed"];
+
+/// The maximum number of `#[random(...)]` arguments: the runtime implements the
+/// strategies' tuples up to this size.
+const MAX_RANDOM_ARGS: usize = 6;
+
+/// A `#[random(strategy, cases = n, seed = n)]` argument: the test draws its values by
+/// the strategy at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RandomArg {
+    pub(crate) arg: Ident,
+    pub(crate) strategy: Expr,
+    options: Vec<MetaNameValue>,
+}
+
+impl RandomArg {
+    fn param(&self) -> Ident {
+        format_ident!("__rstest_random_{}", self.arg)
+    }
+
+    /// The value of the `name` option, if any.
+    pub(crate) fn option(&self, name: &str) -> Option<&Expr> {
+        self.options
+            .iter()
+            .find(|option| option.path.is_ident(name))
+            .map(|option| &option.value)
+    }
+
+    /// The expression that resolves the argument in the test body.
+    pub(crate) fn value(&self) -> Expr {
+        let param = self.param();
+        syn::parse_quote! { #param }
+    }
+
+    fn parse(attr: Attribute, arg: &Ident) -> syn::Result<Self> {
+        let (strategy, options) = attr
+            .parse_args_with(|input: ParseStream| {
+                let strategy = input.parse::<Expr>()?;
+                let mut options = vec![];
+                while !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                    if input.is_empty() {
+                        break;
+                    }
+                    options.push(input.parse::<MetaNameValue>()?);
+                }
+                Ok((strategy, options))
+            })
+            .map_err(|_| {
+                syn::Error::new_spanned(
+                    &attr,
+                    "Use #[random(strategy)] or #[random(strategy, cases = n, seed = n)]",
+                )
+            })?;
+        for (i, option) in options.iter().enumerate() {
+            if !OPTIONS.iter().any(|name| option.path.is_ident(name)) {
+                return Err(syn::Error::new_spanned(
+                    &option.path,
+                    format!(
+                        "Unknown option `{}`: the valid options are cases and seed",
+                        option.path.to_token_stream()
+                    ),
+                ));
+            }
+            if options[..i].iter().any(|o| o.path == option.path) {
+                return Err(syn::Error::new_spanned(
+                    option,
+                    "You cannot use the same option twice",
+                ));
+            }
+        }
+        Ok(Self {
+            arg: arg.clone(),
+            strategy,
+            options,
+        })
+    }
+}
+
+impl ToTokens for RandomArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.arg.to_tokens(tokens)
+    }
+}
+
+/// Wrap the test body to call it for the values drawn by the `randoms` arguments'
+/// strategies: the cases and the seed can be given by just one of them.
+pub(crate) fn render(randoms: &[&RandomArg], body: TokenStream) -> TokenStream {
+    let rstest_path = crate_name();
+    let strategies = randoms.iter().map(|r| &r.strategy);
+    let params = randoms.iter().map(|r| r.param());
+    let names = randoms.iter().map(|r| r.arg.to_string());
+    let option = |value: Option<&Expr>| match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    };
+    let cases = option(randoms.iter().find_map(|r| r.option("cases")));
+    let seed = option(randoms.iter().find_map(|r| r.option("seed")));
+    quote! {
+        #rstest_path::random::check(
+            (#(#strategies,)*),
+            #cases,
+            #seed,
+            &[#(#names),*],
+            |(#(#params,)*)| {
+                #body
+            }
+        )
+    }
+}
+
+/// Entry point function to extract the `#[random(...)]` arguments
+pub(crate) fn extract_random(item_fn: &mut ItemFn) -> Result<Vec<RandomArg>, ErrorsVec> {
+    struct RandomBuilder;
+    impl AttrBuilder<Ident> for RandomBuilder {
+        type Out = RandomArg;
+
+        fn build(attr: Attribute, extra: &Ident) -> syn::Result<Self::Out> {
+            RandomArg::parse(attr, extra)
+        }
+    }
+    impl Validator<syn::FnArg> for RandomBuilder {}
+
+    let mut extractor = JustOnceFnArgAttributeExtractor::<RandomBuilder>::new("random");
+    extractor.visit_item_fn_mut(item_fn);
+    let randoms = extractor.take()?;
+
+    let mut errors = vec![];
+    if item_fn.sig.asyncness.is_some() {
+        errors.extend(
+            randoms.iter().map(|r| {
+                syn::Error::new_spanned(r, "You cannot use #[random(...)] in async tests")
+            }),
+        );
+    }
+    errors.extend(randoms.iter().skip(MAX_RANDOM_ARGS).map(|r| {
+        syn::Error::new_spanned(
+            r,
+            format!("You can use at most {MAX_RANDOM_ARGS} #[random(...)] arguments"),
+        )
+    }));
+    for option in OPTIONS {
+        errors.extend(
+            randoms
+                .iter()
+                .filter(|r| r.option(option).is_some())
+                .skip(1)
+                .map(|r| {
+                    syn::Error::new_spanned(
+                        r,
+                        format!(
+                            "You can give the {option} option in just one #[random(...)] argument"
+                        ),
+                    )
+                }),
+        );
+    }
+    if errors.is_empty() {
+        Ok(randoms)
+    } else {
+        Err(errors.into())
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[test]
+    fn extract_the_random_arguments() {
+        let mut item_fn: ItemFn = r#"
+            fn test(#[random(0..10u32, cases = 32)] a: u32, fix: u32, #[random(any::<bool>(), seed = 42)] b: bool) {}
+        "#
+        .ast();
+
+        let randoms = extract_random(&mut item_fn).unwrap();
+
+        let described = randoms
+            .iter()
+            .map(|r| {
+                format!(
+                    "{}: {} {:?} {:?}",
+                    r.arg,
+                    r.strategy.display_code(),
+                    r.option("cases").map(|c| c.display_code()),
+                    r.option("seed").map(|s| s.display_code())
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                r#"a: 0 .. 10u32 Some("32") None"#,
+                r#"b: any :: < bool > () None Some("42")"#
+            ],
+            described
+        );
+        assert_eq!(item_fn, "fn test(a: u32, fix: u32, b: bool) {}".ast());
+    }
+
+    #[rstest]
+    #[case::no_strategy("fn test(#[random] a: u32) {}", "Use #[random(strategy)]")]
+    #[case::unknown_option(
+        "fn test(#[random(0..10, size = 3)] a: u32) {}",
+        "Unknown option `size`"
+    )]
+    #[case::same_option_twice(
+        "fn test(#[random(0..10, cases = 3, cases = 4)] a: u32) {}",
+        "same option twice"
+    )]
+    #[case::cases_in_more_arguments(
+        "fn test(#[random(0..10, cases = 3)] a: u32, #[random(0..10, cases = 3)] b: u32) {}",
+        "cases option in just one"
+    )]
+    #[case::async_test("async fn test(#[random(0..10)] a: u32) {}", "in async tests")]
+    #[case::too_many_arguments(
+        r#"fn test(
+            #[random(0..2)] a: u32, #[random(0..2)] b: u32, #[random(0..2)] c: u32,
+            #[random(0..2)] d: u32, #[random(0..2)] e: u32, #[random(0..2)] f: u32,
+            #[random(0..2)] g: u32
+        ) {}"#,
+        "at most 6 #[random(...)] arguments"
+    )]
+    fn reject_invalid_attributes(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let errors = extract_random(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{errors:?}"), message);
+    }
+}
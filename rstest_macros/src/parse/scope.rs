@@ -0,0 +1,96 @@
+use syn::{visit_mut::VisitMut, Attribute, Ident, ItemFn};
+
+use crate::error::ErrorsVec;
+
+use super::just_once::{AttrBuilder, JustOnceFnAttributeExtractor, Validator};
+
+/// Where a fixture's value is shared.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(crate) enum ScopeKind {
+    /// All the cases and matrix cells generated by the same `#[rstest]` function
+    Group,
+    /// All the tests in the same module
+    Module,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct FixtureScope {
+    pub(crate) attr: Attribute,
+    pub(crate) kind: ScopeKind,
+}
+
+pub(crate) fn extract_scope(item_fn: &mut ItemFn) -> Result<Option<FixtureScope>, ErrorsVec> {
+    let mut extractor = JustOnceFnAttributeExtractor::<ScopeBuilder>::new("scope");
+
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+struct ScopeBuilder;
+
+impl AttrBuilder<ItemFn> for ScopeBuilder {
+    type Out = FixtureScope;
+
+    fn build(attr: Attribute, _item_fn: &ItemFn) -> syn::Result<Self::Out> {
+        let kind = attr
+            .parse_args::<Ident>()
+            .ok()
+            .and_then(|kind| match kind.to_string().as_str() {
+                "group" => Some(ScopeKind::Group),
+                "module" => Some(ScopeKind::Module),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                syn::Error::new_spanned(&attr, "Use #[scope(group)] or #[scope(module)].")
+            })?;
+        Ok(FixtureScope { attr, kind })
+    }
+}
+
+impl Validator<ItemFn> for ScopeBuilder {}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[test]
+    fn not_change_anything_if_no_scope_attribute_found() {
+        let mut item_fn: ItemFn = "#[once] fn f() -> u32 { 42 }".ast();
+        let orig = item_fn.clone();
+
+        let scope = extract_scope(&mut item_fn).unwrap();
+
+        assert_eq!(orig, item_fn);
+        assert!(scope.is_none());
+    }
+
+    #[rstest]
+    #[case("group", ScopeKind::Group)]
+    #[case("module", ScopeKind::Module)]
+    fn extract(#[case] kind: &str, #[case] expected: ScopeKind) {
+        let mut item_fn: ItemFn = format!("#[scope({kind})] fn f() -> u32 {{ 42 }}").ast();
+
+        let scope = extract_scope(&mut item_fn).unwrap().unwrap();
+
+        assert_eq!(item_fn, "fn f() -> u32 { 42 }".ast());
+        assert_eq!(expected, scope.kind);
+    }
+
+    #[rstest]
+    #[case::no_more_than_one("#[scope(group)] #[scope(module)] fn f() {}", "more than once")]
+    #[case::no_kind("#[scope] fn f() {}", "#[scope(group)] or #[scope(module)]")]
+    #[case::unknown_kind("#[scope(crate)] fn f() {}", "#[scope(group)] or #[scope(module)]")]
+    #[case::more_kinds(
+        "#[scope(group, module)] fn f() {}",
+        "#[scope(group)] or #[scope(module)]"
+    )]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let err = extract_scope(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
@@ -0,0 +1,130 @@
+use quote::ToTokens;
+use syn::{visit_mut::VisitMut, Attribute, Expr, ItemFn, ReturnType, Type};
+
+use crate::error::ErrorsVec;
+
+use super::just_once::{AttrBuilder, JustOnceFnAttributeExtractor, Validator};
+
+/// How the fixture should release its resources.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) enum TearDown {
+    /// `#[teardown]`: the fixture returns a `(value, guard)` tuple.
+    Guard(Attribute),
+    /// `#[teardown(expr)]`: `expr` is called with a reference to the fixture value and
+    /// returns the guard.
+    Expr(Attribute, Expr),
+}
+
+impl TearDown {
+    pub(crate) fn attr(&self) -> &Attribute {
+        match self {
+            TearDown::Guard(attr) | TearDown::Expr(attr, _) => attr,
+        }
+    }
+}
+
+pub(crate) fn extract_teardown(item_fn: &mut ItemFn) -> Result<Option<TearDown>, ErrorsVec> {
+    let mut extractor = JustOnceFnAttributeExtractor::<TearDownBuilder>::new("teardown");
+
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+/// Return the type of the value if the fixture return type is a `(value, guard)` tuple.
+pub(crate) fn guarded_value_type(rt: &ReturnType) -> Option<&Type> {
+    match rt {
+        ReturnType::Type(_, t) => match t.as_ref() {
+            Type::Tuple(tuple) if tuple.elems.len() == 2 => tuple.elems.first(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+struct TearDownBuilder;
+
+impl AttrBuilder<ItemFn> for TearDownBuilder {
+    type Out = TearDown;
+
+    fn build(attr: Attribute, item_fn: &ItemFn) -> syn::Result<Self::Out> {
+        match &attr.meta {
+            syn::Meta::Path(_) => match guarded_value_type(&item_fn.sig.output) {
+                Some(_) => Ok(TearDown::Guard(attr)),
+                None => Err(syn::Error::new_spanned(
+                    match &item_fn.sig.output {
+                        ReturnType::Default => attr.to_token_stream(),
+                        rt => rt.to_token_stream(),
+                    },
+                    "A #[teardown] fixture without expression should return a (value, guard) tuple.",
+                )),
+            },
+            syn::Meta::List(_) => attr
+                .parse_args::<Expr>()
+                .map(|expr| TearDown::Expr(attr, expr)),
+            syn::Meta::NameValue(_) => Err(syn::Error::new_spanned(
+                attr,
+                "Use #[teardown] or #[teardown(expression)].",
+            )),
+        }
+    }
+}
+
+impl Validator<ItemFn> for TearDownBuilder {}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case("fn simple() -> u32 { 42 }")]
+    #[case("#[once] fn once() -> u32 { 42 }")]
+    fn not_change_anything_if_no_teardown_attribute_found(#[case] item_fn: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+        let orig = item_fn.clone();
+
+        let teardown = extract_teardown(&mut item_fn).unwrap();
+
+        assert_eq!(orig, item_fn);
+        assert!(teardown.is_none());
+    }
+
+    #[test]
+    fn extract_guard_form() {
+        let mut item_fn: ItemFn = "#[teardown] fn f() -> (u32, Guard) {}".ast();
+
+        let teardown = extract_teardown(&mut item_fn).unwrap().unwrap();
+
+        assert_eq!(item_fn, "fn f() -> (u32, Guard) {}".ast());
+        assert_eq!(TearDown::Guard(attrs("#[teardown]").remove(0)), teardown);
+    }
+
+    #[test]
+    fn extract_expression_form() {
+        let mut item_fn: ItemFn = "#[teardown(|v| drop(v))] fn f() -> u32 {}".ast();
+
+        let teardown = extract_teardown(&mut item_fn).unwrap().unwrap();
+
+        assert_eq!(item_fn, "fn f() -> u32 {}".ast());
+        match teardown {
+            TearDown::Expr(_, e) => assert_eq!(e, expr("|v| drop(v)")),
+            _ => panic!("Should be an expression"),
+        }
+    }
+
+    #[rstest]
+    #[case::no_more_than_one("#[teardown] #[teardown] fn f() -> (u32, G) {}", "more than once")]
+    #[case::guard_without_tuple("#[teardown] fn f() -> u32 {}", "(value, guard) tuple")]
+    #[case::guard_without_return("#[teardown] fn f() {}", "(value, guard) tuple")]
+    #[case::guard_with_wrong_tuple("#[teardown] fn f() -> (u32, G, H) {}", "(value, guard) tuple")]
+    #[case::name_value("#[teardown = 42] fn f() -> u32 {}", "#[teardown(expression)]")]
+    #[case::invalid_expression("#[teardown(let)] fn f() -> u32 {}", "unexpected end of input")]
+    fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = item_fn.ast();
+
+        let err = extract_teardown(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", err), message);
+    }
+}
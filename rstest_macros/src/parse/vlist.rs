@@ -1,11 +1,14 @@
-use proc_macro2::TokenStream;
-use quote::ToTokens;
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream, Result},
-    Expr, Ident, Token,
+    parse_quote, Attribute, Expr, Ident, LitInt, Path, RangeLimits, Token, UnOp,
 };
 
-use crate::refident::RefIdent;
+use crate::{
+    refident::RefIdent,
+    utils::{attr_is, sanitize_ident},
+};
 
 use super::expressions::Expressions;
 
@@ -13,11 +16,25 @@ use super::expressions::Expressions;
 pub(crate) struct Value {
     pub(crate) expr: Expr,
     pub(crate) description: Option<String>,
+    /// The value's `#[tag(...)]` attributes
+    pub(crate) attrs: Vec<Attribute>,
+    /// The `#[files(...)]` matched file path relative to the glob's base folder
+    pub(crate) file: Option<String>,
+    /// The 1-based position of the value and the size of the list that it was selected
+    /// from: if defined, the test name uses them in place of the value's position, so it
+    /// doesn't change when some values are filtered out.
+    pub(crate) position: Option<(usize, usize)>,
 }
 
 impl Value {
     pub(crate) fn new(expr: Expr, description: Option<String>) -> Self {
-        Self { expr, description }
+        Self {
+            expr,
+            description,
+            attrs: vec![],
+            file: None,
+            position: None,
+        }
     }
 
     pub(crate) fn description(&self) -> String {
@@ -27,6 +44,32 @@ impl Value {
     }
 }
 
+/// A value or a named one: `name => expression`. The name is the value's description. The
+/// value can be tagged by `#[tag(...)]` attributes.
+impl Parse for Value {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        for attr in attrs.iter() {
+            if !attr_is(attr, "tag") {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "You can use just #[tag(...)] attributes in the values",
+                ));
+            }
+            super::parse_tags(attr)?;
+        }
+        let mut value: Self = if input.peek(Ident) && input.peek2(Token![=>]) {
+            let name: Ident = input.parse()?;
+            let _to: Token![=>] = input.parse()?;
+            Self::new(input.parse()?, Some(name.to_string()))
+        } else {
+            input.parse::<Expr>()?.into()
+        };
+        value.attrs = attrs;
+        Ok(value)
+    }
+}
+
 impl From<Expr> for Value {
     fn from(expr: Expr) -> Self {
         Self::new(expr, None)
@@ -74,6 +117,217 @@ impl ToTokens for ValueList {
     }
 }
 
+mod kw {
+    syn::custom_keyword!(from);
+    syn::custom_keyword!(len);
+    syn::custom_keyword!(range);
+    syn::custom_keyword!(step);
+}
+
+/// The maximum number of values that a `range = ...` can expand to.
+pub(crate) const MAX_RANGE_VALUES: u128 = 1024;
+
+/// The `#[values(...)]` attribute's content: a list of values (also named ones), an
+/// integer range (`range = 0..8` or `range = 0..=255; step = 17`) expanded at compile time
+/// or the elements of a constant array or slice with the given length
+/// (`from = MY_CONST; len = 4`).
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum ValuesAttr {
+    List(Vec<Value>),
+    FromConst(Path, LitInt),
+}
+
+impl Parse for ValuesAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(kw::from) && input.peek2(Token![=]) {
+            let _from: kw::from = input.parse()?;
+            let _eq: Token![=] = input.parse()?;
+            let path: Path = input.parse()?;
+            let len = option::<kw::len>(input)?.ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &path,
+                    "Add the constant's length: #[values(from = MY_CONST; len = 4)]",
+                )
+            })?;
+            return Ok(Self::FromConst(path, len));
+        }
+        if input.peek(kw::range) && input.peek2(Token![=]) {
+            let _range: kw::range = input.parse()?;
+            let _eq: Token![=] = input.parse()?;
+            let expr: Expr = input.parse()?;
+            let range = IntRange::new(&expr).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &expr,
+                    "Use range = with a range of integer literals like 0..=255",
+                )
+            })?;
+            let step = match option::<kw::step>(input)? {
+                Some(step) => step
+                    .base10_parse::<u64>()
+                    .ok()
+                    .filter(|&s| s > 0)
+                    .ok_or_else(|| {
+                        syn::Error::new_spanned(&step, "The step should be a positive integer")
+                    })?,
+                None => 1,
+            };
+            let count = range.count(step);
+            if count > MAX_RANGE_VALUES {
+                return Err(syn::Error::new_spanned(
+                    &expr,
+                    format!(
+                        "The range expands to {count} values: the maximum is {MAX_RANGE_VALUES}"
+                    ),
+                ));
+            }
+            return Ok(Self::List(range.expand(step)));
+        }
+        Ok(Self::List(
+            input
+                .parse_terminated(Value::parse, Token![,])?
+                .into_iter()
+                .collect(),
+        ))
+    }
+}
+
+/// Parse an optional `; name = <integer>` option.
+fn option<K: Parse + syn::token::Token>(input: ParseStream) -> Result<Option<LitInt>> {
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let _semi: Token![;] = input.parse()?;
+    let _name: K = input.parse()?;
+    let _eq: Token![=] = input.parse()?;
+    input.parse().map(Some)
+}
+
+/// A range with integer literal bounds.
+struct IntRange {
+    start: i128,
+    end: i128,
+    suffix: String,
+    span: Span,
+}
+
+impl IntRange {
+    fn new(expr: &Expr) -> Option<Self> {
+        match expr {
+            Expr::Range(range) => {
+                let (start, start_suffix) = int_literal(range.start.as_deref()?)?;
+                let (end, end_suffix) = int_literal(range.end.as_deref()?)?;
+                let end = match range.limits {
+                    RangeLimits::HalfOpen(_) => end - 1,
+                    RangeLimits::Closed(_) => end,
+                };
+                let suffix = if start_suffix.is_empty() {
+                    end_suffix
+                } else {
+                    start_suffix
+                };
+                Some(Self {
+                    start,
+                    end,
+                    suffix,
+                    span: syn::spanned::Spanned::span(range),
+                })
+            }
+            Expr::Paren(e) => Self::new(&e.expr),
+            _ => None,
+        }
+    }
+
+    /// The number of values taken by the given `step`.
+    fn count(&self, step: u64) -> u128 {
+        match self.end.checked_sub(self.start) {
+            Some(width) if width >= 0 => width as u128 / step as u128 + 1,
+            _ => 0,
+        }
+    }
+
+    fn expand(&self, step: u64) -> Vec<Value> {
+        let mut values = vec![];
+        let mut value = self.start;
+        while value <= self.end {
+            let lit = LitInt::new(
+                &format!("{}{}", value.unsigned_abs(), self.suffix),
+                self.span,
+            );
+            let expr: Expr = if value < 0 {
+                parse_quote! { -#lit }
+            } else {
+                parse_quote! { #lit }
+            };
+            values.push(expr.into());
+            value += step as i128;
+        }
+        values
+    }
+}
+
+/// The value and the type suffix of an integer literal, also a negative one.
+fn int_literal(expr: &Expr) -> Option<(i128, String)> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => Some((lit.base10_parse().ok()?, lit.suffix().to_owned())),
+        Expr::Unary(e) if matches!(e.op, UnOp::Neg(_)) => {
+            int_literal(&e.expr).map(|(value, suffix)| (-value, suffix))
+        }
+        Expr::Paren(e) => int_literal(&e.expr),
+        _ => None,
+    }
+}
+
+/// A `#[values(from = MY_CONST; len = N)]` argument: the macro cannot read the constant,
+/// so it takes the `N` elements by their index and checks the constant's length at compile
+/// time.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct ValuesFromConst {
+    pub(crate) arg: Ident,
+    pub(crate) path: Path,
+    pub(crate) len: LitInt,
+}
+
+impl ValuesFromConst {
+    /// The values list of the constant's elements.
+    pub(crate) fn value_list(&self) -> syn::Result<ValueList> {
+        let path = &self.path;
+        let name = sanitize_ident(&path.to_token_stream().to_string());
+        let len = self.len.base10_parse::<usize>()?;
+        Ok(ValueList {
+            arg: self.arg.clone(),
+            values: (0..len)
+                .map(|index| {
+                    Value::new(
+                        parse_quote! { ::core::clone::Clone::clone(&#path[#index]) },
+                        Some(format!("{name}_{index}")),
+                    )
+                })
+                .collect(),
+        })
+    }
+
+    /// The compile time check of the constant's length.
+    pub(crate) fn render_len_check(&self) -> TokenStream {
+        let path = &self.path;
+        let len = &self.len;
+        quote! {
+            const _: () = assert!(
+                #path.len() == #len,
+                concat!("`", stringify!(#path), "` should have ", stringify!(#len), " elements")
+            );
+        }
+    }
+}
+
+impl ToTokens for ValuesFromConst {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.path.to_tokens(tokens)
+    }
+}
+
 #[cfg(test)]
 mod should {
     use crate::test::{assert_eq, *};
@@ -126,4 +380,111 @@ mod should {
             parse_values_list(r#"other => 42"#);
         }
     }
+
+    mod parse_values_attr {
+        use super::assert_eq;
+        use super::*;
+
+        fn values(content: &str) -> Vec<String> {
+            match content.ast::<ValuesAttr>() {
+                ValuesAttr::List(values) => values.iter().map(|v| v.expr.display_code()).collect(),
+                ValuesAttr::FromConst(path, _) => {
+                    panic!("Unexpected const {}", path.display_code())
+                }
+            }
+        }
+
+        #[rstest]
+        #[case::list("1, 2, 3", &["1", "2", "3"])]
+        #[case::empty("", &[])]
+        #[case::half_open("range = 0..4", &["0", "1", "2", "3"])]
+        #[case::closed("range = 0..=3", &["0", "1", "2", "3"])]
+        #[case::negative("range = -2..1", &["- 2", "- 1", "0"])]
+        #[case::suffix("range = 250u8..=255", &["250u8", "251u8", "252u8", "253u8", "254u8", "255u8"])]
+        #[case::step("range = 0..=255; step = 85", &["0", "85", "170", "255"])]
+        #[case::step_over_the_end("range = 0..10; step = 4", &["0", "4", "8"])]
+        #[case::empty_range("range = 4..4", &[])]
+        #[case::the_maximum("range = 1..=1024; step = 1023", &["1", "1024"])]
+        #[case::plain_range_is_a_value("0..4", &["0 .. 4"])]
+        #[case::more_ranges_are_values("0..2, 4..6", &["0 .. 2", "4 .. 6"])]
+        fn expand_the_integer_ranges(#[case] content: &str, #[case] expected: &[&str]) {
+            assert_eq!(expected, values(content));
+        }
+
+        #[test]
+        fn read_the_const_path_and_len() {
+            assert_eq!(
+                ValuesAttr::FromConst("data::MY_CONST".ast(), "4".ast()),
+                "from = data::MY_CONST; len = 4".ast()
+            );
+        }
+
+        #[test]
+        fn read_the_values_names() {
+            let values = match "small => vec![1], 42, huge => big_vec(1000)".ast() {
+                ValuesAttr::List(values) => values,
+                other => panic!("Unexpected {other:?}"),
+            };
+
+            assert_eq!(
+                vec![
+                    ("vec ! [1]".to_owned(), Some("small".to_owned())),
+                    ("42".to_owned(), None),
+                    ("big_vec (1000)".to_owned(), Some("huge".to_owned())),
+                ],
+                values
+                    .into_iter()
+                    .map(|v| (v.expr.display_code(), v.description))
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn not_expand_a_named_range() {
+            assert_eq!(vec!["0 .. 3"], values("few => 0..3"));
+        }
+
+        #[test]
+        fn read_the_values_tags() {
+            let values = match r#"1, #[tag("slow")] big => 1000, #[tag("db", "slow")] 0..3"#.ast() {
+                ValuesAttr::List(values) => values,
+                other => panic!("Unexpected {other:?}"),
+            };
+
+            assert_eq!(
+                vec![0, 1, 1],
+                values.iter().map(|v| v.attrs.len()).collect::<Vec<_>>()
+            );
+            assert_eq!("0 .. 3", values[2].expr.display_code());
+        }
+
+        #[rstest]
+        #[case::not_a_tag("1, #[ignore] 2", "just #[tag(...)]")]
+        #[case::invalid_tag(r#"1, #[tag("a b")] 2"#, "Use #[tag(")]
+        fn reject_invalid_values_attributes(#[case] content: &str, #[case] message: &str) {
+            let error = syn::parse_str::<ValuesAttr>(content).unwrap_err();
+
+            rstest_test::assert_in!(error.to_string(), message);
+        }
+
+        #[test]
+        fn not_confuse_the_from_and_range_variables() {
+            assert_eq!(vec!["from", "range"], values("from, range"));
+        }
+
+        #[rstest]
+        #[case::zero_step("range = 0..8; step = 0", "positive integer")]
+        #[case::not_literals("range = 0..N; step = 2", "range of integer literals")]
+        #[case::not_a_range("range = 42", "range of integer literals")]
+        #[case::step_without_range("0..8; step = 2", "expected `,`")]
+        #[case::too_many_values("range = 0..100_000_000", "expands to 100000000 values")]
+        #[case::too_many_steps("range = 0..=2048; step = 2", "the maximum is 1024")]
+        #[case::const_without_len("from = MY_CONST", "len = 4")]
+        #[case::const_with_step("from = MY_CONST; step = 4", "expected `len`")]
+        fn reject_invalid_steps(#[case] content: &str, #[case] message: &str) {
+            let error = syn::parse_str::<ValuesAttr>(content).unwrap_err();
+
+            rstest_test::assert_in!(error.to_string(), message);
+        }
+    }
 }
@@ -1,16 +1,26 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use proc_macro2::{Span, TokenStream};
 use syn::{parse_quote, Ident, ItemFn, ReturnType};
 
-use quote::quote;
+use quote::{format_ident, quote};
 
 use super::apply_argumets::ApplyArgumets;
-use super::{inject, render_exec_call};
+use super::{crate_name, inject, render_exec_call};
+use crate::parse::{
+    params::VARIANTS_ATTR,
+    scope::{FixtureScope, ScopeKind},
+    teardown::{guarded_value_type, TearDown},
+};
 use crate::resolver::{self, Resolver};
-use crate::utils::{fn_args, fn_args_idents};
+use crate::utils::{fn_args, fn_args_idents, SearchImpl};
 use crate::{parse::fixture::FixtureInfo, utils::generics_clean_up};
 
 fn wrap_return_type_as_static_ref(rt: ReturnType) -> ReturnType {
     match rt {
+        syn::ReturnType::Type(_, t) if matches!(*t, syn::Type::ImplTrait(_)) => parse_quote! {
+           -> &'static (#t)
+        },
         syn::ReturnType::Type(_, t) => parse_quote! {
            -> &'static #t
         },
@@ -32,8 +42,126 @@ fn wrap_call_impl_with_call_once_impl(call_impl: TokenStream, rt: &ReturnType) -
     }
 }
 
+fn wrap_async_call_impl_with_call_once_impl(
+    call_future: TokenStream,
+    rt: &ReturnType,
+) -> TokenStream {
+    let rstest_path = crate_name();
+    match rt {
+        syn::ReturnType::Type(_, t) => parse_quote! {
+            static CELL: #rstest_path::once::AsyncOnceCell<#t> =
+                #rstest_path::once::AsyncOnceCell::new();
+            CELL.get_or_init(|| #call_future).await
+        },
+        _ => parse_quote! {
+            static CELL: #rstest_path::once::AsyncOnceCell<()> =
+                #rstest_path::once::AsyncOnceCell::new();
+            CELL.get_or_init(|| #call_future).await;
+        },
+    }
+}
+
+/// Generic fixtures can't use a `static` cell: the value is identified by the fixture and its
+/// concrete types.
+fn wrap_call_impl_with_generic_once_impl(
+    call_impl: TokenStream,
+    generics_idents: &[Ident],
+    is_async: bool,
+    rt: &ReturnType,
+) -> TokenStream {
+    let rstest_path = crate_name();
+    let key = quote! { (Self, #(#generics_idents,)*) };
+    let call = match is_async {
+        true => quote! { #rstest_path::once::generic_async::<#key, _, _, _>(|| #call_impl).await },
+        false => quote! { #rstest_path::once::generic::<#key, _>(|| #call_impl) },
+    };
+    match rt {
+        syn::ReturnType::Type(..) => call,
+        _ => quote! { #call; },
+    }
+}
+
+fn wrap_return_type_as_arc(rt: ReturnType) -> ReturnType {
+    match rt {
+        syn::ReturnType::Type(_, t) => parse_quote! {
+           -> ::std::sync::Arc<#t>
+        },
+        _ => parse_quote! {
+           -> ::std::sync::Arc<()>
+        },
+    }
+}
+
+/// Render the expression that evaluates to the `(value, guard)` tuple.
+fn call_impl_with_guard(call_impl: TokenStream, teardown: Option<&TearDown>) -> TokenStream {
+    let rstest_path = crate_name();
+    match teardown {
+        Some(TearDown::Guard(_)) => call_impl,
+        Some(TearDown::Expr(_, expr)) => quote! {
+            {
+                let value = #call_impl;
+                let guard = #rstest_path::teardown::with_ref(&value, #expr);
+                (value, guard)
+            }
+        },
+        None => quote! { (#call_impl, || {}) },
+    }
+}
+
+fn wrap_call_impl_with_teardown(call_impl: TokenStream, teardown: &TearDown) -> TokenStream {
+    let rstest_path = crate_name();
+    let call_impl = call_impl_with_guard(call_impl, Some(teardown));
+    quote! {
+        {
+            let (value, guard) = #call_impl;
+            #rstest_path::teardown::register(guard);
+            value
+        }
+    }
+}
+
+fn wrap_call_impl_with_scope(
+    call_impl: TokenStream,
+    scope: &FixtureScope,
+    teardown: Option<&TearDown>,
+) -> TokenStream {
+    let rstest_path = crate_name();
+    match scope.kind {
+        ScopeKind::Group => {
+            let call_impl = call_impl_with_guard(call_impl, teardown);
+            quote! {
+                #rstest_path::scope::group::<Self, _, _>(|| #call_impl)
+            }
+        }
+        ScopeKind::Module => quote! {
+            #rstest_path::scope::module::<Self, _>(|| #call_impl)
+        },
+    }
+}
+
+/// The type returned by the generated methods: the fixture's return type or just the value
+/// if the fixture returns a `(value, guard)` tuple.
+fn value_output(fixture: &ItemFn, info: &FixtureInfo) -> ReturnType {
+    match info.arguments.get_teardown() {
+        Some(TearDown::Guard(_)) => guarded_value_type(&fixture.sig.output)
+            .map(|t| parse_quote! { -> #t })
+            .unwrap_or_else(|| fixture.sig.output.clone()),
+        _ => fixture.sig.output.clone(),
+    }
+}
+
 pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
     fixture.apply_argumets(&info.arguments);
+    let generic_once = info.arguments.is_once()
+        && (fixture.sig.generics.type_params().next().is_some()
+            || SearchImpl::output_has_some_impl(&fixture.sig.output));
+    if generic_once {
+        fixture
+            .sig
+            .generics
+            .type_params_mut()
+            .for_each(|tp| tp.bounds.push(parse_quote! { 'static }));
+    }
     let name = &fixture.sig.ident;
     let asyncness = &fixture.sig.asyncness.clone();
     let vargs = fn_args_idents(&fixture).cloned().collect::<Vec<_>>();
@@ -41,19 +169,24 @@ pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
     let orig_args = &fixture.sig.inputs;
     let orig_attrs = &fixture.attrs;
     let generics = &fixture.sig.generics;
+    let mut output = value_output(&fixture, &info);
     let mut default_output = info
         .attributes
         .extract_default_type()
-        .unwrap_or_else(|| fixture.sig.output.clone());
+        .unwrap_or_else(|| output.clone());
     let default_generics =
         generics_clean_up(&fixture.sig.generics, std::iter::empty(), &default_output);
     let default_where_clause = &default_generics.where_clause;
     let where_clause = &fixture.sig.generics.where_clause;
-    let mut output = fixture.sig.output.clone();
     let visibility = &fixture.vis;
+    let variants = info.arguments.get_params().variants();
+    // Without a variant the fixture's parametrized arguments take the first one
     let resolver = (
-        resolver::fixtures::get(info.data.fixtures()),
-        resolver::values::get(info.data.values()),
+        variants[0].values.clone(),
+        (
+            resolver::fixtures::get(info.data.fixtures()),
+            resolver::values::get(info.data.values()),
+        ),
     );
     let generics_idents = generics
         .type_params()
@@ -72,12 +205,60 @@ pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
     let call_get = render_exec_call(parse_quote! { Self::get }, &args, asyncness.is_some());
     let mut call_impl = render_exec_call(parse_quote! { #name }, &args, asyncness.is_some());
 
-    if info.arguments.is_once() {
-        call_impl = wrap_call_impl_with_call_once_impl(call_impl, &output);
+    if let Some(scope) = info.arguments.get_scope() {
+        call_impl = wrap_call_impl_with_scope(call_impl, scope, info.arguments.get_teardown());
+        output = wrap_return_type_as_arc(output);
+        default_output = wrap_return_type_as_arc(default_output);
+    } else if let Some(teardown) = info.arguments.get_teardown() {
+        call_impl = wrap_call_impl_with_teardown(call_impl, teardown);
+    }
+    if generic_once {
+        call_impl = wrap_call_impl_with_generic_once_impl(
+            render_exec_call(parse_quote! { #name }, &args, false),
+            &generics_idents,
+            asyncness.is_some(),
+            &output,
+        );
+        output = wrap_return_type_as_static_ref(output);
+        default_output = wrap_return_type_as_static_ref(default_output);
+    } else if info.arguments.is_once() {
+        call_impl = match asyncness {
+            Some(_) => wrap_async_call_impl_with_call_once_impl(
+                render_exec_call(parse_quote! { #name }, &args, false),
+                &output,
+            ),
+            None => wrap_call_impl_with_call_once_impl(call_impl, &output),
+        };
         output = wrap_return_type_as_static_ref(output);
         default_output = wrap_return_type_as_static_ref(default_output);
     }
 
+    let params = (!info.arguments.get_params().is_empty()).then(|| {
+        let arms = variants.iter().enumerate().map(|(index, variant)| {
+            let inject = inject::resolve_aruments(
+                fixture.sig.inputs.iter(),
+                &(&variant.values, &resolver),
+                &generics_idents,
+            );
+            quote! {
+                #index => {
+                    #inject
+                    #call_get
+                }
+            }
+        });
+        quote! {
+            pub #asyncness fn param #default_generics (index: usize) #default_output #default_where_clause {
+                match index {
+                    #(#arms)*
+                    _ => panic!("The fixture has not the variant {}", index),
+                }
+            }
+        }
+    });
+    let params_macro = (!info.arguments.get_params().is_empty())
+        .then(|| render_params_macro(name, variants.iter().map(|v| &v.name)));
+
     quote! {
         #[allow(non_camel_case_types)]
         #visibility struct #name {}
@@ -95,13 +276,45 @@ pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
             }
 
             #(#partials)*
+
+            #params
         }
 
+        #params_macro
+
         #[allow(dead_code)]
         #fixture
     }
 }
 
+/// A parametrized fixture defines a macro with its own name that expands the `#[rstest]`
+/// functions that take all its variants by `#[params]`: the test become a values list
+/// of the fixture's variants.
+fn render_params_macro<'a>(
+    name: &Ident,
+    variants: impl Iterator<Item = &'a String>,
+) -> TokenStream {
+    static MACROS: AtomicUsize = AtomicUsize::new(0);
+    let macro_name = format_ident!(
+        "__rstest_params_{}_{}",
+        name,
+        MACROS.fetch_add(1, Ordering::Relaxed)
+    );
+    let variants_attr = format_ident!("{}", VARIANTS_ATTR);
+    quote! {
+        #[doc(hidden)]
+        macro_rules! #macro_name {
+            ([$arg:ident] [$($rstest:tt)*] $($test:tt)*) => {
+                #[$($rstest)*]
+                #[#variants_attr($arg: #(#variants),*)]
+                $($test)*
+            };
+        }
+        #[allow(unused_imports)]
+        pub(crate) use #macro_name as #name;
+    }
+}
+
 fn render_partial_impl(
     fixture: &ItemFn,
     n: usize,
@@ -111,11 +324,14 @@ fn render_partial_impl(
     let mut output = info
         .attributes
         .extract_partial_type(n)
-        .unwrap_or_else(|| fixture.sig.output.clone());
+        .unwrap_or_else(|| value_output(fixture, info));
 
     if info.arguments.is_once() {
         output = wrap_return_type_as_static_ref(output);
     }
+    if info.arguments.get_scope().is_some() {
+        output = wrap_return_type_as_arc(output);
+    }
 
     let generics = generics_clean_up(&fixture.sig.generics, fn_args(fixture).take(n), &output);
     let where_clause = &generics.where_clause;
@@ -160,7 +376,7 @@ mod should {
 
     use crate::parse::{
         arguments::{ArgumentsInfo, FutureArg},
-        Attribute, Attributes,
+        Attribute, Attributes, ExtendWithFunctionAttrs,
     };
 
     use super::*;
@@ -176,9 +392,15 @@ mod should {
 
     impl Parse for FixtureOutput {
         fn parse(input: ParseStream) -> Result<Self> {
+            let fixture = input.parse()?;
+            let core_impl = input.parse()?;
+            // Skip the parametrized fixture's macro
+            while input.fork().parse::<ItemFn>().is_err() {
+                input.parse::<syn::Item>()?;
+            }
             Ok(FixtureOutput {
-                fixture: input.parse()?,
-                core_impl: input.parse()?,
+                fixture,
+                core_impl,
                 orig: input.parse()?,
             })
         }
@@ -252,6 +474,125 @@ mod should {
         assert_eq!(signature.output, "-> &'static (u32, B, String, &str)".ast())
     }
 
+    #[test]
+    fn use_an_async_cell_if_once_attribute_on_async_fixture() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub async fn test(s: String) -> u32 { 42 }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+
+        assert!(get.sig.asyncness.is_some());
+        assert_eq!(get.sig.output, "-> &'static u32".ast());
+        assert_in!(get.block.display_code(), "once :: AsyncOnceCell < u32 >");
+        assert_in!(
+            get.block.display_code(),
+            "get_or_init (| | test (s)) . await"
+        );
+    }
+
+    #[rstest]
+    #[case::sync("", "once :: generic :: < (Self , T ,) , _ > (| | test (s))")]
+    #[case::async_fixture(
+        "async",
+        "once :: generic_async :: < (Self , T ,) , _ , _ , _ > (| | test (s)) . await"
+    )]
+    fn use_a_cell_for_each_type_if_once_attribute_on_generic_fixture(
+        #[case] asyncness: &str,
+        #[case] expected: &str,
+    ) {
+        let item_fn = parse_str::<ItemFn>(&format!(
+            r#"
+                pub {asyncness} fn test<T: Default>(s: String) -> T {{ T::default() }}
+        "#
+        ))
+        .unwrap();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+
+        assert_eq!(get.sig.output, "-> &'static T".ast());
+        assert_eq!(
+            get.sig.generics,
+            parse_str::<ItemFn>("fn f<T: Default + 'static>() {}")
+                .unwrap()
+                .sig
+                .generics
+        );
+        assert_in!(get.block.display_code(), expected);
+    }
+
+    #[test]
+    fn render_a_param_method_and_a_macro_for_parametrized_fixture() {
+        let mut item_fn = parse_str::<ItemFn>(
+            r#"
+                #[case::small(1)]
+                #[case::big(1000)]
+                pub fn test(#[case] v: u32, other: String) -> u32 { 42 }
+        "#,
+        )
+        .unwrap();
+        let mut info = FixtureInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let out: FixtureOutput = parse2(render(item_fn, info)).unwrap();
+
+        let default = select_method(out.core_impl.clone(), "default").unwrap();
+        let param = select_method(out.core_impl, "param").unwrap();
+
+        assert_in!(default.block.display_code(), "let v = 1 ;");
+        assert_eq!(param.sig.inputs, parse_quote! { index: usize });
+        assert_in!(param.block.display_code(), "0usize => { let v = 1 ;");
+        assert_in!(param.block.display_code(), "1usize => { let v = 1000 ;");
+    }
+
+    #[rstest]
+    #[case("get")]
+    #[case("default")]
+    #[case("partial_1")]
+    fn return_just_the_value_if_teardown_guard(#[case] method: &str) {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test(s: String) -> (Connection, impl FnOnce()) { }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_teardown("#[teardown]");
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let signature = select_method(out.core_impl, method).unwrap().sig;
+
+        assert_eq!(signature.output, "-> Connection".ast())
+    }
+
+    #[test]
+    fn register_teardown_expression_in_get() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test(s: String) -> Connection { }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_teardown("#[teardown(|c| c.close())]");
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+
+        assert_eq!(get.sig.output, "-> Connection".ast());
+        assert_in!(get.block.display_code(), "teardown :: register");
+        assert_in!(get.block.display_code(), "| c | c . close ()");
+    }
+
     #[template]
     #[rstest(
         method => ["default", "get", "partial_1", "partial_2", "partial_3"])
@@ -0,0 +1,176 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{spanned::Spanned, Ident};
+
+use crate::parse::{testcase::TestCase, vlist::Value};
+
+use super::crate_resolver::crate_name;
+
+/// What generated a test: its case and its values. The indexes start from 1 like in the
+/// tests' names.
+#[derive(Clone, Copy)]
+pub(crate) enum Origin<'a> {
+    Case {
+        index: usize,
+        case: &'a TestCase,
+    },
+    Value {
+        arg: &'a Ident,
+        index: usize,
+        value: &'a Value,
+    },
+}
+
+/// Register the test `name` generated from `testfn` in the `rstest::manifest` registry:
+/// `rstest::__register!` does nothing if `rstest`'s `manifest` feature is not enabled.
+pub(crate) fn register<'a>(
+    name: &Ident,
+    testfn: &Ident,
+    case_args: impl Iterator<Item = &'a Ident>,
+    origins: &[Origin],
+    tags: &[String],
+) -> TokenStream {
+    let rstest_path = crate_name();
+    let case_args = case_args.map(ToString::to_string).collect::<Vec<_>>();
+    let case = origins
+        .iter()
+        .find_map(|origin| match origin {
+            Origin::Case { index, case } => Some((index, case)),
+            _ => None,
+        })
+        .map(|(index, case)| {
+            let description = option(case.description.as_ref().map(ToString::to_string));
+            let args = case_args.iter().zip(case.args.iter()).map(|(arg, expr)| {
+                let expr = expr.to_token_stream().to_string();
+                quote! { (#arg, #expr) }
+            });
+            let line = line(
+                case.args
+                    .first()
+                    .map(Spanned::span)
+                    .or_else(|| case.description.as_ref().map(Ident::span))
+                    .unwrap_or_else(|| testfn.span()),
+            );
+            quote! {
+                Some(#rstest_path::manifest::CaseEntry {
+                    index: #index,
+                    description: #description,
+                    args: &[#(#args),*],
+                    line: #line,
+                })
+            }
+        })
+        .unwrap_or_else(|| quote! { None });
+    let values = origins.iter().filter_map(|origin| match origin {
+        Origin::Value { arg, index, value } => {
+            let arg = arg.to_string();
+            let expr = value.expr.to_token_stream().to_string();
+            let description = option(value.description.clone());
+            let file = option(value.file.clone());
+            let line = line(value.expr.span());
+            Some(quote! {
+                #rstest_path::manifest::ValueEntry {
+                    arg: #arg,
+                    index: #index,
+                    value: #expr,
+                    description: #description,
+                    file: #file,
+                    line: #line,
+                }
+            })
+        }
+        _ => None,
+    });
+    let function = testfn.to_string();
+    let file = quote_spanned! { testfn.span() => file!() };
+    let line = line(testfn.span());
+    quote! {
+        #[cfg(test)]
+        #rstest_path::__register! {
+            #rstest_path::manifest::TestEntry {
+                module: module_path!(),
+                name: stringify!(#name),
+                function: #function,
+                file: #file,
+                line: #line,
+                case: #case,
+                values: &[#(#values),*],
+                tags: &[#(#tags),*],
+            }
+        }
+    }
+}
+
+/// The line of the code at `span`.
+fn line(span: Span) -> TokenStream {
+    quote_spanned! { span => line!() }
+}
+
+fn option(value: Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::*;
+    use rstest_test::assert_in;
+
+    #[test]
+    fn register_the_case_and_the_values() {
+        let case: TestCase = "case::overflow(u8::MAX)".ast();
+        let value = Value {
+            file: Some("data/a.txt".to_owned()),
+            ..Value::new("x".ast(), Some("data/a.txt".to_owned()))
+        };
+        let arg: Ident = "a".ast();
+        let path: Ident = "path".ast();
+
+        let code = register(
+            &"path_1_data_a_txt_tag_slow".ast(),
+            &"my_test".ast(),
+            [&arg].into_iter(),
+            &[
+                Origin::Case {
+                    index: 3,
+                    case: &case,
+                },
+                Origin::Value {
+                    arg: &path,
+                    index: 1,
+                    value: &value,
+                },
+            ],
+            &["slow".to_owned()],
+        )
+        .display_code();
+
+        assert_in!(code, "rstest :: __register !");
+        assert_in!(
+            code,
+            r#"case : Some (rstest :: manifest :: CaseEntry { index : 3usize , description : Some ("overflow") , args : & [("a" , "u8 :: MAX")] , line : line ! () , })"#
+        );
+        assert_in!(
+            code,
+            r#"values : & [rstest :: manifest :: ValueEntry { arg : "path" , index : 1usize , value : "x" , description : Some ("data/a.txt") , file : Some ("data/a.txt") , line : line ! () , }]"#
+        );
+        assert_in!(code, r#"tags : & ["slow"]"#);
+    }
+
+    #[test]
+    fn register_a_test_without_cases_and_values() {
+        let code = register(
+            &"my_test".ast(),
+            &"my_test".ast(),
+            std::iter::empty(),
+            &[],
+            &[],
+        )
+        .display_code();
+
+        assert_in!(code, "case : None , values : & [] , tags : & []");
+    }
+}
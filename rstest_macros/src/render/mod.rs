@@ -1,5 +1,7 @@
 pub mod crate_resolver;
 pub(crate) mod fixture;
+mod manifest;
+mod pairwise;
 mod test;
 mod wrapper;
 
@@ -10,12 +12,17 @@ use syn::token::Async;
 use proc_macro2::{Span, TokenStream};
 use syn::{parse_quote, Attribute, Expr, FnArg, Ident, ItemFn, Path, ReturnType, Stmt};
 
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, ToTokens};
 
 use crate::utils::{attr_ends_with, sanitize_ident};
 use crate::{
     parse::{
-        rstest::{RsTestAttributes, RsTestData, RsTestInfo},
+        params::ParamsArg,
+        parse_expect_err, parse_retries, parse_tags, parse_xfail,
+        rstest::{
+            cases_from::CasesFromFn, exclude_when::ExcludeWhen, name_template::CASE_PLACEHOLDER,
+            random, RsTestAttributes, RsTestData, RsTestInfo,
+        },
         testcase::TestCase,
         vlist::ValueList,
     },
@@ -25,6 +32,7 @@ use crate::{
     refident::MaybeIdent,
     resolver::{self, Resolver},
 };
+use manifest::Origin;
 use wrapper::WrapByModule;
 
 pub(crate) use fixture::render as fixture;
@@ -46,6 +54,7 @@ pub(crate) fn single(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         &test.sig.ident,
         &args,
         &attrs,
+        &[],
         &test.sig.output,
         asyncness,
         Some(&test),
@@ -60,13 +69,13 @@ pub(crate) fn parametrize(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
     let resolver_fixtures = resolver::fixtures::get(info.data.fixtures());
 
     let rendered_cases = cases_data(&info.data, test.sig.ident.span())
-        .map(|(name, attrs, resolver)| {
-            TestCaseRender::new(name, attrs, (resolver, &resolver_fixtures))
+        .map(|(name, attrs, resolver, origin)| {
+            TestCaseRender::new(name, attrs, vec![origin], (resolver, &resolver_fixtures))
         })
         .map(|case| case.render(&test, &info))
         .collect();
 
-    test_group(test, rendered_cases)
+    test_group(test, rendered_cases, info.data.cases().count(), &info.data)
 }
 
 impl ValueList {
@@ -75,17 +84,59 @@ impl ValueList {
         test: &ItemFn,
         resolver: &dyn Resolver,
         attrs: &[syn::Attribute],
+        origins: &[Origin],
         info: &RsTestInfo,
+        combinations: Option<&[&[usize]]>,
     ) -> TokenStream {
         let span = test.sig.ident.span();
+        // Every test takes the value's tags too
+        let attrs = self
+            .values
+            .iter()
+            .map(|value| {
+                attrs
+                    .iter()
+                    .chain(&value.attrs)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
         let test_cases = self
             .argument_data(resolver)
-            .map(|(name, r)| TestCaseRender::new(Ident::new(&name, span), attrs, r))
+            .enumerate()
+            .filter(|(index, _)| {
+                combinations
+                    .map(|combinations| combinations.iter().any(|c| c[0] == *index))
+                    .unwrap_or(true)
+            })
+            .map(|(index, (name, r))| {
+                TestCaseRender::new(
+                    Ident::new(&name, span),
+                    &attrs[index],
+                    self.origins(origins, index),
+                    r,
+                )
+            })
             .map(|test_case| test_case.render(test, info));
 
         quote! { #(#test_cases)* }
     }
 
+    /// The `origins` of the test for the value at `index` too.
+    fn origins<'a>(&'a self, origins: &[Origin<'a>], index: usize) -> Vec<Origin<'a>> {
+        let mut origins = origins.to_vec();
+        let value = &self.values[index];
+        origins.push(Origin::Value {
+            arg: &self.arg,
+            index: value
+                .position
+                .map(|(position, _)| position)
+                .unwrap_or(index + 1),
+            value,
+        });
+        origins
+    }
+
     fn argument_data<'a>(
         &'a self,
         resolver: &'a dyn Resolver,
@@ -93,10 +144,11 @@ impl ValueList {
         let max_len = self.values.len();
         self.values.iter().enumerate().map(move |(index, value)| {
             let description = sanitize_ident(&value.description());
+            let (position, max_len) = value.position.unwrap_or((index + 1, max_len));
             let name = format!(
                 "{}_{:0len$}_{description:.64}",
                 self.arg,
-                index + 1,
+                position,
                 len = max_len.display_len()
             );
             let resolver_this = (self.arg.to_string(), value.expr.clone());
@@ -105,12 +157,16 @@ impl ValueList {
     }
 }
 
+/// Render the tests for the combinations of the values lists: all of them or just the
+/// given `combinations` of the values' indexes.
 fn _matrix_recursive<'a>(
     test: &ItemFn,
     list_values: &'a [&'a ValueList],
     resolver: &dyn Resolver,
     attrs: &'a [syn::Attribute],
+    origins: &[Origin<'a>],
     info: &RsTestInfo,
+    combinations: Option<&[&[usize]]>,
 ) -> TokenStream {
     if list_values.is_empty() {
         return Default::default();
@@ -123,13 +179,41 @@ fn _matrix_recursive<'a>(
         attrs.push(parse_quote!(
             #[allow(non_snake_case)]
         ));
-        vlist.render(test, resolver, &attrs, info)
+        vlist.render(test, resolver, &attrs, origins, info, combinations)
     } else {
         let span = test.sig.ident.span();
-        let modules = vlist.argument_data(resolver).map(move |(name, resolver)| {
-            _matrix_recursive(test, list_values, &resolver, attrs, info)
-                .wrap_by_mod(&Ident::new(&name, span))
-        });
+        let modules = vlist.argument_data(resolver).enumerate().filter_map(
+            move |(index, (name, resolver))| {
+                // The combinations that start with this value, without it
+                let combinations = combinations.map(|combinations| {
+                    combinations
+                        .iter()
+                        .filter(|c| c[0] == index)
+                        .map(|c| &c[1..])
+                        .collect::<Vec<_>>()
+                });
+                if matches!(&combinations, Some(combinations) if combinations.is_empty()) {
+                    return None;
+                }
+                let attrs = attrs
+                    .iter()
+                    .chain(&vlist.values[index].attrs)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                Some(
+                    _matrix_recursive(
+                        test,
+                        list_values,
+                        &resolver,
+                        &attrs,
+                        &vlist.origins(origins, index),
+                        info,
+                        combinations.as_deref(),
+                    )
+                    .wrap_by_mod(&Ident::new(&name, span)),
+                )
+            },
+        );
 
         quote! { #(
             #[allow(non_snake_case)]
@@ -145,27 +229,175 @@ pub(crate) fn matrix(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
     let cases = cases_data(&info.data, span).collect::<Vec<_>>();
 
     let resolver = resolver::fixtures::get(info.data.fixtures());
+    let combinations = value_combinations(&info);
+    let combinations = combinations
+        .as_ref()
+        .map(|combinations| combinations.iter().map(Vec::as_slice).collect::<Vec<_>>());
+    let tests = cases.len().max(1)
+        * combinations.as_ref().map(Vec::len).unwrap_or_else(|| {
+            info.data
+                .list_values()
+                .map(|list| list.values.len())
+                .product()
+        });
     let rendered_cases = if cases.is_empty() {
         let list_values = info.data.list_values().collect::<Vec<_>>();
-        _matrix_recursive(&test, &list_values, &resolver, &[], &info)
+        _matrix_recursive(
+            &test,
+            &list_values,
+            &resolver,
+            &[],
+            &[],
+            &info,
+            combinations.as_deref(),
+        )
     } else {
         cases
             .into_iter()
-            .map(|(case_name, attrs, case_resolver)| {
+            .map(|(case_name, attrs, case_resolver, origin)| {
                 let list_values = info.data.list_values().collect::<Vec<_>>();
                 _matrix_recursive(
                     &test,
                     &list_values,
                     &(case_resolver, &resolver),
                     attrs,
+                    &[origin],
                     &info,
+                    combinations.as_deref(),
                 )
                 .wrap_by_mod(&case_name)
             })
             .collect()
     };
 
-    test_group(test, rendered_cases)
+    test_group(test, rendered_cases, tests, &info.data)
+}
+
+/// The combinations of the values' indexes to render: with `#[pairwise]` the ones that
+/// cover all the values' pairs, with `#[zip]` the values with the same index. Otherwise
+/// `None`: all the combinations.
+fn value_combinations(info: &RsTestInfo) -> Option<Vec<Vec<usize>>> {
+    let sizes = info
+        .data
+        .list_values()
+        .map(|list| list.values.len())
+        .collect::<Vec<_>>();
+    if info.attributes.pairwise() {
+        Some(pairwise::covering_array(&sizes))
+    } else if info.attributes.zip() {
+        let len = sizes.iter().copied().min().unwrap_or_default();
+        Some((0..len).map(|index| vec![index; sizes.len()]).collect())
+    } else {
+        None
+    }
+}
+
+/// All the combinations of the values' indexes for lists of the given `sizes`.
+fn all_combinations(sizes: &[usize]) -> Vec<Vec<usize>> {
+    sizes.iter().fold(vec![vec![]], |combinations, &size| {
+        combinations
+            .into_iter()
+            .flat_map(|c| {
+                (0..size).map(move |index| {
+                    let mut c = c.clone();
+                    c.push(index);
+                    c
+                })
+            })
+            .collect()
+    })
+}
+
+/// Render the cases and the values' combinations as flat tests named by the
+/// `name = "template"` argument.
+pub(crate) fn named(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
+    let template = match info.data.name_template() {
+        Some(template) => template,
+        None => return matrix(test, info),
+    };
+    test.apply_argumets(&info.arguments);
+    let resolver_fixtures = resolver::fixtures::get(info.data.fixtures());
+    let list_values = info.data.list_values().collect::<Vec<_>>();
+    let combinations = value_combinations(&info).unwrap_or_else(|| {
+        all_combinations(
+            &list_values
+                .iter()
+                .map(|list| list.values.len())
+                .collect::<Vec<_>>(),
+        )
+    });
+    let cases = match info.data.has_cases() {
+        true => info.data.cases().map(Some).collect::<Vec<_>>(),
+        false => vec![None],
+    };
+    let display_len = cases.len().display_len();
+
+    let mut tests = vec![];
+    let mut names = std::collections::HashSet::new();
+    for (n, case) in cases.into_iter().enumerate() {
+        for combination in &combinations {
+            let mut descriptions = HashMap::new();
+            let mut resolver = HashMap::new();
+            let mut attrs = vec![parse_quote! { #[allow(non_snake_case)] }];
+            let mut origins = vec![];
+            if let Some(case) = case {
+                origins.push(Origin::Case { index: n + 1, case });
+                descriptions.insert(
+                    CASE_PLACEHOLDER.to_owned(),
+                    case.description
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_else(|| format!("{:0display_len$}", n + 1)),
+                );
+                for (arg, expr) in info.data.case_args().zip(case.args.iter()) {
+                    descriptions.insert(arg.to_string(), expr.to_token_stream().to_string());
+                    resolver.insert(arg.to_string(), expr);
+                }
+                attrs.extend(case.attrs.iter().cloned());
+            }
+            for (list, &index) in list_values.iter().zip(combination) {
+                let value = &list.values[index];
+                descriptions.insert(list.arg.to_string(), value.description());
+                resolver.insert(list.arg.to_string(), &value.expr);
+                attrs.extend(value.attrs.iter().cloned());
+                origins = list.origins(&origins, index);
+            }
+            let name = match template.render(&descriptions) {
+                Ok(name) => name,
+                Err(error) => return error.to_compile_error(),
+            };
+            if !names.insert(name.clone()) {
+                return template.duplicated(&name).to_compile_error();
+            }
+            tests.push((name, attrs, origins, resolver));
+        }
+    }
+    let span = test.sig.ident.span();
+    let rendered_cases = tests
+        .iter()
+        .map(|(name, attrs, origins, resolver)| {
+            TestCaseRender::new(
+                Ident::new(name, span),
+                attrs,
+                origins.clone(),
+                (resolver.clone(), &resolver_fixtures),
+            )
+            .render(&test, &info)
+        })
+        .collect();
+
+    test_group(test, rendered_cases, tests.len(), &info.data)
+}
+
+/// Just the fixture knows its variants: ask it to expand the test again with them. It calls
+/// `#[rstest]` with the same arguments and adds the variants' names to the test.
+pub(crate) fn params(args: TokenStream, test: TokenStream, params: &ParamsArg) -> TokenStream {
+    let rstest_path = crate_name();
+    let fixture = &params.fixture;
+    let arg = &params.arg;
+    quote! {
+        #fixture! { [#arg] [#rstest_path::rstest(#args)] #test }
+    }
 }
 
 fn resolve_default_test_attr(is_async: bool) -> TokenStream {
@@ -219,6 +451,7 @@ fn generics_types_ident(generics: &syn::Generics) -> impl Iterator<Item = &'_ Id
 /// * `testfn_name` - The name of test function to call
 /// * `args` - The arguments of the test function
 /// * `attrs` - The expected test attributes
+/// * `origins` - The case and the values that generated the test
 /// * `output` - The expected test return type
 /// * `asyncness` - The `async` fn token
 /// * `test_impl` - If you want embed test function (should be the one called by `testfn_name`)
@@ -233,6 +466,7 @@ fn single_test_case(
     testfn_name: &Ident,
     args: &[FnArg],
     attrs: &[Attribute],
+    origins: &[Origin],
     output: &ReturnType,
     asyncness: Option<Async>,
     test_impl: Option<&ItemFn>,
@@ -246,7 +480,63 @@ fn single_test_case(
     if !trace_me.is_empty() {
         attributes.add_trace(format_ident!("trace"));
     }
+    // The function's, case's and values' tags: they are in the test name too
+    let (attrs, tag_attrs): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|a| !attr_is(a, "tag"));
+    let mut tags = tag_attrs
+        .iter()
+        .flat_map(|attribute| parse_tags(attribute).unwrap())
+        .map(|tag| tag.value())
+        .collect::<Vec<_>>();
+    tags.sort();
+    tags.dedup();
+    let name = &Ident::new(
+        &tags.iter().fold(name.to_string(), |name, tag| {
+            format!("{name}_tag_{}", tag.replace('-', "_"))
+        }),
+        name.span(),
+    );
     let generics_types = generics_types_ident(generics).cloned().collect::<Vec<_>>();
+    let runtime_files = info.data.runtime_files().collect::<Vec<_>>();
+    let randoms = info.data.randoms().collect::<Vec<_>>();
+    let cases_from_fn = info.data.cases_from_fn();
+    // The exclusions that are true at compile time make the test ignored, the ones that
+    // cannot be evaluated are checked when the arguments are resolved
+    let (excluded, runtime_exclusions): (Vec<_>, Vec<_>) = info
+        .data
+        .exclusions()
+        .map(|exclusion| (exclusion, exclusion.evaluate(&resolver)))
+        .filter(|(_, excluded)| *excluded != Some(false))
+        .partition(|(_, excluded)| *excluded == Some(true));
+    let excluded = excluded
+        .into_iter()
+        .map(|(exclusion, _)| exclusion)
+        .collect::<Vec<_>>();
+    let exclusions = excluded
+        .iter()
+        .copied()
+        .chain(
+            runtime_exclusions
+                .into_iter()
+                .map(|(exclusion, _)| exclusion),
+        )
+        .map(|exclusion| exclusion.render_check())
+        .collect::<Vec<_>>();
+    let runtime_resolver = runtime_files
+        .iter()
+        .map(|files| (files.arg.to_string(), files.value()))
+        .chain(
+            randoms
+                .iter()
+                .map(|random| (random.arg.to_string(), random.value())),
+        )
+        .chain(
+            cases_from_fn
+                .iter()
+                .flat_map(|_| info.data.case_args())
+                .map(|arg| (arg.to_string(), CasesFromFn::value(arg))),
+        )
+        .collect::<HashMap<_, _>>();
+    let resolver = (runtime_resolver, resolver);
     let inject = inject::resolve_aruments(args.iter(), &resolver, &generics_types);
     let args = args
         .iter()
@@ -264,6 +554,25 @@ fn single_test_case(
         .last()
         .map(|attribute| attribute.parse_args::<Expr>().unwrap());
 
+    // The case's retries override the function's ones
+    let (attrs, retries): (Vec<_>, Vec<_>) =
+        attrs.into_iter().partition(|a| !attr_is(a, "retries"));
+    let retries = retries
+        .last()
+        .map(|attribute| parse_retries(attribute).unwrap());
+    let (attrs, xfails): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|a| !attr_is(a, "xfail"));
+    let xfail = xfails
+        .last()
+        .map(|attribute| parse_xfail(attribute).unwrap());
+    let (attrs, expect_errs): (Vec<_>, Vec<_>) =
+        attrs.into_iter().partition(|a| !attr_is(a, "expect_err"));
+    let expect_err = expect_errs
+        .last()
+        .map(|attribute| parse_expect_err(attribute).unwrap());
+
+    let ignore = (!excluded.is_empty() && !attrs.iter().any(|a| attr_is(a, "ignore")))
+        .then(|| ExcludeWhen::render_ignore(excluded.iter().copied()));
+
     // If no injected attribut provided use the default one
     let test_attr = if attrs
         .iter()
@@ -285,15 +594,107 @@ fn single_test_case(
         .collect::<Vec<_>>();
     let execute = render_test_call(testfn_name.clone().into(), &args, timeout, is_async);
     let lifetimes = generics.lifetimes();
+    let rstest_path = crate_name();
+    let group = group_ident();
+    // Tests that are not rendered in a group (the ones that include the test implementation)
+    // are a group by themselves
+    let group_static = test_impl.map(|_| {
+        quote! {
+            static #group: #rstest_path::scope::Group =
+                #rstest_path::scope::Group::new(concat!(module_path!(), "::", stringify!(#name)), 1);
+        }
+    });
+
+    let register = manifest::register(name, testfn_name, info.data.case_args(), origins, &tags);
+    let tags_filter = (!tags.is_empty()).then(|| {
+        quote! {
+            if !#rstest_path::tags::is_selected(&[#(#tags),*]) {
+                return #rstest_path::tags::skip(&[#(#tags),*]);
+            }
+        }
+    });
+
+    let mut body = quote! {
+        #inject
+        #trace_args
+        #(#exclusions)*
+        #execute
+    };
+    if let Some(pattern) = expect_err {
+        // The pattern's static is scoped to its own block: it doesn't shadow the test's items
+        body = quote! {
+            #rstest_path::expect::check_err(
+                {
+                    static RSTEST_EXPECT_ERR: #rstest_path::expect::Pattern =
+                        #rstest_path::expect::Pattern::new(#pattern);
+                    &RSTEST_EXPECT_ERR
+                },
+                {
+                    #body
+                },
+            )
+        };
+    }
+    // Property based tests call the test for the random values
+    if !randoms.is_empty() {
+        body = random::render(&randoms, body);
+    }
+    if let Some(retries) = retries {
+        // Resolve the fixtures again for every attempt
+        body = match is_async {
+            true => quote! {
+                #rstest_path::retries::execute_with_retries_async(#retries, || async {
+                    #body
+                }).await
+            },
+            false => quote! {
+                #rstest_path::retries::execute_with_retries(#retries, || {
+                    #body
+                })
+            },
+        };
+    }
+    if let Some(reason) = xfail {
+        let reason = match reason {
+            Some(reason) => quote! { Some(#reason) },
+            None => quote! { None },
+        };
+        body = match is_async {
+            true => quote! {
+                #rstest_path::expect::execute_xfail_async(#reason, async {
+                    #body
+                }).await
+            },
+            false => quote! {
+                #rstest_path::expect::execute_xfail(#reason, || {
+                    #body
+                })
+            },
+        };
+    }
+    // Arguments expanded at runtime call the test for each file
+    let body = runtime_files
+        .iter()
+        .rev()
+        .fold(body, |body, files| files.render(body));
+    // Cases generated at runtime call the test for each of them
+    let body = match cases_from_fn {
+        Some(cases_from_fn) => cases_from_fn.render(info.data.case_args(), body),
+        None => body,
+    };
 
     quote! {
         #test_attr
         #(#attrs)*
+        #ignore
         #asyncness fn #name<#(#lifetimes,)*>() #output {
             #test_impl
-            #inject
-            #trace_args
-            #execute
+            #group_static
+            #register
+            let _rstest_group = #group.enter();
+            let _rstest_teardown = #rstest_path::teardown::Scope::new();
+            #tags_filter
+            #body
         }
     }
 }
@@ -325,14 +726,21 @@ fn trace_arguments<'a>(
 struct TestCaseRender<'a> {
     name: Ident,
     attrs: &'a [syn::Attribute],
+    origins: Vec<Origin<'a>>,
     resolver: Box<dyn Resolver + 'a>,
 }
 
 impl<'a> TestCaseRender<'a> {
-    pub fn new<R: Resolver + 'a>(name: Ident, attrs: &'a [syn::Attribute], resolver: R) -> Self {
+    pub fn new<R: Resolver + 'a>(
+        name: Ident,
+        attrs: &'a [syn::Attribute],
+        origins: Vec<Origin<'a>>,
+        resolver: R,
+    ) -> Self {
         TestCaseRender {
             name,
             attrs,
+            origins,
             resolver: Box::new(resolver),
         }
     }
@@ -348,6 +756,7 @@ impl<'a> TestCaseRender<'a> {
             &testfn.sig.ident,
             &args,
             &attrs,
+            &self.origins,
             &testfn.sig.output,
             asyncness,
             None,
@@ -358,8 +767,26 @@ impl<'a> TestCaseRender<'a> {
     }
 }
 
-fn test_group(mut test: ItemFn, rendered_cases: TokenStream) -> TokenStream {
+/// The name of the static that tracks the running tests of a group: fixtures with
+/// `#[scope(group)]` are shared by all the tests of the same group.
+fn group_ident() -> Ident {
+    format_ident!("RSTEST_GROUP")
+}
+
+/// Render the test function and the module with its `tests` rendered tests.
+fn test_group(
+    mut test: ItemFn,
+    rendered_cases: TokenStream,
+    tests: usize,
+    data: &RsTestData,
+) -> TokenStream {
     let fname = &test.sig.ident;
+    let group = group_ident();
+    let rstest_path = crate_name();
+    let tracked = data.tracked();
+    let len_checks = data
+        .values_from_consts()
+        .map(|from_const| from_const.render_len_check());
     test.attrs = vec![];
 
     quote! {
@@ -370,6 +797,14 @@ fn test_group(mut test: ItemFn, rendered_cases: TokenStream) -> TokenStream {
         mod #fname {
             use super::*;
 
+            #[allow(dead_code)]
+            static #group: #rstest_path::scope::Group =
+                #rstest_path::scope::Group::new(module_path!(), #tests);
+
+            #(#tracked)*
+
+            #(#len_checks)*
+
             #rendered_cases
         }
     }
@@ -397,7 +832,14 @@ fn format_case_name(case: &TestCase, index: usize, display_len: usize) -> String
 fn cases_data(
     data: &RsTestData,
     name_span: Span,
-) -> impl Iterator<Item = (Ident, &[syn::Attribute], HashMap<String, &syn::Expr>)> {
+) -> impl Iterator<
+    Item = (
+        Ident,
+        &[syn::Attribute],
+        HashMap<String, &syn::Expr>,
+        Origin<'_>,
+    ),
+> {
     let display_len = data.cases().count().display_len();
     data.cases().enumerate().map({
         move |(n, case)| {
@@ -410,6 +852,7 @@ fn cases_data(
                 Ident::new(&format_case_name(case, n + 1, display_len), name_span),
                 case.attrs.as_slice(),
                 resolver_case,
+                Origin::Case { index: n + 1, case },
             )
         }
     })
@@ -0,0 +1,128 @@
+//! Pairwise (all-pairs) reduction of the values lists' combinations: a deterministic
+//! covering array where every pair of values of two different arguments appears in at
+//! least one combination. It uses the In-Parameter-Order strategy: start from all the
+//! combinations of the first two arguments and add one argument at a time, first by
+//! extending the existing combinations and then by adding the ones for the uncovered pairs.
+use std::{cmp::Reverse, collections::BTreeSet};
+
+/// The sorted combinations of the values' indexes that cover all the pairs. `sizes` are
+/// the number of values of each argument.
+pub(crate) fn covering_array(sizes: &[usize]) -> Vec<Vec<usize>> {
+    if sizes.is_empty() || sizes.contains(&0) {
+        return vec![];
+    }
+    if sizes.len() == 1 {
+        return (0..sizes[0]).map(|v| vec![v]).collect();
+    }
+    let mut rows = (0..sizes[0])
+        .flat_map(|a| (0..sizes[1]).map(move |b| vec![Some(a), Some(b)]))
+        .collect::<Vec<_>>();
+    for (arg, &size) in sizes.iter().enumerate().skip(2) {
+        let mut uncovered = (0..arg)
+            .flat_map(|other| {
+                (0..sizes[other]).flat_map(move |a| (0..size).map(move |v| (other, a, v)))
+            })
+            .collect::<BTreeSet<_>>();
+        // Extend every combination with the value that covers more pairs
+        for row in rows.iter_mut() {
+            let value = (0..size)
+                .max_by_key(|&v| {
+                    (
+                        pairs(row, v).filter(|p| uncovered.contains(p)).count(),
+                        Reverse(v),
+                    )
+                })
+                .unwrap_or_default();
+            for pair in pairs(row, value).collect::<Vec<_>>() {
+                uncovered.remove(&pair);
+            }
+            row.push(Some(value));
+        }
+        // Cover the remaining pairs: fill the free slots before adding new combinations
+        for (other, a, v) in uncovered {
+            match rows
+                .iter_mut()
+                .find(|row| row[arg] == Some(v) && row[other].is_none())
+            {
+                Some(row) => row[other] = Some(a),
+                None => {
+                    let mut row = vec![None; arg + 1];
+                    row[other] = Some(a);
+                    row[arg] = Some(v);
+                    rows.push(row);
+                }
+            }
+        }
+    }
+    let mut rows = rows
+        .into_iter()
+        .map(|row| row.into_iter().map(Option::unwrap_or_default).collect())
+        .collect::<Vec<Vec<_>>>();
+    rows.sort();
+    rows.dedup();
+    rows
+}
+
+/// The pairs that `value` of the next argument forms with the values in `row`.
+fn pairs(row: &[Option<usize>], value: usize) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+    row.iter()
+        .enumerate()
+        .filter_map(move |(other, a)| a.map(|a| (other, a, value)))
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    fn all_pairs_covered(sizes: &[usize], rows: &[Vec<usize>]) -> bool {
+        (0..sizes.len()).all(|i| {
+            (i + 1..sizes.len()).all(|j| {
+                (0..sizes[i]).all(|a| {
+                    (0..sizes[j]).all(|b| rows.iter().any(|row| row[i] == a && row[j] == b))
+                })
+            })
+        })
+    }
+
+    #[rstest]
+    #[case(&[3, 3, 3])]
+    #[case(&[2, 5, 3, 4])]
+    #[case(&[8, 8, 8, 8, 8])]
+    #[case(&[1, 4, 2, 7, 3, 2])]
+    fn cover_all_pairs(#[case] sizes: &[usize]) {
+        let rows = covering_array(sizes);
+
+        assert!(all_pairs_covered(sizes, &rows));
+        assert!(rows.len() < sizes.iter().product::<usize>());
+        assert!(rows
+            .iter()
+            .all(|row| row.iter().zip(sizes).all(|(v, size)| v < size)));
+    }
+
+    #[test]
+    fn be_much_smaller_than_the_full_matrix() {
+        let rows = covering_array(&[8, 8, 8, 8, 8]);
+
+        // Instead of 32768: we need at least 64 combinations to cover the pairs of two
+        // arguments
+        assert!(rows.len() <= 2 * 64, "{} combinations", rows.len());
+    }
+
+    #[rstest]
+    #[case::no_args(&[], vec![])]
+    #[case::no_values(&[2, 0, 3], vec![])]
+    #[case::one_arg(&[3], vec![vec![0], vec![1], vec![2]])]
+    #[case::two_args(&[2, 2], vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]])]
+    fn take_all_combinations_when_cannot_reduce(
+        #[case] sizes: &[usize],
+        #[case] expected: Vec<Vec<usize>>,
+    ) {
+        assert_eq!(expected, covering_array(sizes));
+    }
+
+    #[test]
+    fn be_deterministic() {
+        assert_eq!(covering_array(&[3, 4, 2, 5]), covering_array(&[3, 4, 2, 5]));
+    }
+}
@@ -37,7 +37,10 @@ mod single_test_should {
     use rstest_test::{assert_in, assert_not_in};
 
     use crate::{
-        parse::arguments::{ArgumentsInfo, FutureArg},
+        parse::{
+            arguments::{ArgumentsInfo, FutureArg},
+            ExtendWithFunctionAttrs,
+        },
         test::{assert_eq, *},
     };
 
@@ -214,6 +217,126 @@ mod single_test_should {
         );
     }
 
+    #[test]
+    fn retry_the_fixtures_resolution_and_the_test() {
+        let input_fn: ItemFn = r#"#[retries(3)] fn test(fix: String) {} "#.ast();
+
+        let item_fn: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = item_fn.block.stmts.last().display_code();
+        assert_in!(code, "retries :: execute_with_retries (3usize");
+        assert_in!(code, "let fix = fix :: default ()");
+        assert!(!format!("{:?}", item_fn.attrs).contains("retries"));
+    }
+
+    #[test]
+    fn retry_the_async_test_by_awaiting_it_again() {
+        let input_fn: ItemFn = r#"#[retries(3)] async fn test(fix: String) {} "#.ast();
+
+        let item_fn: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = item_fn.block.stmts.last().display_code();
+        assert_in!(
+            code,
+            "retries :: execute_with_retries_async (3usize , | | async { let fix = fix :: default ()"
+        );
+        assert!(code.ends_with(") . await"));
+    }
+
+    #[test]
+    fn wrap_the_test_that_should_fail() {
+        let input_fn: ItemFn =
+            r#"#[xfail("known bug")] #[expect_err("invalid")] fn test() -> Result<(), E> {} "#
+                .ast();
+
+        let item_fn: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = item_fn.block.stmts.last().display_code();
+        assert_in!(code, r#"expect :: execute_xfail (Some ("known bug")"#);
+        assert_in!(code, r#"expect :: check_err ({ static RSTEST_EXPECT_ERR"#);
+        assert_in!(code, r#"expect :: Pattern :: new ("invalid")"#);
+        assert!(!format!("{:?}", item_fn.attrs).contains("xfail"));
+        assert!(!format!("{:?}", item_fn.attrs).contains("expect_err"));
+    }
+
+    #[test]
+    fn await_the_async_test_that_should_fail() {
+        let input_fn: ItemFn = r#"#[xfail] async fn test(fix: String) {} "#.ast();
+
+        let item_fn: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        let code = item_fn.block.stmts.last().display_code();
+        assert_in!(
+            code,
+            "expect :: execute_xfail_async (None , async { let fix = fix :: default ()"
+        );
+        assert!(code.ends_with(") . await"));
+    }
+
+    #[test]
+    fn run_the_cases_generated_at_runtime() {
+        let mut input_fn: ItemFn = r#"
+            #[cases_from_fn(generated::cases)]
+            fn test(#[case] a: u32, fix: String, #[case] b: &str) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut input_fn).unwrap();
+
+        let item_fn: ItemFn = single(input_fn, info).ast();
+
+        let code = item_fn.block.stmts.last().display_code();
+        assert_in!(
+            code,
+            "cases :: execute_cases (generated :: cases () , | (__rstest_case_a , __rstest_case_b ,) |"
+        );
+        assert_in!(code, "let a = __rstest_case_a ;");
+        assert_in!(code, "let b = __rstest_case_b ;");
+        assert_in!(code, "let fix = fix :: default ()");
+    }
+
+    #[test]
+    fn draw_the_random_arguments() {
+        let mut input_fn: ItemFn = r#"
+            fn test(#[random(0..10u32, cases = 32)] a: u32, fix: String, #[random(any::<bool>())] b: bool) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut input_fn).unwrap();
+
+        let item_fn: ItemFn = single(input_fn, info).ast();
+
+        let code = item_fn.block.stmts.last().display_code();
+        assert_in!(
+            code,
+            r#"random :: check ((0 .. 10u32 , any :: < bool > () ,) , Some (32) , None , & ["a" , "b"] , | (__rstest_random_a , __rstest_random_b ,) |"#
+        );
+        assert_in!(code, "let a = __rstest_random_a ;");
+        assert_in!(code, "let b = __rstest_random_b ;");
+        assert_in!(code, "let fix = fix :: default ()");
+    }
+
+    #[test]
+    fn filter_the_tagged_test_at_runtime() {
+        let mut input_fn: ItemFn = r#"
+            #[tag("slow", "db")]
+            #[tag("slow")]
+            fn test(fix: String) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut input_fn).unwrap();
+
+        let item_fn: ItemFn = single(input_fn, info).ast();
+
+        assert_eq!("test_tag_db_tag_slow", item_fn.sig.ident.to_string());
+        assert!(!item_fn.attrs.iter().any(|a| attr_is(a, "tag")));
+        assert_in!(
+            item_fn.block.display_code(),
+            r#"if ! rstest :: tags :: is_selected (& ["db" , "slow"]) { return rstest :: tags :: skip (& ["db" , "slow"]) ; }"#
+        );
+    }
+
     #[test]
     fn trace_arguments_values() {
         let input_fn: ItemFn = r#"#[trace]fn test(s: String, a:i32) {} "#.ast();
@@ -601,6 +724,31 @@ mod cases_should {
         assert_eq!(output.module.ident, "should_be_the_module_name");
     }
 
+    #[test]
+    fn define_the_group_of_the_test_function() {
+        let (item_fn, info) = some_simple_cases(3);
+
+        let tokens = parametrize(item_fn, info);
+
+        let output = TestsGroup::from(tokens);
+        let group = output
+            .module
+            .content
+            .unwrap()
+            .1
+            .into_iter()
+            .find_map(|item| match item {
+                syn::Item::Static(s) if s.ident == "RSTEST_GROUP" => Some(s),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_in!(
+            group.expr.display_code(),
+            "Group :: new (module_path ! () , 3usize)"
+        );
+    }
+
     #[test]
     fn copy_user_function() {
         let (item_fn, info) = TestCaseBuilder::from(
@@ -676,6 +824,27 @@ mod cases_should {
         assert_eq!(given_attrs.as_slice(), &test_attrs[..l]);
     }
 
+    #[test]
+    fn override_the_retries_by_the_case_ones() {
+        let (mut item_fn, info) = TestCaseBuilder::from(r#"fn test(v: i32){}"#)
+            .push_case(TestCase::from("42").with_attrs(attrs("#[retries(5)]")))
+            .push_case(TestCase::from("43"))
+            .take();
+        item_fn.attrs = attrs("#[retries(2)]");
+
+        let tokens = parametrize(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+        assert_in!(
+            tests[0].block.display_code(),
+            "execute_with_retries (5usize"
+        );
+        assert_in!(
+            tests[1].block.display_code(),
+            "execute_with_retries (2usize"
+        );
+    }
+
     #[test]
     fn mark_user_function_as_test() {
         let (item_fn, info) = TestCaseBuilder::from(
@@ -1061,7 +1230,10 @@ mod cases_should {
 mod matrix_cases_should {
     use rstest_test::{assert_in, assert_not_in};
 
-    use crate::parse::arguments::{ArgumentsInfo, FutureArg};
+    use crate::parse::{
+        arguments::{ArgumentsInfo, FutureArg},
+        ExtendWithFunctionAttrs,
+    };
 
     /// Should test matrix tests render without take in account MatrixInfo to RsTestInfo
     /// transformation
@@ -1696,6 +1868,216 @@ mod matrix_cases_should {
         );
     }
 
+    #[test]
+    fn render_just_the_pairwise_combinations_with_the_same_names() {
+        let mut item_fn: ItemFn = r#"
+            #[pairwise]
+            fn test(
+                #[values(1, 2, 3)] first: u32,
+                #[values(1, 2, 3)] second: u32,
+                #[values(1, 2, 3)] third: u32,
+            ) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let tokens = matrix(item_fn, info);
+
+        let tg = TestsGroup::from(tokens);
+        let tests = tg.module.get_all_tests();
+        assert!(tests.len() < 27);
+        assert_eq!(
+            vec!["first_1_1", "first_2_2", "first_3_3"],
+            tg.module.get_modules().names()
+        );
+        assert_eq!(
+            vec!["second_1_1", "second_2_2", "second_3_3"],
+            tg.module.get_modules()[0].get_modules().names()
+        );
+        assert!(tests.names().iter().all(|name| name.starts_with("third_")));
+    }
+
+    #[rstest]
+    #[case::all(r#"fn test(#[values(1, 2, 3)] a: u32, #[values(1, 2)] b: u32) {}"#)]
+    #[case::pairwise(
+        r#"#[pairwise] fn test(#[values(1, 2, 3)] a: u32, #[values(1, 2, 3)] b: u32, #[values(1, 2, 3)] c: u32) {}"#
+    )]
+    #[case::zip(r#"#[zip] fn test(#[values(1, 2, 3)] a: u32, #[values(2, 4, 6)] b: u32) {}"#)]
+    #[case::with_cases(
+        r#"#[case(1)] #[case(2)] fn test(#[case] c: u32, #[values(1, 2, 3)] a: u32) {}"#
+    )]
+    fn count_the_tests_of_the_group(#[case] code: &str) {
+        let mut item_fn: ItemFn = code.ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let tokens = matrix(item_fn, info);
+
+        let tests = TestsGroup::from(tokens.clone())
+            .module
+            .get_all_tests()
+            .len();
+        assert_in!(
+            tokens.to_string(),
+            format!("Group :: new (module_path ! () , {tests}usize)")
+        );
+    }
+
+    #[test]
+    fn render_the_zipped_values_with_the_same_names() {
+        let mut item_fn: ItemFn = r#"
+            #[zip]
+            fn test(#[values(1, 2, 3)] input: u32, #[values(2, 4, 6)] expected: u32) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let tokens = matrix(item_fn, info);
+
+        let tg = TestsGroup::from(tokens);
+        assert_eq!(
+            vec!["input_1_1", "input_2_2", "input_3_3"],
+            tg.module.get_modules().names()
+        );
+        assert_eq!(
+            vec![
+                vec!["expected_1_2"],
+                vec!["expected_2_4"],
+                vec!["expected_3_6"]
+            ],
+            tg.module
+                .get_modules()
+                .iter()
+                .map(|m| m.get_tests().names())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn name_the_modules_by_the_values_names() {
+        let mut item_fn: ItemFn = r#"
+            fn test(
+                #[values(small => vec![1], huge => (0..1000).collect())] input: Vec<u32>,
+                #[values(1, fast => Speed::new(100))] speed: Speed,
+            ) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let tokens = matrix(item_fn, info);
+
+        let tg = TestsGroup::from(tokens);
+        assert_eq!(
+            vec!["input_1_small", "input_2_huge"],
+            tg.module.get_modules().names()
+        );
+        assert_eq!(
+            vec!["speed_1_1", "speed_2_fast"],
+            tg.module.get_modules()[0].get_tests().names()
+        );
+    }
+
+    #[test]
+    fn render_a_test_for_each_element_of_a_const() {
+        let mut item_fn: ItemFn = r#"
+            fn test(#[values(from = data::VALUES; len = 2)] a: u32) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let tokens = matrix(item_fn, info);
+
+        let tg = TestsGroup::from(tokens.clone());
+        let tests = tg.module.get_tests();
+        assert_eq!(
+            vec!["a_1_data__VALUES_0", "a_2_data__VALUES_1"],
+            tests.names()
+        );
+        assert_in!(
+            tests[1].block.display_code(),
+            "let a = :: core :: clone :: Clone :: clone (& data :: VALUES [1usize]) ;"
+        );
+        assert_in!(
+            tokens.to_string(),
+            "const _ : () = assert ! (data :: VALUES . len () == 2"
+        );
+    }
+
+    #[test]
+    fn tag_the_tests_by_the_function_cases_and_values() {
+        let mut item_fn: ItemFn = r#"
+            #[tag("db")]
+            #[case(1)]
+            #[case(2)]
+            #[tag("fast")]
+            fn test(#[case] a: u32, #[values(1, #[tag("slow")] 2)] b: u32) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let tokens = matrix(item_fn, info);
+
+        let tg = TestsGroup::from(tokens);
+        assert_eq!(
+            vec!["b_1_1_tag_db_tag_fast", "b_2_2_tag_db_tag_fast_tag_slow"],
+            tg.module.get_modules()[0].get_tests().names()
+        );
+        assert_eq!(
+            vec!["b_1_1_tag_fast", "b_2_2_tag_fast_tag_slow"],
+            tg.module.get_modules()[1].get_tests().names()
+        );
+    }
+
+    #[test]
+    fn skip_the_excluded_combinations() {
+        let mut item_fn: ItemFn = r#"
+            #[exclude_when(state == State::Terminated && event == Event::Fatal)]
+            #[exclude_when(event == Event::Other && n > 1)]
+            fn test(
+                #[values(State::Running, State::Terminated)] state: State,
+                #[values(Event::Fatal, Event::Other)] event: Event,
+                #[values(1, 2)] n: u32,
+            ) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let tokens = matrix(item_fn, info);
+
+        let tg = TestsGroup::from(tokens);
+        let tests = tg.module.get_all_tests();
+        assert_eq!(8, tests.len());
+        let ignored = tests
+            .iter()
+            .filter(|t| t.attrs.iter().any(|a| a.path().is_ident("ignore")))
+            .collect::<Vec<_>>();
+        // The `Terminated` and `Fatal` ones
+        assert_eq!(2, ignored.len());
+        assert!(ignored.iter().all(|t| t.attrs.iter().any(|a| a
+            .display_code()
+            .contains("excluded by #[exclude_when(state == State :: Terminated"))));
+        // `n > 1` cannot be evaluated at compile time: the `Other` ones check it when
+        // they run
+        let runtime_checks = tests
+            .iter()
+            .filter(|t| {
+                t.block.display_code().contains(
+                    "if event == Event :: Other && n > 1 { return rstest :: exclude :: skip",
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(4, runtime_checks.len());
+        assert!(runtime_checks
+            .iter()
+            .all(|t| !t.attrs.iter().any(|a| a.path().is_ident("ignore"))));
+    }
+
     #[test]
     fn pad_case_index() {
         let item_fn: ItemFn =
@@ -1731,6 +2113,68 @@ mod matrix_cases_should {
         assert_in!(functions[0], "third_1");
         assert_in!(functions[1], "third_2");
     }
+
+    fn named_tests(template: &str, item_fn: &str) -> TokenStream {
+        let mut item_fn: ItemFn = item_fn.ast();
+        let mut info: RsTestInfo = format!(r#"name = "{template}""#).ast();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        named(item_fn, info)
+    }
+
+    #[test]
+    fn render_flat_tests_named_by_the_template() {
+        let tokens = named_tests(
+            "{op}_{case}_{n}",
+            r#"
+            #[case::small(1)]
+            #[case(10)]
+            fn test(#[case] n: u32, #[values(Op::Add, Op::Mul)] op: Op) {}
+            "#,
+        );
+
+        let tg = TestsGroup::from(tokens);
+        assert!(tg.module.get_modules().is_empty());
+        assert_eq!(
+            vec![
+                "Op__Add_small_1",
+                "Op__Mul_small_1",
+                "Op__Add_2_10",
+                "Op__Mul_2_10"
+            ],
+            tg.module.get_tests().names()
+        );
+    }
+
+    #[test]
+    fn render_just_the_selected_combinations() {
+        let tokens = named_tests(
+            "{input}_doubled_is_{expected}",
+            r#"
+            #[zip]
+            fn test(#[values(1, 2)] input: u32, #[values(2, 4)] expected: u32) {}
+            "#,
+        );
+
+        let tg = TestsGroup::from(tokens);
+        assert_eq!(
+            vec!["_1_doubled_is_2", "_2_doubled_is_4"],
+            tg.module.get_tests().names()
+        );
+    }
+
+    #[test]
+    fn return_an_error_for_duplicated_names() {
+        let tokens = named_tests(
+            "with_{a}",
+            "fn test(#[values(1, 2)] a: u32, #[values(3, 4)] b: u32) {}",
+        );
+
+        assert_in!(
+            tokens.to_string(),
+            "The test name `with_1` is generated more than once"
+        );
+    }
 }
 
 mod complete_should {
@@ -1864,3 +2308,55 @@ mod complete_should {
         }
     }
 }
+
+mod params_should {
+    use rstest_test::assert_in;
+
+    use super::{assert_eq, *};
+    use crate::parse::{params::ParamsArg, ExtendWithFunctionAttrs};
+
+    #[test]
+    fn ask_the_fixture_to_expand_the_test() {
+        let test = quote! { fn test(#[params] #[from(fixtures::backend)] storage: Storage) {} };
+        let params = ParamsArg {
+            arg: ident("storage"),
+            fixture: path("fixtures::backend"),
+            variants: None,
+        };
+
+        let tokens = super::super::params(quote! { #[case(42)] }, test.clone(), &params);
+
+        assert_eq!(
+            tokens.to_string(),
+            quote! { fixtures::backend! { [storage] [rstest::rstest(#[case(42)])] #test } }
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn render_a_test_for_each_fixture_variant() {
+        let mut item_fn: ItemFn = r#"
+            #[__rstest_params(backend: "memory", "sqlite")]
+            fn test(#[params] backend: Storage) {}
+        "#
+        .ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+        let tokens = matrix(item_fn, info);
+
+        let output = TestsGroup::from(tokens);
+        let tests = output.module.get_all_tests();
+        assert_eq!(
+            vec!["backend_1_memory", "backend_2_sqlite"],
+            tests
+                .iter()
+                .map(|t| t.sig.ident.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_in!(
+            tests[1].block.display_code(),
+            "let backend = backend :: param (1usize) ;"
+        );
+    }
+}
@@ -319,6 +319,29 @@ impl crate::parse::fixture::FixtureInfo {
         self.arguments.set_once(Some(attr("#[once]")));
         self
     }
+
+    pub(crate) fn with_teardown(mut self, teardown: &str) -> Self {
+        use crate::parse::teardown::TearDown;
+
+        let attr = attr(teardown);
+        let teardown = match attr.meta {
+            syn::Meta::List(_) => TearDown::Expr(attr.clone(), attr.parse_args().unwrap()),
+            _ => TearDown::Guard(attr),
+        };
+        self.arguments.set_teardown(Some(teardown));
+        self
+    }
+
+    pub(crate) fn with_scope(mut self, kind: crate::parse::scope::ScopeKind) -> Self {
+        use crate::parse::scope::{FixtureScope, ScopeKind};
+
+        let attr = match kind {
+            ScopeKind::Group => attr("#[scope(group)]"),
+            ScopeKind::Module => attr("#[scope(module)]"),
+        };
+        self.arguments.set_scope(Some(FixtureScope { attr, kind }));
+        self
+    }
 }
 
 pub(crate) fn await_argument_code_string(arg_name: &str) -> String {
@@ -261,6 +261,45 @@ fn first_type_path_segment_ident(t: &Type) -> Option<&Ident> {
     }
 }
 
+/// Search for some `impl Trait` in a function signature.
+#[derive(Default)]
+pub(crate) struct SearchImpl(bool);
+
+impl<'ast> syn::visit::Visit<'ast> for SearchImpl {
+    fn visit_type(&mut self, i: &'ast syn::Type) {
+        if self.0 {
+            return;
+        }
+        if let syn::Type::ImplTrait(_) = i {
+            self.0 = true
+        }
+        syn::visit::visit_type(self, i);
+    }
+}
+
+impl SearchImpl {
+    pub(crate) fn function_has_some_impl(f: &ItemFn) -> bool {
+        use syn::visit::Visit;
+        let mut s = SearchImpl::default();
+        s.visit_item_fn(f);
+        s.0
+    }
+
+    pub(crate) fn inputs_have_some_impl(f: &ItemFn) -> bool {
+        use syn::visit::Visit;
+        let mut s = SearchImpl::default();
+        f.sig.inputs.iter().for_each(|arg| s.visit_fn_arg(arg));
+        s.0
+    }
+
+    pub(crate) fn output_has_some_impl(output: &ReturnType) -> bool {
+        use syn::visit::Visit;
+        let mut s = SearchImpl::default();
+        s.visit_return_type(output);
+        s.0
+    }
+}
+
 pub(crate) fn fn_arg_mutability(arg: &FnArg) -> Option<syn::token::Mut> {
     match arg {
         FnArg::Typed(syn::PatType { pat, .. }) => match pat.as_ref() {
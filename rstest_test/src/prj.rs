@@ -49,6 +49,8 @@ pub struct Project {
     nocapture: bool,
     ws: Arc<std::sync::RwLock<()>>,
     default_timeout: Option<u64>,
+    envs: Vec<(String, String)>,
+    test_args: Vec<String>,
 }
 
 impl Project {
@@ -62,6 +64,8 @@ impl Project {
             nocapture: false,
             ws: Arc::new(std::sync::RwLock::new(())),
             default_timeout: Default::default(),
+            envs: Default::default(),
+            test_args: Default::default(),
         }
         .create()
     }
@@ -85,6 +89,8 @@ impl Project {
             nocapture: self.nocapture,
             ws: self.ws.clone(),
             default_timeout: Default::default(),
+            envs: Default::default(),
+            test_args: Default::default(),
         }
         .create()
     }
@@ -108,14 +114,17 @@ impl Project {
         if let Some(timeout) = self.default_timeout {
             cmd.env("RSTEST_TIMEOUT", timeout.to_string());
         }
+        cmd.envs(self.envs.iter().map(|(key, value)| (key, value)));
 
         cmd.current_dir(&self.path())
             .arg(&self.cargo_channel_arg())
             .arg("test");
 
+        cmd.arg("--");
         if self.nocapture {
-            cmd.args(["--", "--nocapture"]);
+            cmd.arg("--nocapture");
         }
+        cmd.args(&self.test_args);
 
         cmd.output()
     }
@@ -190,6 +199,15 @@ impl Project {
         self.save_cargo_toml(&doc);
     }
 
+    pub fn set_dependency(&self, crate_name: &str, attrs: &str) {
+        let mut doc = self.read_cargo_toml();
+
+        doc["dependencies"].or_insert(Item::Table(Table::new()))[crate_name] =
+            Item::Value(attrs.parse().unwrap());
+
+        self.save_cargo_toml(&doc);
+    }
+
     pub fn add_path_dependency(&self, name: &str, path: &str) {
         self.add_dependency(name, format!(r#"{{path="{path}"}}"#).as_str());
     }
@@ -262,4 +280,12 @@ impl Project {
     pub fn set_default_timeout(&mut self, timeout: u64) {
         self.default_timeout = Some(timeout);
     }
+
+    pub fn set_env(&mut self, key: &str, value: &str) {
+        self.envs.push((key.to_owned(), value.to_owned()));
+    }
+
+    pub fn add_test_arg(&mut self, arg: &str) {
+        self.test_args.push(arg.to_owned());
+    }
 }